@@ -0,0 +1,347 @@
+//! Security audit of scanned networks.
+//!
+//! ```no_run
+//! use wifi_scan::audit::{audit, Severity};
+//!
+//! let report = audit(&wifi_scan::scan().unwrap());
+//! for network in &report.networks {
+//!     for finding in network.findings.iter().filter(|f| f.severity >= Severity::High) {
+//!         println!("{} ({}): {}", network.ssid, network.bssid, finding);
+//!     }
+//! }
+//! println!("{}", report.summary);
+//! ```
+//!
+//! Checks based on cipher suites and protected management frames need the raw
+//! information elements and are skipped on platforms that don't expose them.
+
+use std::{cmp::Reverse, fmt};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    security::{self, Rsn},
+    wps::WpsState,
+    Wifi, WifiSecurity,
+};
+
+/// Severity of a finding, from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Kinds of security issues the audit detects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FindingKind {
+    /// WEP encryption, broken and recoverable within minutes
+    Wep,
+    /// TKIP as pairwise or group cipher
+    Tkip,
+    /// WPA (version 1)
+    Wpa1,
+    /// Open network without Opportunistic Wireless Encryption
+    OpenWithoutOwe,
+    /// Wi-Fi Protected Setup accepts PINs on a configured access point that isn't locked
+    WpsEnabled,
+    /// WPA3 without the required protected management frames
+    Wpa3WithoutPmf,
+    /// Older and newer security generations are offered side by side
+    TransitionMode,
+    /// The SSID is not broadcast
+    HiddenSsid,
+}
+
+/// A security issue of a network
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub severity: Severity,
+    /// Human readable explanation
+    pub description: String,
+}
+
+/// Findings of a single network
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetworkAudit {
+    pub ssid: String,
+    pub bssid: String,
+    pub findings: Vec<Finding>,
+}
+
+/// Statistics over all audited networks
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuditSummary {
+    /// Number of audited networks
+    pub networks: usize,
+    /// Number of networks with at least one finding above `Info`
+    pub networks_at_risk: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub info: usize,
+    /// Number of networks per finding kind, most frequent first
+    pub by_kind: Vec<(FindingKind, usize)>,
+}
+
+/// Audit of a whole scan
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuditReport {
+    pub networks: Vec<NetworkAudit>,
+    pub summary: AuditSummary,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "Info"),
+            Severity::Low => write!(f, "Low"),
+            Severity::Medium => write!(f, "Medium"),
+            Severity::High => write!(f, "High"),
+            Severity::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+impl FindingKind {
+    /// Returns the default severity of the finding
+    pub fn severity(&self) -> Severity {
+        match self {
+            FindingKind::Wep => Severity::Critical,
+            FindingKind::Tkip | FindingKind::Wpa1 => Severity::High,
+            FindingKind::OpenWithoutOwe | FindingKind::WpsEnabled | FindingKind::Wpa3WithoutPmf => {
+                Severity::Medium
+            }
+            FindingKind::TransitionMode => Severity::Low,
+            FindingKind::HiddenSsid => Severity::Info,
+        }
+    }
+
+    /// Returns a human readable explanation of the finding
+    pub fn description(&self) -> &'static str {
+        match self {
+            FindingKind::Wep => "WEP encryption is broken and can be cracked within minutes",
+            FindingKind::Tkip => "TKIP is deprecated and vulnerable to packet injection",
+            FindingKind::Wpa1 => "WPA (version 1) is deprecated",
+            FindingKind::OpenWithoutOwe => {
+                "Open network without Opportunistic Wireless Encryption, traffic is unencrypted"
+            }
+            FindingKind::WpsEnabled => "WPS PIN setup is enabled and may allow PIN brute forcing",
+            FindingKind::Wpa3WithoutPmf => {
+                "WPA3 without required protected management frames allows deauthentication attacks"
+            }
+            FindingKind::TransitionMode => {
+                "Mixed security mode allows clients to be downgraded to the weaker security"
+            }
+            FindingKind::HiddenSsid => {
+                "Hidden SSIDs provide no security and make clients probe for the network"
+            }
+        }
+    }
+}
+
+impl fmt::Display for FindingKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FindingKind::Wep => write!(f, "WEP"),
+            FindingKind::Tkip => write!(f, "TKIP"),
+            FindingKind::Wpa1 => write!(f, "WPA1"),
+            FindingKind::OpenWithoutOwe => write!(f, "Open without OWE"),
+            FindingKind::WpsEnabled => write!(f, "WPS enabled"),
+            FindingKind::Wpa3WithoutPmf => write!(f, "WPA3 without PMF"),
+            FindingKind::TransitionMode => write!(f, "Transition mode"),
+            FindingKind::HiddenSsid => write!(f, "Hidden SSID"),
+        }
+    }
+}
+
+impl From<FindingKind> for Finding {
+    fn from(kind: FindingKind) -> Self {
+        Finding {
+            kind,
+            severity: kind.severity(),
+            description: kind.description().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.kind, self.description)
+    }
+}
+
+impl NetworkAudit {
+    /// Returns the severity of the most severe finding
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+}
+
+impl fmt::Display for AuditSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} networks, {} at risk | Critical: {} | High: {} | Medium: {} | Low: {} | Info: {}",
+            self.networks,
+            self.networks_at_risk,
+            self.critical,
+            self.high,
+            self.medium,
+            self.low,
+            self.info
+        )
+    }
+}
+
+/// Audits every network of a scan and summarises the findings
+pub fn audit(wifis: &[Wifi]) -> AuditReport {
+    let networks: Vec<NetworkAudit> = wifis
+        .iter()
+        .map(|wifi| NetworkAudit {
+            ssid: wifi.ssid.clone(),
+            bssid: wifi.mac.clone(),
+            findings: findings(wifi),
+        })
+        .collect();
+
+    let mut summary = AuditSummary {
+        networks: networks.len(),
+        ..AuditSummary::default()
+    };
+    for network in &networks {
+        if network.max_severity().is_some_and(|s| s > Severity::Info) {
+            summary.networks_at_risk += 1;
+        }
+
+        for finding in &network.findings {
+            match finding.severity {
+                Severity::Critical => summary.critical += 1,
+                Severity::High => summary.high += 1,
+                Severity::Medium => summary.medium += 1,
+                Severity::Low => summary.low += 1,
+                Severity::Info => summary.info += 1,
+            }
+
+            match summary.by_kind.iter_mut().find(|(k, _)| *k == finding.kind) {
+                Some((_, count)) => *count += 1,
+                None => summary.by_kind.push((finding.kind, 1)),
+            }
+        }
+    }
+    summary.by_kind.sort_by_key(|(_, count)| Reverse(*count));
+
+    AuditReport { networks, summary }
+}
+
+/// Returns `true` if the AKM suite of an RSN element is a WPA2 one: EAP, PSK, their FT
+/// variants and PSK-SHA256. EAP-SHA256 is WPA2-Enterprise unless PMF is required.
+fn is_wpa2_akm(akm: u8, rsn: &Rsn) -> bool {
+    match akm {
+        1..=4 | 6 => true,
+        5 => !rsn.mfp_required,
+        _ => false,
+    }
+}
+
+/// Returns `true` if the AKM suite of an RSN element is a WPA3 one: SAE and SAE-EXT-KEY
+/// with their FT variants, Suite B and OWE. EAP-SHA256 is WPA3-Enterprise only if PMF
+/// is required.
+fn is_wpa3_akm(akm: u8, rsn: &Rsn) -> bool {
+    match akm {
+        8 | 9 | 11 | 12 | 18 | 24 | 25 => true,
+        5 => rsn.mfp_required,
+        _ => false,
+    }
+}
+
+/// Returns the security findings of a network, most severe first
+pub(crate) fn findings(wifi: &Wifi) -> Vec<Finding> {
+    let ies = &wifi.information_elements;
+    let rsn = security::rsn(ies);
+    let wpa = security::wpa(ies);
+    let elements = [rsn.as_ref(), wpa.as_ref()];
+
+    let has = |security: &[WifiSecurity]| wifi.security.iter().any(|s| security.contains(s));
+    let wpa1 = has(&[WifiSecurity::WpaPersonalPsk, WifiSecurity::WpaEnterpriseEap]);
+
+    let mut kinds = Vec::new();
+
+    let wep_cipher = |e: &Rsn| {
+        e.group_cipher
+            .is_some_and(|c| c == security::CIPHER_WEP40 || c == security::CIPHER_WEP104)
+    };
+    if has(&[WifiSecurity::Wep]) || elements.iter().flatten().any(|e| wep_cipher(e)) {
+        kinds.push(FindingKind::Wep);
+    }
+
+    let tkip = |e: &Rsn| {
+        e.group_cipher == Some(security::CIPHER_TKIP)
+            || e.pairwise_ciphers.contains(&security::CIPHER_TKIP)
+    };
+    if elements.iter().flatten().any(|e| tkip(e)) {
+        kinds.push(FindingKind::Tkip);
+    }
+
+    if wpa1 || wpa.is_some() {
+        kinds.push(FindingKind::Wpa1);
+    }
+
+    let owe = rsn
+        .as_ref()
+        .is_some_and(|r| r.akms.contains(&security::AKM_OWE))
+        || security::has_owe_transition(ies);
+    // some platforms report networks without an RSN element as open, even with a WPA element
+    if wifi.is_open() && rsn.is_none() && wpa.is_none() && !owe {
+        kinds.push(FindingKind::OpenWithoutOwe);
+    }
+
+    // unconfigured access points only offer WPS for their initial setup
+    let wps_pin = wifi
+        .wps()
+        .is_some_and(|wps| wps.state != WpsState::Unconfigured && wps.is_pin_enabled());
+    if wps_pin {
+        kinds.push(FindingKind::WpsEnabled);
+    }
+
+    // the securities derived from the RSN element can't tell WPA2-Enterprise with SHA-256
+    // from WPA3-Enterprise, so the AKM suites decide when the element is available
+    let (wpa2, wpa3) = match &rsn {
+        Some(rsn) => (
+            rsn.akms.iter().any(|akm| is_wpa2_akm(*akm, rsn)),
+            rsn.akms.iter().any(|akm| is_wpa3_akm(*akm, rsn)),
+        ),
+        None => (wifi.is_wpa2(), wifi.is_wpa3()),
+    };
+
+    if let Some(rsn) = &rsn {
+        // transition mode networks only have to be capable of PMF, WPA3-only ones require it
+        if wpa3 && (!rsn.mfp_capable || !wpa2 && !rsn.mfp_required) {
+            kinds.push(FindingKind::Wpa3WithoutPmf);
+        }
+    }
+
+    if ((wpa1 || wpa.is_some()) && (wpa2 || wpa3)) || (wpa2 && wpa3) {
+        kinds.push(FindingKind::TransitionMode);
+    }
+
+    if wifi.is_hidden() {
+        kinds.push(FindingKind::HiddenSsid);
+    }
+
+    let mut findings: Vec<Finding> = kinds.into_iter().map(Finding::from).collect();
+    findings.sort_by_key(|f| Reverse(f.severity));
+    findings
+}
@@ -0,0 +1,314 @@
+//! Channel congestion analysis and channel recommendation.
+//!
+//! ```no_run
+//! use wifi_scan::{congestion::ChannelAnalyzer, Band};
+//!
+//! let wifis = wifi_scan::scan().unwrap();
+//! let analyzer = ChannelAnalyzer::new(&wifis).exclude_dfs(true);
+//! if let Some(best) = analyzer.recommend(Band::FiveGhz, 80) {
+//!     println!("Use channel {} ({})", best.channel, best);
+//! }
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ie, phy, signal, Band, Wifi};
+
+/// Load assumed for networks that don't advertise their channel utilisation
+const UNKNOWN_UTILIZATION: f64 = 0.5;
+/// Signal quality in percent assumed for networks with an unknown signal level
+const UNKNOWN_QUALITY: u8 = 50;
+/// Minimum load of a network with a known channel utilisation
+const MIN_UTILIZATION: f64 = 0.1;
+
+const CHANNELS_2GHZ: [u32; 13] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+const CHANNELS_5GHZ: [u32; 28] = [
+    36, 40, 44, 48, 52, 56, 60, 64, 100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144,
+    149, 153, 157, 161, 165, 169, 173, 177,
+];
+
+/// Contents of the BSS Load element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BssLoad {
+    /// Number of associated stations
+    pub station_count: u16,
+    /// Share of time the AP sensed the medium busy, from 0 to 255
+    pub channel_utilization: u8,
+    /// Remaining medium time available via explicit admission control, in units of 32 µs/s
+    pub available_admission_capacity: u16,
+}
+
+/// Estimated congestion of a channel
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChannelLoad {
+    /// Band of the channel
+    pub band: Band,
+    /// Lowest 20 MHz channel of the channel
+    pub channel: u32,
+    /// Channel width in MHz
+    pub width: u16,
+    /// 20 MHz channels the channel is made of
+    pub channels: Vec<u32>,
+    /// Center frequency in MHz
+    pub center_frequency: u32,
+    /// Sum of the overlapping networks, each weighted by signal quality (0.0 to 1.0),
+    /// channel utilisation and share of its bandwidth overlapping the channel
+    pub load: f64,
+    /// Number of networks overlapping the channel
+    pub bss_count: usize,
+    /// `true` if the channel requires dynamic frequency selection (radar detection)
+    pub dfs: bool,
+}
+
+/// Analyses the channel congestion of scan results
+#[derive(Debug, Clone)]
+pub struct ChannelAnalyzer<'a> {
+    wifis: &'a [Wifi],
+    exclude_dfs: bool,
+}
+
+/// Frequency range occupied by a network
+struct Occupied {
+    band: Band,
+    low: f64,
+    high: f64,
+    weight: f64,
+}
+
+impl BssLoad {
+    /// Parses the BSS Load element from raw information elements
+    pub(crate) fn from_elements(ies: &[u8]) -> Option<BssLoad> {
+        let data = ie::find(ies, ie::BSS_LOAD).filter(|d| d.len() >= 5)?;
+        Some(BssLoad {
+            station_count: u16::from_le_bytes([data[0], data[1]]),
+            channel_utilization: data[2],
+            available_admission_capacity: u16::from_le_bytes([data[3], data[4]]),
+        })
+    }
+
+    /// Returns the channel utilisation in percent
+    pub fn utilization_percent(&self) -> f64 {
+        self.channel_utilization as f64 * 100.0 / 255.0
+    }
+}
+
+impl fmt::Display for ChannelLoad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} channel {} @ {} MHz: load {:.2} from {} networks{}",
+            self.band,
+            self.channel,
+            self.width,
+            self.load,
+            self.bss_count,
+            if self.dfs { " (DFS)" } else { "" }
+        )
+    }
+}
+
+impl<'a> ChannelAnalyzer<'a> {
+    pub fn new(wifis: &'a [Wifi]) -> Self {
+        ChannelAnalyzer {
+            wifis,
+            exclude_dfs: false,
+        }
+    }
+
+    /// Excludes channels requiring dynamic frequency selection from the results
+    pub fn exclude_dfs(mut self, exclude: bool) -> Self {
+        self.exclude_dfs = exclude;
+        self
+    }
+
+    /// Returns the load of every channel of the given band and width, in channel order.
+    /// Supported widths are 20 and 40 MHz on 2.4 GHz, up to 160 MHz on 5 GHz and
+    /// up to 320 MHz on 6 GHz; other widths return no channels.
+    pub fn loads(&self, band: Band, width: u16) -> Vec<ChannelLoad> {
+        let occupied: Vec<Occupied> = self.wifis.iter().filter_map(occupied).collect();
+
+        candidates(band, width)
+            .into_iter()
+            .filter(|c| !(self.exclude_dfs && c.dfs))
+            .map(|mut candidate| {
+                let half = occupied_width(band, width) / 2.0;
+                let low = candidate.center_frequency as f64 - half;
+                let high = candidate.center_frequency as f64 + half;
+
+                for bss in occupied.iter().filter(|o| o.band == band) {
+                    let overlap = high.min(bss.high) - low.max(bss.low);
+                    if overlap > 0.0 {
+                        candidate.load += bss.weight * overlap / (bss.high - bss.low);
+                        candidate.bss_count += 1;
+                    }
+                }
+                candidate
+            })
+            .collect()
+    }
+
+    /// Returns the least congested channel of the given band and width.
+    /// Ties are resolved in favour of the lower channel.
+    pub fn recommend(&self, band: Band, width: u16) -> Option<ChannelLoad> {
+        self.loads(band, width)
+            .into_iter()
+            .min_by(|a, b| a.load.total_cmp(&b.load))
+    }
+
+    /// Returns the least congested channel for every supported band and width
+    pub fn recommendations(&self) -> Vec<ChannelLoad> {
+        let widths: [(Band, &[u16]); 3] = [
+            (Band::TwoPointFourGhz, &[20, 40]),
+            (Band::FiveGhz, &[20, 40, 80, 160]),
+            (Band::SixGhz, &[20, 40, 80, 160, 320]),
+        ];
+
+        widths
+            .iter()
+            .flat_map(|(band, widths)| widths.iter().map(move |width| (*band, *width)))
+            .filter_map(|(band, width)| self.recommend(band, width))
+            .collect()
+    }
+}
+
+/// Returns the width in MHz a channel occupies. 20 MHz channels on 2.4 GHz
+/// are treated as 22 MHz wide as legacy DSSS transmissions are.
+fn occupied_width(band: Band, width: u16) -> f64 {
+    if band == Band::TwoPointFourGhz && width == 20 {
+        22.0
+    } else {
+        width as f64
+    }
+}
+
+/// Returns the 20 MHz channels of a band
+fn channels(band: Band) -> Vec<u32> {
+    match band {
+        Band::TwoPointFourGhz => CHANNELS_2GHZ.to_vec(),
+        Band::FiveGhz => CHANNELS_5GHZ.to_vec(),
+        Band::SixGhz => (1..=233).step_by(4).collect(),
+    }
+}
+
+/// Returns the lowest 20 MHz channel of the aligned block of `width` containing `channel`
+/// on 5 and 6 GHz. `shifted` selects the 320-2 channelization on 6 GHz, whose 320 MHz
+/// channels start 160 MHz above those of 320-1.
+fn block_start(band: Band, channel: u32, width: u16, shifted: bool) -> u32 {
+    let base = match band {
+        Band::FiveGhz if channel >= 149 => 149,
+        Band::FiveGhz => 36,
+        Band::SixGhz if shifted => 33,
+        _ => 1,
+    };
+    let span = width as u32 / 5;
+    base + (channel.saturating_sub(base) / span) * span
+}
+
+/// Returns `true` if a 5 GHz channel requires dynamic frequency selection
+fn is_dfs(band: Band, channel: u32) -> bool {
+    band == Band::FiveGhz && (52..=144).contains(&channel)
+}
+
+/// Returns all channels of the given band and width with zero load
+fn candidates(band: Band, width: u16) -> Vec<ChannelLoad> {
+    let all = channels(band);
+    let supported = match band {
+        Band::TwoPointFourGhz => matches!(width, 20 | 40),
+        Band::FiveGhz => matches!(width, 20 | 40 | 80 | 160),
+        Band::SixGhz => matches!(width, 20 | 40 | 80 | 160 | 320),
+    };
+    if !supported {
+        return Vec::new();
+    }
+
+    let blocks: Vec<Vec<u32>> = match (band, width) {
+        (_, 20) => all.iter().map(|c| vec![*c]).collect(),
+        // 40 MHz on 2.4 GHz: primary channel with the secondary channel four channels above
+        (Band::TwoPointFourGhz, _) => all
+            .iter()
+            .filter(|c| all.contains(&(*c + 4)))
+            .map(|c| vec![*c, c + 4])
+            .collect(),
+        _ => {
+            let span = width as u32 / 5;
+            let shifted = band == Band::SixGhz && width == 320;
+            all.iter()
+                .filter(|c| {
+                    block_start(band, **c, width, false) == **c
+                        || shifted && block_start(band, **c, width, true) == **c
+                })
+                .map(|start| (0..span / 4).map(|i| start + i * 4).collect::<Vec<u32>>())
+                .filter(|block| block.iter().all(|c| all.contains(c)))
+                .collect()
+        }
+    };
+
+    blocks
+        .into_iter()
+        .map(|block| {
+            let first = band.channel_frequency(block[0]);
+            let last = band.channel_frequency(block[block.len() - 1]);
+            ChannelLoad {
+                band,
+                channel: block[0],
+                width,
+                center_frequency: (first + last) / 2,
+                load: 0.0,
+                bss_count: 0,
+                dfs: block.iter().any(|c| is_dfs(band, *c)),
+                channels: block,
+            }
+        })
+        .collect()
+}
+
+/// Returns the frequency range a network occupies, weighted by its signal quality
+/// and channel utilisation
+fn occupied(wifi: &Wifi) -> Option<Occupied> {
+    let band = wifi.band()?;
+    let primary = band.channel_frequency(wifi.channel) as f64;
+    let width = phy::operating_width(&wifi.information_elements).unwrap_or(20);
+
+    let eht_center = phy::eht_center_channel(&wifi.information_elements);
+
+    let center = match (band, width, eht_center) {
+        (_, 20, _) => primary,
+        (Band::TwoPointFourGhz, _, _) => {
+            // secondary channel offset of the HT Operation element
+            let offset = ie::find(&wifi.information_elements, ie::HT_OPERATION)
+                .and_then(|ht| ht.get(1).copied())
+                .map_or(0, |info| info & 0x03);
+            match offset {
+                1 => primary + 10.0,
+                3 => primary - 10.0,
+                _ => primary,
+            }
+        }
+        // 320-1 and 320-2 channels overlap, only the EHT Operation element tells them apart
+        (_, 320, Some(center)) => band.channel_frequency(center) as f64,
+        _ => {
+            let start =
+                band.channel_frequency(block_start(band, wifi.channel, width, false)) as f64;
+            start - 10.0 + width as f64 / 2.0
+        }
+    };
+
+    let quality = signal::quality(wifi.signal_level).unwrap_or(UNKNOWN_QUALITY) as f64 / 100.0;
+    let utilization = match BssLoad::from_elements(&wifi.information_elements) {
+        Some(load) => (load.channel_utilization as f64 / 255.0).max(MIN_UTILIZATION),
+        None => UNKNOWN_UTILIZATION,
+    };
+
+    let half = occupied_width(band, width) / 2.0;
+    Some(Occupied {
+        band,
+        low: center - half,
+        high: center + half,
+        weight: quality * utilization,
+    })
+}
@@ -0,0 +1,219 @@
+//! Rough distance estimation from signal levels using path-loss models.
+//!
+//! ```no_run
+//! use wifi_scan::distance::DistanceConfig;
+//!
+//! let config = DistanceConfig::indoor();
+//! for wifi in wifi_scan::scan().unwrap() {
+//!     if let Some(estimate) = wifi.distance(&config) {
+//!         println!("{}: {}", wifi.ssid, estimate);
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ie, Band, Wifi};
+
+/// Transmit power in dBm assumed if none is configured or advertised
+pub const DEFAULT_TX_POWER: f64 = 20.0;
+
+/// Path-loss model used to convert signal loss into distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PathLossModel {
+    /// Free-space path loss, suitable for line of sight outdoors
+    FreeSpace,
+    /// Log-distance path loss with free-space loss up to `reference_distance` metres
+    /// and a loss of `10 * exponent` dB per decade beyond it.
+    /// Typical exponents are 2 (free space) to 4 (offices with many walls).
+    LogDistance {
+        exponent: f64,
+        reference_distance: f64,
+    },
+}
+
+/// Settings for distance estimation
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistanceConfig {
+    /// Transmit power of the access point in dBm.
+    /// If `None`, the power advertised by the AP (TPC Report or Country element) is used,
+    /// falling back to [`DEFAULT_TX_POWER`].
+    pub tx_power: Option<f64>,
+    /// Path-loss model
+    pub model: PathLossModel,
+    /// Standard deviation of the signal level in dB (shadowing), used for the uncertainty interval
+    pub shadowing: f64,
+}
+
+/// Source of the transmit power used for an estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TxPowerSource {
+    /// Configured in [`DistanceConfig::tx_power`]
+    Configured,
+    /// Transmit power from the TPC Report element
+    TpcReport,
+    /// Maximum transmit power from the Country element, reduced by the Power Constraint element
+    Country,
+    /// [`DEFAULT_TX_POWER`]
+    Default,
+}
+
+/// Estimated distance to an access point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistanceEstimate {
+    /// Estimated distance in metres
+    pub meters: f64,
+    /// Lower bound of the uncertainty interval in metres
+    pub min_meters: f64,
+    /// Upper bound of the uncertainty interval in metres
+    pub max_meters: f64,
+    /// Transmit power used for the estimate in dBm
+    pub tx_power: f64,
+    /// Where the transmit power came from
+    pub tx_power_source: TxPowerSource,
+}
+
+impl Default for DistanceConfig {
+    fn default() -> Self {
+        DistanceConfig::indoor()
+    }
+}
+
+impl DistanceConfig {
+    /// Free-space model for line of sight conditions
+    pub fn free_space() -> Self {
+        DistanceConfig {
+            tx_power: None,
+            model: PathLossModel::FreeSpace,
+            shadowing: 4.0,
+        }
+    }
+
+    /// Log-distance model with an exponent of 3, suitable for typical indoor environments
+    pub fn indoor() -> Self {
+        DistanceConfig {
+            tx_power: None,
+            model: PathLossModel::LogDistance {
+                exponent: 3.0,
+                reference_distance: 1.0,
+            },
+            shadowing: 6.0,
+        }
+    }
+}
+
+impl fmt::Display for DistanceEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:.1} m ({:.1} - {:.1} m)",
+            self.meters, self.min_meters, self.max_meters
+        )
+    }
+}
+
+impl PathLossModel {
+    /// Returns the distance in metres at which the given path loss in dB is reached
+    pub fn distance(&self, path_loss: f64, frequency_mhz: f64) -> f64 {
+        match *self {
+            PathLossModel::FreeSpace => free_space_distance(path_loss, frequency_mhz),
+            PathLossModel::LogDistance {
+                exponent,
+                reference_distance,
+            } => {
+                let reference_loss = free_space_loss(reference_distance, frequency_mhz);
+                reference_distance * 10f64.powf((path_loss - reference_loss) / (10.0 * exponent))
+            }
+        }
+    }
+}
+
+/// Free-space path loss in dB at `meters` and `frequency_mhz`
+pub fn free_space_loss(meters: f64, frequency_mhz: f64) -> f64 {
+    20.0 * meters.log10() + 20.0 * frequency_mhz.log10() - 27.55
+}
+
+/// Distance in metres at which the free-space path loss reaches `path_loss` dB
+fn free_space_distance(path_loss: f64, frequency_mhz: f64) -> f64 {
+    10f64.powf((path_loss - 20.0 * frequency_mhz.log10() + 27.55) / 20.0)
+}
+
+/// Estimates the distance to a network.
+/// Returns `None` if the signal level or frequency is unknown.
+pub(crate) fn estimate(wifi: &Wifi, config: &DistanceConfig) -> Option<DistanceEstimate> {
+    if wifi.signal_level == 0 {
+        return None;
+    }
+    let frequency = match wifi.band() {
+        Some(band) if wifi.frequency == 0 => band.channel_frequency(wifi.channel),
+        _ => wifi.get_frequency(),
+    };
+    if frequency == 0 {
+        return None;
+    }
+
+    let (tx_power, tx_power_source) = match config.tx_power {
+        Some(power) => (power, TxPowerSource::Configured),
+        None => advertised_tx_power(wifi).unwrap_or((DEFAULT_TX_POWER, TxPowerSource::Default)),
+    };
+
+    let path_loss = tx_power - wifi.signal_level as f64;
+    let frequency = frequency as f64;
+    let shadowing = config.shadowing.abs();
+
+    Some(DistanceEstimate {
+        meters: config.model.distance(path_loss, frequency),
+        min_meters: config.model.distance(path_loss - shadowing, frequency),
+        max_meters: config.model.distance(path_loss + shadowing, frequency),
+        tx_power,
+        tx_power_source,
+    })
+}
+
+/// Returns the transmit power advertised by the network in dBm
+fn advertised_tx_power(wifi: &Wifi) -> Option<(f64, TxPowerSource)> {
+    let ies = &wifi.information_elements;
+    if let Some(tpc) = ie::find(ies, ie::TPC_REPORT).filter(|tpc| !tpc.is_empty()) {
+        return Some((tpc[0] as i8 as f64, TxPowerSource::TpcReport));
+    }
+
+    // 6 GHz channels are described by operating extension triplets only
+    if wifi.band() == Some(Band::SixGhz) {
+        return None;
+    }
+    let max_power = country_max_power(ie::find(ies, ie::COUNTRY)?, wifi.channel)?;
+    let constraint = ie::find(ies, ie::POWER_CONSTRAINT)
+        .and_then(|c| c.first().copied())
+        .unwrap_or(0);
+
+    Some((max_power as f64 - constraint as f64, TxPowerSource::Country))
+}
+
+/// Returns the maximum transmit power in dBm for `channel` from a Country element
+fn country_max_power(country: &[u8], channel: u32) -> Option<i8> {
+    // 3 byte country string followed by triplets of first channel, channel count and power
+    let triplets = country.get(3..)?.chunks_exact(3);
+
+    for triplet in triplets {
+        let (first, count, power) = (triplet[0] as u32, triplet[1] as u32, triplet[2] as i8);
+        // operating extension triplets start with an identifier of 201 or more
+        if first >= 201 {
+            continue;
+        }
+
+        let spacing = if first <= 14 { 1 } else { 4 };
+        let last = first + count.saturating_sub(1) * spacing;
+        if (first..=last).contains(&channel) {
+            return Some(power);
+        }
+    }
+
+    None
+}
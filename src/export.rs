@@ -0,0 +1,318 @@
+//! Streaming export and import of scan results.
+//!
+//! - [`csv`]: CSV with a header row and configurable columns
+//! - [`jsonl`]: JSON Lines following the [`schema`](crate::schema), available with the
+//!   `json` feature
+//! - [`wigle`]: WiGLE CSV for uploading wardriving data
+//! - [`netxml`]: Kismet NetXML for legacy tooling
+//! - [`kml`] and [`geojson`]: maps for Google Earth, QGIS and other GIS software
+//! - [`pcapng`]: synthetic beacon frames for Wireshark
+//!
+//! Writers stamp every network with the time of the scan and readers return
+//! [`ScanRecord`]s, so surveys can be written incrementally and read back later.
+//! Geographic formats take [`Observation`]s, records paired with a [`GpsFix`].
+//! NetXML, KML and GeoJSON summarise all observations of a BSS into one entry.
+
+pub mod csv;
+pub mod geojson;
+#[cfg(feature = "json")]
+pub mod jsonl;
+pub mod kml;
+pub mod netxml;
+pub mod pcapng;
+pub mod wigle;
+
+use std::{
+    collections::HashMap,
+    fmt, io,
+    time::{Duration, SystemTime},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    misc::{format_mac, parse_mac},
+    security,
+    signal::normalize_rssi,
+    Wifi, WifiSecurity,
+};
+
+/// A network together with the time it was scanned
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanRecord {
+    /// Time of the scan. `None` if it was not recorded.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::schema::timestamp"))]
+    pub timestamp: Option<SystemTime>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub wifi: Wifi,
+}
+
+impl ScanRecord {
+    pub fn new(wifi: Wifi, timestamp: SystemTime) -> Self {
+        ScanRecord {
+            timestamp: Some(timestamp),
+            wifi,
+        }
+    }
+}
+
+/// A geographic position, e.g. from a GPS receiver
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GpsFix {
+    /// Latitude in degrees, north positive
+    pub latitude: f64,
+    /// Longitude in degrees, east positive
+    pub longitude: f64,
+    /// Altitude above mean sea level in meters
+    pub altitude: Option<f64>,
+    /// Estimated horizontal accuracy in meters
+    pub accuracy: Option<f64>,
+    /// Quality of the fix. `None` if unknown, e.g. for positions entered by hand.
+    pub quality: Option<FixQuality>,
+    /// Time of the fix
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::schema::timestamp"))]
+    pub timestamp: Option<SystemTime>,
+}
+
+impl GpsFix {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        GpsFix {
+            latitude,
+            longitude,
+            ..GpsFix::default()
+        }
+    }
+}
+
+/// Quality of a position fix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FixQuality {
+    /// The receiver has no valid position
+    NoFix,
+    Fix2d,
+    Fix3d,
+    /// Differential GPS
+    Dgps,
+    /// Real time kinematic with fixed integers
+    RtkFixed,
+    /// Real time kinematic with floating integers
+    RtkFloat,
+    /// Dead reckoning or another estimate
+    Estimated,
+    /// Position entered manually on the receiver
+    Manual,
+    Simulated,
+}
+
+impl FixQuality {
+    /// Returns `true` if the fix contains a valid position
+    pub fn has_position(&self) -> bool {
+        *self != FixQuality::NoFix
+    }
+}
+
+impl fmt::Display for FixQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FixQuality::NoFix => write!(f, "No fix"),
+            FixQuality::Fix2d => write!(f, "2D"),
+            FixQuality::Fix3d => write!(f, "3D"),
+            FixQuality::Dgps => write!(f, "DGPS"),
+            FixQuality::RtkFixed => write!(f, "RTK fixed"),
+            FixQuality::RtkFloat => write!(f, "RTK float"),
+            FixQuality::Estimated => write!(f, "Estimated"),
+            FixQuality::Manual => write!(f, "Manual"),
+            FixQuality::Simulated => write!(f, "Simulated"),
+        }
+    }
+}
+
+/// A scan record together with the position it was made at
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Observation {
+    pub record: ScanRecord,
+    pub fix: GpsFix,
+}
+
+impl Observation {
+    pub fn new(record: ScanRecord, fix: GpsFix) -> Self {
+        Observation { record, fix }
+    }
+
+    /// Pairs every record with the fix closest in time.
+    /// Records without timestamp or without a fix within `max_gap` are dropped.
+    pub fn pair(records: &[ScanRecord], fixes: &[GpsFix], max_gap: Duration) -> Vec<Observation> {
+        records
+            .iter()
+            .filter_map(|record| {
+                let time = record.timestamp?;
+                let (gap, fix) = fixes
+                    .iter()
+                    .filter_map(|fix| Some((time_between(time, fix.timestamp?), fix)))
+                    .min_by_key(|(gap, _)| *gap)?;
+                (gap <= max_gap).then(|| Observation::new(record.clone(), *fix))
+            })
+            .collect()
+    }
+}
+
+/// Coarse security classification, used to style map exports.
+/// Unlike [`query::SecurityClass`](crate::query::SecurityClass) every network has exactly one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MapSecurityClass {
+    Unknown,
+    Open,
+    Wep,
+    Wpa,
+    Wpa2,
+    /// WPA3 or Enhanced Open (OWE)
+    Wpa3,
+}
+
+impl MapSecurityClass {
+    /// Returns the class of the strongest security offered by the network
+    pub fn of(wifi: &Wifi) -> MapSecurityClass {
+        let owe = security::rsn(&wifi.information_elements)
+            .is_some_and(|r| r.akms.contains(&security::AKM_OWE));
+        let has = |s: WifiSecurity| wifi.security.contains(&s);
+
+        if wifi.is_wpa3() || owe {
+            MapSecurityClass::Wpa3
+        } else if wifi.is_wpa2() {
+            MapSecurityClass::Wpa2
+        } else if has(WifiSecurity::WpaPersonalPsk) || has(WifiSecurity::WpaEnterpriseEap) {
+            MapSecurityClass::Wpa
+        } else if has(WifiSecurity::Wep) {
+            MapSecurityClass::Wep
+        } else if wifi.is_open() {
+            MapSecurityClass::Open
+        } else {
+            MapSecurityClass::Unknown
+        }
+    }
+
+    /// Returns the display color as RGB
+    pub fn color(&self) -> [u8; 3] {
+        match self {
+            MapSecurityClass::Unknown => [0x80, 0x80, 0x80],
+            MapSecurityClass::Open => [0xe0, 0x20, 0x20],
+            MapSecurityClass::Wep => [0xf0, 0x80, 0x00],
+            MapSecurityClass::Wpa => [0xe0, 0xc0, 0x00],
+            MapSecurityClass::Wpa2 => [0x30, 0xa0, 0x30],
+            MapSecurityClass::Wpa3 => [0x20, 0x60, 0xe0],
+        }
+    }
+}
+
+impl fmt::Display for MapSecurityClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapSecurityClass::Unknown => write!(f, "Unknown"),
+            MapSecurityClass::Open => write!(f, "Open"),
+            MapSecurityClass::Wep => write!(f, "WEP"),
+            MapSecurityClass::Wpa => write!(f, "WPA"),
+            MapSecurityClass::Wpa2 => write!(f, "WPA2"),
+            MapSecurityClass::Wpa3 => write!(f, "WPA3"),
+        }
+    }
+}
+
+/// All observations of a single BSS
+pub(crate) struct BssObservations<'a> {
+    /// Lowercase, colon separated BSSID
+    pub bssid: String,
+    /// Observations in the order they were given
+    pub observations: Vec<&'a Observation>,
+}
+
+impl<'a> BssObservations<'a> {
+    /// Returns the most recent observation, or the last one given if timestamps are equal
+    pub fn latest(&self) -> &'a Observation {
+        self.observations
+            .iter()
+            .copied()
+            .max_by_key(|o| o.record.timestamp)
+            .expect("at least one observation")
+    }
+
+    /// Returns the observation with the strongest signal
+    pub fn strongest(&self) -> &'a Observation {
+        self.observations
+            .iter()
+            .copied()
+            .max_by_key(|o| match normalize_rssi(o.record.wifi.signal_level) {
+                0 => i32::MIN,
+                level => level,
+            })
+            .expect("at least one observation")
+    }
+
+    /// Returns the known signal levels in dBm
+    pub fn signal_levels(&self) -> impl Iterator<Item = i32> + '_ {
+        self.observations
+            .iter()
+            .map(|o| normalize_rssi(o.record.wifi.signal_level))
+            .filter(|level| *level != 0)
+    }
+
+    pub fn first_seen(&self) -> Option<SystemTime> {
+        self.observations
+            .iter()
+            .filter_map(|o| o.record.timestamp)
+            .min()
+    }
+
+    pub fn last_seen(&self) -> Option<SystemTime> {
+        self.observations
+            .iter()
+            .filter_map(|o| o.record.timestamp)
+            .max()
+    }
+}
+
+/// Groups observations by BSSID in order of first appearance.
+/// Observations without BSSID are skipped.
+pub(crate) fn group_by_bss(observations: &[Observation]) -> Vec<BssObservations<'_>> {
+    let mut groups: Vec<BssObservations> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for observation in observations {
+        let mac = &observation.record.wifi.mac;
+        if mac.is_empty() {
+            continue;
+        }
+        let bssid = parse_mac(mac).map_or(mac.to_lowercase(), |mac| format_mac(&mac));
+
+        match index.get(&bssid) {
+            Some(&i) => groups[i].observations.push(observation),
+            None => {
+                index.insert(bssid.clone(), groups.len());
+                groups.push(BssObservations {
+                    bssid,
+                    observations: vec![observation],
+                });
+            }
+        }
+    }
+
+    groups
+}
+
+/// Returns the absolute time between two points in time
+fn time_between(a: SystemTime, b: SystemTime) -> Duration {
+    a.duration_since(b).unwrap_or_else(|e| e.duration())
+}
+
+/// Returns an `InvalidData` error for the given line
+pub(crate) fn invalid_data(line: usize, message: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, message),
+    )
+}
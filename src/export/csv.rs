@@ -0,0 +1,330 @@
+//! CSV export and import.
+//!
+//! The first row holds the column names. Fields containing commas, quotes, line
+//! breaks or surrounding whitespace are quoted, securities are separated by `;` and
+//! information elements are written as hex.
+//!
+//! ```no_run
+//! use std::{fs::File, io::BufReader};
+//! use wifi_scan::export::csv::{Column, CsvReader, CsvWriter};
+//!
+//! let mut writer = CsvWriter::new(File::create("survey.csv").unwrap()).columns(&[
+//!     Column::Timestamp,
+//!     Column::Mac,
+//!     Column::Ssid,
+//!     Column::SignalLevel,
+//! ]);
+//! writer.write(&wifi_scan::scan().unwrap()).unwrap();
+//! writer.flush().unwrap();
+//!
+//! for record in CsvReader::new(BufReader::new(File::open("survey.csv").unwrap())) {
+//!     let record = record.unwrap();
+//!     println!("{:?} {}", record.timestamp, record.wifi);
+//! }
+//! ```
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    time::SystemTime,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{invalid_data, ScanRecord};
+use crate::{
+    misc::{escape_csv, format_hex, format_rfc3339, parse_hex, parse_rfc3339, split_csv_line},
+    Wifi, WifiSecurity,
+};
+
+/// Columns of a CSV export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Column {
+    /// Time of the scan in RFC 3339 format
+    Timestamp,
+    Mac,
+    Ssid,
+    Channel,
+    /// Center frequency in MHz, 0 if unknown
+    Frequency,
+    SignalLevel,
+    /// Securities separated by `;`. `;` and `\` in names are escaped with a `\`.
+    Security,
+    /// Raw information elements as hex
+    InformationElements,
+    Inferred,
+}
+
+/// Columns written by default, covering all fields of [`ScanRecord`]
+pub const DEFAULT_COLUMNS: [Column; 9] = [
+    Column::Timestamp,
+    Column::Mac,
+    Column::Ssid,
+    Column::Channel,
+    Column::Frequency,
+    Column::SignalLevel,
+    Column::Security,
+    Column::InformationElements,
+    Column::Inferred,
+];
+
+impl Column {
+    /// Returns the name of the column used in the header row
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Timestamp => "timestamp",
+            Column::Mac => "mac",
+            Column::Ssid => "ssid",
+            Column::Channel => "channel",
+            Column::Frequency => "frequency",
+            Column::SignalLevel => "signal_level",
+            Column::Security => "security",
+            Column::InformationElements => "information_elements",
+            Column::Inferred => "inferred",
+        }
+    }
+
+    /// Returns the column with the given header name
+    pub fn from_name(name: &str) -> Option<Column> {
+        DEFAULT_COLUMNS.into_iter().find(|c| c.name() == name)
+    }
+
+    fn format(&self, record: &ScanRecord) -> String {
+        let wifi = &record.wifi;
+        match self {
+            Column::Timestamp => record.timestamp.map(format_rfc3339).unwrap_or_default(),
+            Column::Mac => wifi.mac.clone(),
+            Column::Ssid => wifi.ssid.clone(),
+            Column::Channel => wifi.channel.to_string(),
+            Column::Frequency => wifi.frequency.to_string(),
+            Column::SignalLevel => wifi.signal_level.to_string(),
+            Column::Security => join_securities(&wifi.security),
+            Column::InformationElements => format_hex(&wifi.information_elements),
+            Column::Inferred => wifi.inferred.to_string(),
+        }
+    }
+
+    /// Sets the field of the record from its text. Returns `None` if it is invalid.
+    fn parse(&self, text: &str, record: &mut ScanRecord) -> Option<()> {
+        let wifi = &mut record.wifi;
+        match self {
+            Column::Timestamp if text.is_empty() => record.timestamp = None,
+            Column::Timestamp => record.timestamp = Some(parse_rfc3339(text)?),
+            Column::Mac => wifi.mac = text.to_string(),
+            Column::Ssid => wifi.ssid = text.to_string(),
+            Column::Channel => wifi.channel = text.trim().parse().ok()?,
+            Column::Frequency => wifi.frequency = text.trim().parse().ok()?,
+            Column::SignalLevel => wifi.signal_level = text.trim().parse().ok()?,
+            Column::Security => wifi.security = split_securities(text),
+            Column::InformationElements => wifi.information_elements = parse_hex(text.trim())?,
+            Column::Inferred => wifi.inferred = text.trim().parse().ok()?,
+        }
+        Some(())
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Writes scan results as CSV. The header row is written before the first record.
+pub struct CsvWriter<W: Write> {
+    writer: W,
+    columns: Vec<Column>,
+    header_written: bool,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Creates a writer with the [`DEFAULT_COLUMNS`]
+    pub fn new(writer: W) -> Self {
+        CsvWriter {
+            writer,
+            columns: DEFAULT_COLUMNS.to_vec(),
+            header_written: false,
+        }
+    }
+
+    /// Sets the columns to write, in order.
+    /// Has no effect once the header has been written.
+    pub fn columns(mut self, columns: &[Column]) -> Self {
+        if !self.header_written {
+            self.columns = columns.to_vec();
+        }
+        self
+    }
+
+    /// Writes the networks of a scan made now
+    pub fn write(&mut self, wifis: &[Wifi]) -> io::Result<()> {
+        self.write_at(wifis, SystemTime::now())
+    }
+
+    /// Writes the networks of a scan made at the given time
+    pub fn write_at(&mut self, wifis: &[Wifi], timestamp: SystemTime) -> io::Result<()> {
+        for wifi in wifis {
+            self.write_record(&ScanRecord::new(wifi.clone(), timestamp))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single record
+    pub fn write_record(&mut self, record: &ScanRecord) -> io::Result<()> {
+        self.write_header()?;
+        let mut fields: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| escape_csv(&c.format(record)))
+            .collect();
+        // a single empty field would be read back as a blank line and skipped
+        if let [field] = fields.as_mut_slice() {
+            if field.is_empty() {
+                *field = "\"\"".to_string();
+            }
+        }
+        writeln!(self.writer, "{}", fields.join(","))
+    }
+
+    /// Writes the header if nothing has been written yet and flushes the writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_header()?;
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.header_written {
+            return Ok(());
+        }
+        self.header_written = true;
+        let names: Vec<&str> = self.columns.iter().map(|c| c.name()).collect();
+        writeln!(self.writer, "{}", names.join(","))
+    }
+}
+
+/// Reads scan results written by [`CsvWriter`], or any CSV file with a header row
+/// using the [`Column`] names. Unknown columns are ignored and missing ones are left
+/// at their defaults.
+pub struct CsvReader<R: BufRead> {
+    reader: R,
+    /// Columns in file order, `None` for unknown ones. Read from the first row.
+    columns: Option<Vec<Option<Column>>>,
+    line: usize,
+}
+
+impl<R: BufRead> CsvReader<R> {
+    pub fn new(reader: R) -> Self {
+        CsvReader {
+            reader,
+            columns: None,
+            line: 0,
+        }
+    }
+
+    /// Reads the next non-empty row, joining lines inside quoted fields.
+    /// Returns the row and the number of its first line.
+    fn read_row(&mut self) -> io::Result<Option<(usize, String)>> {
+        let mut row = String::new();
+        let mut first_line = self.line + 1;
+        loop {
+            if self.reader.read_line(&mut row)? == 0 {
+                if row.is_empty() {
+                    return Ok(None);
+                }
+                return Err(invalid_data(first_line, "unterminated quoted field"));
+            }
+            self.line += 1;
+
+            if row.trim().is_empty() {
+                row.clear();
+                first_line = self.line + 1;
+            } else if row.matches('"').count() % 2 == 0 {
+                let end = row.trim_end_matches(['\n', '\r']).len();
+                row.truncate(end);
+                return Ok(Some((first_line, row)));
+            }
+        }
+    }
+
+    fn next_record(&mut self) -> io::Result<Option<ScanRecord>> {
+        if self.columns.is_none() {
+            let Some((_, header)) = self.read_row()? else {
+                return Ok(None);
+            };
+            let header = header.strip_prefix('\u{feff}').unwrap_or(&header);
+            self.columns = Some(
+                split_csv_line(header)
+                    .iter()
+                    .map(|name| Column::from_name(name.trim()))
+                    .collect(),
+            );
+        }
+
+        let Some((line, row)) = self.read_row()? else {
+            return Ok(None);
+        };
+        let fields = split_csv_line(&row);
+        let columns = self.columns.as_deref().unwrap_or_default();
+        if fields.len() != columns.len() {
+            return Err(invalid_data(
+                line,
+                format!("expected {} fields, found {}", columns.len(), fields.len()),
+            ));
+        }
+
+        let mut record = ScanRecord::default();
+        for (column, field) in columns.iter().zip(&fields) {
+            if let Some(column) = column {
+                column
+                    .parse(field, &mut record)
+                    .ok_or_else(|| invalid_data(line, format!("invalid {}: {}", column, field)))?;
+            }
+        }
+        Ok(Some(record))
+    }
+}
+
+impl<R: BufRead> Iterator for CsvReader<R> {
+    type Item = io::Result<ScanRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Joins the names of securities with `;`, escaping `;` and `\` with a `\`
+fn join_securities(securities: &[WifiSecurity]) -> String {
+    securities
+        .iter()
+        .map(|s| s.to_string().replace('\\', "\\\\").replace(';', "\\;"))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Splits securities joined by [`join_securities`]
+fn split_securities(text: &str) -> Vec<WifiSecurity> {
+    let mut names = Vec::new();
+    let mut name = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => name.extend(chars.next()),
+            ';' => names.push(std::mem::take(&mut name)),
+            _ => name.push(c),
+        }
+    }
+    names.push(name);
+
+    names
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| s.parse().ok())
+        .collect()
+}
@@ -0,0 +1,89 @@
+//! GeoJSON export for QGIS and web maps.
+//!
+//! Every BSS becomes a point feature at the position of its strongest observation.
+//! The `marker-color` property follows the [simplestyle] convention and reflects
+//! the [`MapSecurityClass`], which is also available as the `security` property.
+//!
+//! [simplestyle]: https://github.com/mapbox/simplestyle-spec
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use wifi_scan::export::{geojson, GpsFix, Observation, ScanRecord};
+//!
+//! let fix = GpsFix::new(52.5200, 13.4050);
+//! let observations: Vec<Observation> = wifi_scan::scan()
+//!     .unwrap()
+//!     .into_iter()
+//!     .map(|wifi| Observation::new(ScanRecord::new(wifi, std::time::SystemTime::now()), fix))
+//!     .collect();
+//! geojson::write(File::create("survey.geojson").unwrap(), &observations).unwrap();
+//! ```
+
+use std::io::{self, Write};
+
+use super::{group_by_bss, BssObservations, MapSecurityClass, Observation};
+use crate::misc::{escape_json, format_rfc3339};
+
+/// Writes the observations as a GeoJSON feature collection
+pub fn write<W: Write>(mut writer: W, observations: &[Observation]) -> io::Result<()> {
+    let groups = group_by_bss(observations);
+
+    writeln!(writer, r#"{{"type":"FeatureCollection","features":["#)?;
+    for (i, group) in groups.iter().enumerate() {
+        let separator = if i + 1 < groups.len() { "," } else { "" };
+        writeln!(writer, "{}{}", feature(group), separator)?;
+    }
+    writeln!(writer, "]}}")?;
+    writer.flush()
+}
+
+fn feature(group: &BssObservations) -> String {
+    let wifi = &group.latest().record.wifi;
+    let fix = &group.strongest().fix;
+    let class = MapSecurityClass::of(wifi);
+    let [r, g, b] = class.color();
+
+    let mut coordinates = format!("{},{}", fix.longitude, fix.latitude);
+    if let Some(altitude) = fix.altitude {
+        coordinates.push_str(&format!(",{}", altitude));
+    }
+
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+    let securities: Vec<String> = wifi
+        .security
+        .iter()
+        .map(|s| escape_json(&s.to_string()))
+        .collect();
+    let properties = [
+        ("bssid", escape_json(&group.bssid)),
+        ("ssid", escape_json(&wifi.ssid)),
+        ("channel", wifi.channel.to_string()),
+        ("frequency", wifi.get_frequency().to_string()),
+        (
+            "signal_level",
+            optional(group.signal_levels().max().map(|s| s.to_string())),
+        ),
+        ("security", escape_json(&class.to_string())),
+        ("securities", format!("[{}]", securities.join(","))),
+        (
+            "first_seen",
+            optional(group.first_seen().map(|t| escape_json(&format_rfc3339(t)))),
+        ),
+        (
+            "last_seen",
+            optional(group.last_seen().map(|t| escape_json(&format_rfc3339(t)))),
+        ),
+        ("observations", group.observations.len().to_string()),
+        ("marker-color", format!("\"#{:02x}{:02x}{:02x}\"", r, g, b)),
+    ];
+    let properties: Vec<String> = properties
+        .iter()
+        .map(|(name, value)| format!("\"{}\":{}", name, value))
+        .collect();
+
+    format!(
+        r#"{{"type":"Feature","geometry":{{"type":"Point","coordinates":[{}]}},"properties":{{{}}}}}"#,
+        coordinates,
+        properties.join(",")
+    )
+}
@@ -0,0 +1,130 @@
+//! JSON Lines export and import, available with the `json` feature.
+//!
+//! Every line is a JSON object with the fields of a [`Wifi`] following the
+//! [`schema`](crate::schema), plus `schema_version` and an RFC 3339 `timestamp`:
+//!
+//! ```text
+//! {"schema_version":1,"timestamp":"2024-05-01T12:30:00.000Z","mac":"00:11:22:33:44:55","ssid":"Home",...}
+//! ```
+//!
+//! ```no_run
+//! use std::{fs::File, io::BufReader};
+//! use wifi_scan::export::jsonl::{JsonLinesReader, JsonLinesWriter};
+//!
+//! let mut writer = JsonLinesWriter::new(File::create("survey.jsonl").unwrap());
+//! writer.write(&wifi_scan::scan().unwrap()).unwrap();
+//! writer.flush().unwrap();
+//!
+//! for record in JsonLinesReader::new(BufReader::new(File::open("survey.jsonl").unwrap())) {
+//!     println!("{:?}", record.unwrap());
+//! }
+//! ```
+
+use std::{
+    io::{self, BufRead, Write},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{invalid_data, ScanRecord};
+use crate::{
+    schema::{Versioned, SCHEMA_VERSION},
+    Wifi,
+};
+
+/// A record tagged with the schema version on a single line
+#[derive(Serialize, Deserialize)]
+struct Line<T> {
+    schema_version: u32,
+    #[serde(flatten)]
+    record: T,
+}
+
+/// Writes scan results as JSON Lines
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(writer: W) -> Self {
+        JsonLinesWriter { writer }
+    }
+
+    /// Writes the networks of a scan made now
+    pub fn write(&mut self, wifis: &[Wifi]) -> io::Result<()> {
+        self.write_at(wifis, SystemTime::now())
+    }
+
+    /// Writes the networks of a scan made at the given time
+    pub fn write_at(&mut self, wifis: &[Wifi], timestamp: SystemTime) -> io::Result<()> {
+        for wifi in wifis {
+            self.write_record(&ScanRecord::new(wifi.clone(), timestamp))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single record
+    pub fn write_record(&mut self, record: &ScanRecord) -> io::Result<()> {
+        let line = Line {
+            schema_version: SCHEMA_VERSION,
+            record,
+        };
+        serde_json::to_writer(&mut self.writer, &line)?;
+        writeln!(self.writer)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads scan results written by [`JsonLinesWriter`].
+/// Empty lines are skipped, lines with a newer schema version are rejected.
+pub struct JsonLinesReader<R: BufRead> {
+    lines: io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> JsonLinesReader<R> {
+    pub fn new(reader: R) -> Self {
+        JsonLinesReader {
+            lines: reader.lines(),
+            line: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for JsonLinesReader<R> {
+    type Item = io::Result<ScanRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text = match self.lines.next()? {
+                Ok(text) => text,
+                Err(e) => return Some(Err(e)),
+            };
+            self.line += 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            let record = serde_json::from_str::<Line<ScanRecord>>(&text)
+                .map_err(|e| invalid_data(self.line, e))
+                .and_then(|line| {
+                    Versioned {
+                        schema_version: line.schema_version,
+                        data: line.record,
+                    }
+                    .into_data()
+                    .map_err(|e| invalid_data(self.line, e))
+                });
+            return Some(record);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! KML export for Google Earth and GIS software.
+//!
+//! Every BSS becomes a placemark at the position of its strongest observation,
+//! colored by its [`MapSecurityClass`] and grouped into one folder per class.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use wifi_scan::export::{kml, GpsFix, Observation, ScanRecord};
+//!
+//! let fix = GpsFix::new(52.5200, 13.4050);
+//! let observations: Vec<Observation> = wifi_scan::scan()
+//!     .unwrap()
+//!     .into_iter()
+//!     .map(|wifi| Observation::new(ScanRecord::new(wifi, std::time::SystemTime::now()), fix))
+//!     .collect();
+//! kml::write(File::create("survey.kml").unwrap(), &observations).unwrap();
+//! ```
+
+use std::io::{self, Write};
+
+use super::{group_by_bss, BssObservations, MapSecurityClass, Observation};
+use crate::misc::{escape_xml, format_rfc3339};
+
+const ICON: &str = "http://maps.google.com/mapfiles/kml/shapes/placemark_circle.png";
+
+const CLASSES: [MapSecurityClass; 6] = [
+    MapSecurityClass::Open,
+    MapSecurityClass::Wep,
+    MapSecurityClass::Wpa,
+    MapSecurityClass::Wpa2,
+    MapSecurityClass::Wpa3,
+    MapSecurityClass::Unknown,
+];
+
+/// Writes the observations as a KML document
+pub fn write<W: Write>(mut writer: W, observations: &[Observation]) -> io::Result<()> {
+    let groups = group_by_bss(observations);
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#)?;
+    writeln!(writer, "<Document>")?;
+    writeln!(writer, "  <name>wifi_scan survey</name>")?;
+
+    for class in CLASSES {
+        let [r, g, b] = class.color();
+        writeln!(writer, r#"  <Style id="{}">"#, style_id(class))?;
+        // KML colors are aabbggrr
+        writeln!(
+            writer,
+            "    <IconStyle><color>ff{:02x}{:02x}{:02x}</color><Icon><href>{}</href></Icon></IconStyle>",
+            b, g, r, ICON
+        )?;
+        writeln!(writer, "  </Style>")?;
+    }
+
+    for class in CLASSES {
+        let members: Vec<&BssObservations> = groups
+            .iter()
+            .filter(|g| MapSecurityClass::of(&g.latest().record.wifi) == class)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        writeln!(writer, "  <Folder>")?;
+        writeln!(writer, "    <name>{}</name>", class)?;
+        for group in members {
+            write_placemark(&mut writer, group, class)?;
+        }
+        writeln!(writer, "  </Folder>")?;
+    }
+
+    writeln!(writer, "</Document>")?;
+    writeln!(writer, "</kml>")?;
+    writer.flush()
+}
+
+fn write_placemark<W: Write>(
+    writer: &mut W,
+    group: &BssObservations,
+    class: MapSecurityClass,
+) -> io::Result<()> {
+    let wifi = &group.latest().record.wifi;
+    let strongest = group.strongest();
+    let fix = &strongest.fix;
+    let name = if wifi.is_hidden() {
+        &group.bssid
+    } else {
+        &wifi.ssid
+    };
+    let securities: Vec<String> = wifi.security.iter().map(|s| s.to_string()).collect();
+    let signal = group.signal_levels().max();
+
+    let data = [
+        ("bssid", group.bssid.clone()),
+        ("ssid", wifi.ssid.clone()),
+        ("channel", wifi.channel.to_string()),
+        (
+            "signal_level",
+            signal.map_or(String::new(), |s| s.to_string()),
+        ),
+        ("security", class.to_string()),
+        ("securities", securities.join(", ")),
+        (
+            "first_seen",
+            group.first_seen().map(format_rfc3339).unwrap_or_default(),
+        ),
+        (
+            "last_seen",
+            group.last_seen().map(format_rfc3339).unwrap_or_default(),
+        ),
+        ("observations", group.observations.len().to_string()),
+    ];
+
+    writeln!(writer, "    <Placemark>")?;
+    writeln!(writer, "      <name>{}</name>", escape_xml(name))?;
+    writeln!(
+        writer,
+        "      <description>{}</description>",
+        escape_xml(&format!(
+            "BSSID: {}\nChannel: {}\nSignal: {}\nSecurity: {}\nObservations: {}",
+            group.bssid,
+            wifi.channel,
+            signal.map_or("Unknown".to_string(), |s| format!("{} dBm", s)),
+            securities.join(", "),
+            group.observations.len()
+        ))
+    )?;
+    writeln!(writer, "      <styleUrl>#{}</styleUrl>", style_id(class))?;
+
+    writeln!(writer, "      <ExtendedData>")?;
+    for (name, value) in data {
+        writeln!(
+            writer,
+            r#"        <Data name="{}"><value>{}</value></Data>"#,
+            name,
+            escape_xml(&value)
+        )?;
+    }
+    writeln!(writer, "      </ExtendedData>")?;
+
+    let altitude = fix.altitude.map_or(String::new(), |a| format!(",{}", a));
+    writeln!(
+        writer,
+        "      <Point><coordinates>{},{}{}</coordinates></Point>",
+        fix.longitude, fix.latitude, altitude
+    )?;
+    writeln!(writer, "    </Placemark>")
+}
+
+fn style_id(class: MapSecurityClass) -> String {
+    class.to_string().to_lowercase()
+}
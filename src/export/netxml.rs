@@ -0,0 +1,258 @@
+//! Kismet NetXML export for legacy tooling.
+//!
+//! Every BSS becomes a `wireless-network` with signal statistics over all of its
+//! observations and GPS information, where the peak position is the position of the
+//! strongest observation. Times are written in UTC.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use wifi_scan::export::{netxml, GpsFix, Observation, ScanRecord};
+//!
+//! let fix = GpsFix::new(52.5200, 13.4050);
+//! let observations: Vec<Observation> = wifi_scan::scan()
+//!     .unwrap()
+//!     .into_iter()
+//!     .map(|wifi| Observation::new(ScanRecord::new(wifi, std::time::SystemTime::now()), fix))
+//!     .collect();
+//! netxml::write(File::create("survey.netxml").unwrap(), &observations).unwrap();
+//! ```
+
+use std::{
+    io::{self, Write},
+    time::SystemTime,
+};
+
+use super::{group_by_bss, BssObservations, Observation};
+use crate::{
+    misc::{escape_xml, UtcDateTime},
+    security, Band, Wifi, WifiSecurity,
+};
+
+/// Writes the observations as a Kismet NetXML detection run
+pub fn write<W: Write>(mut writer: W, observations: &[Observation]) -> io::Result<()> {
+    let groups = group_by_bss(observations);
+    let start = groups
+        .iter()
+        .filter_map(|g| g.first_seen())
+        .min()
+        .unwrap_or_else(SystemTime::now);
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<!DOCTYPE detection-run SYSTEM "http://kismetwireless.net/kismet-3.1.0.dtd">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<detection-run kismet-version="wifi_scan {}" start-time="{}">"#,
+        env!("CARGO_PKG_VERSION"),
+        format_time(start)
+    )?;
+    for (number, group) in groups.iter().enumerate() {
+        write_network(&mut writer, number + 1, group)?;
+    }
+    writeln!(writer, "</detection-run>")?;
+    writer.flush()
+}
+
+fn write_network<W: Write>(
+    writer: &mut W,
+    number: usize,
+    group: &BssObservations,
+) -> io::Result<()> {
+    let latest = group.latest();
+    let wifi = &latest.record.wifi;
+    let first = group
+        .first_seen()
+        .or(latest.record.timestamp)
+        .map(format_time);
+    let last = group
+        .last_seen()
+        .or(latest.record.timestamp)
+        .map(format_time);
+    let first = first.unwrap_or_default();
+    let last = last.unwrap_or_default();
+    let packets = group.observations.len();
+
+    writeln!(
+        writer,
+        r#"  <wireless-network number="{}" type="infrastructure" first-time="{}" last-time="{}">"#,
+        number, first, last
+    )?;
+
+    writeln!(
+        writer,
+        r#"    <SSID first-time="{}" last-time="{}">"#,
+        first, last
+    )?;
+    writeln!(writer, "      <type>Beacon</type>")?;
+    writeln!(writer, "      <packets>{}</packets>", packets)?;
+    for encryption in encryption(wifi) {
+        writeln!(writer, "      <encryption>{}</encryption>", encryption)?;
+    }
+    if wifi.is_hidden() {
+        writeln!(writer, r#"      <essid cloaked="true"></essid>"#)?;
+    } else {
+        writeln!(
+            writer,
+            r#"      <essid cloaked="false">{}</essid>"#,
+            escape_xml(&wifi.ssid)
+        )?;
+    }
+    writeln!(writer, "    </SSID>")?;
+
+    writeln!(writer, "    <BSSID>{}</BSSID>", group.bssid.to_uppercase())?;
+    writeln!(
+        writer,
+        "    <manuf>{}</manuf>",
+        escape_xml(&manufacturer(wifi))
+    )?;
+    writeln!(writer, "    <channel>{}</channel>", wifi.channel)?;
+    writeln!(
+        writer,
+        "    <freqmhz>{} {}</freqmhz>",
+        wifi.get_frequency(),
+        packets
+    )?;
+    writeln!(writer, "    <carrier>{}</carrier>", carrier(wifi))?;
+    writeln!(writer, "    <packets>{}</packets>", packets)?;
+    writeln!(writer, "    <datasize>0</datasize>")?;
+
+    let last_signal = latest.record.wifi.signal_level;
+    let min_signal = group.signal_levels().min().unwrap_or(0);
+    let max_signal = group.signal_levels().max().unwrap_or(0);
+    writeln!(writer, "    <snr-info>")?;
+    for (name, value) in [
+        ("last_signal_dbm", last_signal),
+        ("last_noise_dbm", 0),
+        ("min_signal_dbm", min_signal),
+        ("min_noise_dbm", 0),
+        ("max_signal_dbm", max_signal),
+        ("max_noise_dbm", 0),
+    ] {
+        writeln!(writer, "      <{0}>{1}</{0}>", name, value)?;
+    }
+    writeln!(writer, "    </snr-info>")?;
+
+    write_gps_info(writer, group)?;
+    writeln!(writer, "  </wireless-network>")
+}
+
+fn write_gps_info<W: Write>(writer: &mut W, group: &BssObservations) -> io::Result<()> {
+    let fixes = group.observations.iter().map(|o| &o.fix);
+    let latitudes: Vec<f64> = fixes.clone().map(|f| f.latitude).collect();
+    let longitudes: Vec<f64> = fixes.clone().map(|f| f.longitude).collect();
+    let altitudes: Vec<f64> = fixes.map(|f| f.altitude.unwrap_or(0.0)).collect();
+    let peak = &group.strongest().fix;
+
+    let min = |values: &[f64]| values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = |values: &[f64]| values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let avg = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+
+    let values = [
+        ("min-lat", min(&latitudes)),
+        ("min-lon", min(&longitudes)),
+        ("min-alt", min(&altitudes)),
+        ("min-spd", 0.0),
+        ("max-lat", max(&latitudes)),
+        ("max-lon", max(&longitudes)),
+        ("max-alt", max(&altitudes)),
+        ("max-spd", 0.0),
+        ("peak-lat", peak.latitude),
+        ("peak-lon", peak.longitude),
+        ("peak-alt", peak.altitude.unwrap_or(0.0)),
+        ("avg-lat", avg(&latitudes)),
+        ("avg-lon", avg(&longitudes)),
+        ("avg-alt", avg(&altitudes)),
+    ];
+
+    writeln!(writer, "    <gps-info>")?;
+    for (name, value) in values {
+        writeln!(writer, "      <{0}>{1:.6}</{0}>", name, value)?;
+    }
+    writeln!(writer, "    </gps-info>")
+}
+
+/// Returns the encryption entries in Kismet notation, e.g. `WPA+PSK` and `WPA+AES-CCM`
+fn encryption(wifi: &Wifi) -> Vec<&'static str> {
+    let mut entries = Vec::new();
+    let mut add = |entry: &'static str| {
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    };
+
+    for s in &wifi.security {
+        match s {
+            WifiSecurity::Open => add("None"),
+            WifiSecurity::Wep => add("WEP"),
+            WifiSecurity::Wpa3PersonalSae | WifiSecurity::Wpa3PersonalSaeFt => add("WPA+SAE"),
+            WifiSecurity::WpaPersonalPsk
+            | WifiSecurity::Wpa2PersonalPsk
+            | WifiSecurity::Wpa2PersonalPskFt
+            | WifiSecurity::Wpa2PersonalPsk256 => add("WPA+PSK"),
+            WifiSecurity::WpaEnterpriseEap
+            | WifiSecurity::Wpa2EnterpriseEap
+            | WifiSecurity::Wpa2EnterpriseEapFt
+            | WifiSecurity::Wpa2EnterpriseEap256
+            | WifiSecurity::Wpa3EnterpriseEap
+            | WifiSecurity::Wpa3EnterpriseEap256
+            | WifiSecurity::Wpa3EnterpriseSuiteBEap256 => add("WPA+MGT"),
+            WifiSecurity::TunneledDirectLinkSetup
+            | WifiSecurity::Unknown
+            | WifiSecurity::Other(_) => {}
+        }
+    }
+
+    let ies = &wifi.information_elements;
+    for element in [security::rsn(ies), security::wpa(ies)].iter().flatten() {
+        for cipher in &element.pairwise_ciphers {
+            match *cipher {
+                security::CIPHER_TKIP => add("WPA+TKIP"),
+                security::CIPHER_CCMP => add("WPA+AES-CCM"),
+                _ => {}
+            }
+        }
+    }
+
+    entries
+}
+
+#[cfg(feature = "oui")]
+fn manufacturer(wifi: &Wifi) -> String {
+    wifi.vendor().unwrap_or_else(|| "Unknown".to_string())
+}
+
+#[cfg(not(feature = "oui"))]
+fn manufacturer(_wifi: &Wifi) -> String {
+    "Unknown".to_string()
+}
+
+fn carrier(wifi: &Wifi) -> &'static str {
+    match wifi.band() {
+        Some(Band::TwoPointFourGhz) => "IEEE 802.11b+",
+        Some(Band::FiveGhz) => "IEEE 802.11a",
+        Some(Band::SixGhz) => "IEEE 802.11ax",
+        None => "Unknown",
+    }
+}
+
+/// Formats a time like `ctime`, e.g. `Wed May  1 12:30:00 2024`
+fn format_time(time: SystemTime) -> String {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let t = UtcDateTime::from_system_time(time);
+    format!(
+        "{} {} {:>2} {:02}:{:02}:{:02} {}",
+        DAYS[t.weekday() as usize],
+        MONTHS[t.month as usize - 1],
+        t.day,
+        t.hour,
+        t.minute,
+        t.second,
+        t.year
+    )
+}
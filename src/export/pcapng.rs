@@ -0,0 +1,341 @@
+//! pcapng export of scan results as synthetic beacon frames.
+//!
+//! Every network becomes a beacon frame behind a radiotap header with its channel and
+//! signal level, so results can be opened in Wireshark and inspected with the standard
+//! 802.11 dissectors. The stored information elements are used as the frame body if
+//! the platform exposed them. Otherwise SSID, supported rates, DS parameter set and
+//! RSN/WPA elements are synthesised from the network's SSID, channel and security.
+//! WPA3-Enterprise (EAP) and WPA2-Enterprise (EAP-256) both become the EAP-SHA256 AKM,
+//! so they read back as WPA3-Enterprise (EAP-256).
+//!
+//! Scan metadata is stored as comments: the section header names the exporting
+//! application and carries any comments set with [`PcapngWriter::comment`], the first
+//! packet of each scan notes the scan time and the number of networks written and
+//! skipped, and every packet has a one-line summary of its network. Scans without any
+//! packet note this in the section header or, once it is written, in an otherwise
+//! empty name resolution block.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use wifi_scan::export::pcapng::PcapngWriter;
+//!
+//! let mut writer = PcapngWriter::new(File::create("scan.pcapng").unwrap())
+//!     .comment("Office, 3rd floor");
+//! writer.write(&wifi_scan::scan().unwrap()).unwrap();
+//! writer.flush().unwrap();
+//! ```
+//!
+//! Networks without BSSID (e.g. on macOS without location access) are skipped.
+//! Unknown securities make no claim about encryption: they add no elements and don't
+//! set the privacy bit, so networks without any other security information are
+//! written as open networks.
+
+use std::{
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::ScanRecord;
+use crate::{
+    ie,
+    misc::{format_rfc3339, parse_mac},
+    pcap::{
+        LINKTYPE_IEEE802_11_RADIOTAP, OPTION_COMMENT, OPTION_END, OPTION_IF_NAME, OPTION_SHB_OS,
+        OPTION_SHB_USERAPPL, PCAPNG_BYTE_ORDER, PCAPNG_ENHANCED_PACKET,
+        PCAPNG_INTERFACE_DESCRIPTION, PCAPNG_NAME_RESOLUTION, PCAPNG_SECTION_HEADER,
+        SUBTYPE_BEACON,
+    },
+    radiotap::channel_flags,
+    security,
+    signal::normalize_rssi,
+    Band, Wifi, WifiSecurity,
+};
+
+/// Beacon interval in time units of 1024 µs
+const BEACON_INTERVAL: u16 = 100;
+/// Capability information bits
+const CAPABILITY_ESS: u16 = 0x0001;
+const CAPABILITY_PRIVACY: u16 = 0x0010;
+
+/// Writes networks as a pcapng capture of beacon frames with radiotap headers.
+/// The section header and interface description are written before the first packet.
+pub struct PcapngWriter<W: Write> {
+    writer: W,
+    comments: Vec<String>,
+    header_written: bool,
+    sequence: u16,
+}
+
+impl<W: Write> PcapngWriter<W> {
+    pub fn new(writer: W) -> Self {
+        PcapngWriter {
+            writer,
+            comments: Vec::new(),
+            header_written: false,
+            sequence: 0,
+        }
+    }
+
+    /// Adds a comment to the section header, e.g. the location of the scan.
+    /// Has no effect once the header has been written.
+    pub fn comment(mut self, comment: &str) -> Self {
+        if !self.header_written {
+            self.comments.push(comment.to_string());
+        }
+        self
+    }
+
+    /// Writes the networks of a scan made now
+    pub fn write(&mut self, wifis: &[Wifi]) -> io::Result<()> {
+        self.write_at(wifis, SystemTime::now())
+    }
+
+    /// Writes the networks of a scan made at the given time
+    pub fn write_at(&mut self, wifis: &[Wifi], timestamp: SystemTime) -> io::Result<()> {
+        let written = wifis.iter().filter(|w| parse_mac(&w.mac).is_some()).count();
+        let mut comment = format!(
+            "Scan at {} with {} networks",
+            format_rfc3339(timestamp),
+            written
+        );
+        if written < wifis.len() {
+            comment.push_str(&format!(
+                ", {} without BSSID skipped",
+                wifis.len() - written
+            ));
+        }
+
+        if written == 0 && !self.header_written {
+            self.comments.push(comment);
+            return self.write_header();
+        }
+
+        let mut scan_comment = Some(comment);
+        for wifi in wifis {
+            let record = ScanRecord::new(wifi.clone(), timestamp);
+            if self.write_packet(&record, scan_comment.as_deref())? {
+                scan_comment = None;
+            }
+        }
+        match scan_comment {
+            Some(comment) => self.write_comment(&comment),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes a single record. Records without timestamp are written at the epoch.
+    pub fn write_record(&mut self, record: &ScanRecord) -> io::Result<()> {
+        self.write_packet(record, None).map(|_| ())
+    }
+
+    /// Writes the headers if nothing has been written yet and flushes the writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_header()?;
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Returns `false` if the network was skipped
+    fn write_packet(&mut self, record: &ScanRecord, comment: Option<&str>) -> io::Result<bool> {
+        self.write_header()?;
+
+        let wifi = &record.wifi;
+        let Some(bssid) = parse_mac(&wifi.mac) else {
+            return Ok(false);
+        };
+        let mut data = radiotap_header(wifi);
+        data.extend(beacon(wifi, bssid, self.sequence));
+        self.sequence = (self.sequence + 1) & 0x0fff;
+
+        let microseconds = record
+            .timestamp
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_micros() as u64);
+
+        let mut body = Vec::new();
+        // interface ID
+        body.extend_from_slice(&0u32.to_le_bytes());
+        body.extend_from_slice(&((microseconds >> 32) as u32).to_le_bytes());
+        body.extend_from_slice(&(microseconds as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        body.extend_from_slice(&data);
+        pad(&mut body);
+        if let Some(comment) = comment {
+            push_option(&mut body, OPTION_COMMENT, comment.as_bytes());
+        }
+        push_option(&mut body, OPTION_COMMENT, wifi.to_string().as_bytes());
+        push_option(&mut body, OPTION_END, &[]);
+
+        write_block(&mut self.writer, PCAPNG_ENHANCED_PACKET, &body)?;
+        Ok(true)
+    }
+
+    /// Writes a name resolution block without records carrying only the comment
+    fn write_comment(&mut self, comment: &str) -> io::Result<()> {
+        self.write_header()?;
+
+        let mut body = Vec::new();
+        // end of records
+        body.extend_from_slice(&[0x00; 4]);
+        push_option(&mut body, OPTION_COMMENT, comment.as_bytes());
+        push_option(&mut body, OPTION_END, &[]);
+        write_block(&mut self.writer, PCAPNG_NAME_RESOLUTION, &body)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.header_written {
+            return Ok(());
+        }
+        self.header_written = true;
+
+        let mut section = Vec::new();
+        section.extend_from_slice(&PCAPNG_BYTE_ORDER.to_le_bytes());
+        // version 1.0
+        section.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        // section length not specified
+        section.extend_from_slice(&u64::MAX.to_le_bytes());
+        push_option(
+            &mut section,
+            OPTION_COMMENT,
+            b"Synthetic beacon frames of wifi_scan scan results",
+        );
+        for comment in &self.comments {
+            push_option(&mut section, OPTION_COMMENT, comment.as_bytes());
+        }
+        push_option(&mut section, OPTION_SHB_OS, std::env::consts::OS.as_bytes());
+        push_option(
+            &mut section,
+            OPTION_SHB_USERAPPL,
+            format!("wifi_scan {}", env!("CARGO_PKG_VERSION")).as_bytes(),
+        );
+        push_option(&mut section, OPTION_END, &[]);
+        write_block(&mut self.writer, PCAPNG_SECTION_HEADER, &section)?;
+
+        let mut interface = Vec::new();
+        interface.extend_from_slice(&(LINKTYPE_IEEE802_11_RADIOTAP as u16).to_le_bytes());
+        interface.extend_from_slice(&[0x00, 0x00]);
+        // no snapshot length limit
+        interface.extend_from_slice(&0u32.to_le_bytes());
+        push_option(&mut interface, OPTION_IF_NAME, b"wifi_scan");
+        push_option(&mut interface, OPTION_END, &[]);
+        write_block(&mut self.writer, PCAPNG_INTERFACE_DESCRIPTION, &interface)
+    }
+}
+
+/// Builds a radiotap header with flags, rate, channel and, if known, antenna signal
+fn radiotap_header(wifi: &Wifi) -> Vec<u8> {
+    let frequency = match wifi.band() {
+        Some(band) if wifi.frequency != 0 => Some((band, wifi.frequency)),
+        Some(band) if wifi.channel != 0 => Some((band, band.channel_frequency(wifi.channel))),
+        _ => None,
+    };
+    let signal = normalize_rssi(wifi.signal_level);
+
+    // flags without FCS, beacon rate of 1 Mbps in 2.4 GHz and 6 Mbps elsewhere
+    let rate = match frequency {
+        Some((Band::TwoPointFourGhz, _)) | None => 2,
+        Some(_) => 12,
+    };
+    let mut present: u32 = 1 << 1 | 1 << 2;
+    let mut fields = vec![0, rate];
+    if let Some((band, frequency)) = frequency {
+        present |= 1 << 3;
+        let modulation = if band == Band::TwoPointFourGhz {
+            channel_flags::SPECTRUM_2GHZ | channel_flags::CCK
+        } else {
+            channel_flags::SPECTRUM_5GHZ | channel_flags::OFDM
+        };
+        fields.extend_from_slice(&(frequency as u16).to_le_bytes());
+        fields.extend_from_slice(&modulation.to_le_bytes());
+    }
+    if signal != 0 {
+        present |= 1 << 5;
+        fields.push(signal.clamp(i8::MIN as i32, -1) as i8 as u8);
+    }
+
+    let mut header = vec![0x00, 0x00];
+    header.extend_from_slice(&((8 + fields.len()) as u16).to_le_bytes());
+    header.extend_from_slice(&present.to_le_bytes());
+    header.extend(fields);
+    header
+}
+
+/// Builds a beacon frame without FCS
+fn beacon(wifi: &Wifi, bssid: [u8; 6], sequence: u16) -> Vec<u8> {
+    let mut frame = vec![SUBTYPE_BEACON << 4, 0x00, 0x00, 0x00];
+    frame.extend_from_slice(&[0xff; 6]);
+    frame.extend_from_slice(&bssid);
+    frame.extend_from_slice(&bssid);
+    frame.extend_from_slice(&(sequence << 4).to_le_bytes());
+
+    // stored RSN elements imply encryption even if the security is unknown, e.g. for OWE
+    let privacy = wifi.security.iter().any(|s| {
+        !matches!(
+            s,
+            WifiSecurity::Open | WifiSecurity::Unknown | WifiSecurity::Other(_)
+        )
+    }) || security::rsn(&wifi.information_elements).is_some();
+    let mut capability = CAPABILITY_ESS;
+    if privacy {
+        capability |= CAPABILITY_PRIVACY;
+    }
+    // timestamp
+    frame.extend_from_slice(&[0; 8]);
+    frame.extend_from_slice(&BEACON_INTERVAL.to_le_bytes());
+    frame.extend_from_slice(&capability.to_le_bytes());
+
+    if wifi.information_elements.is_empty() {
+        frame.extend(elements(wifi));
+    } else {
+        frame.extend_from_slice(&wifi.information_elements);
+    }
+    frame
+}
+
+/// Synthesises the information elements of a network
+fn elements(wifi: &Wifi) -> Vec<u8> {
+    let ssid = wifi.ssid.as_bytes();
+    let ssid = &ssid[..ssid.len().min(32)];
+    let mut elements = vec![ie::SSID, ssid.len() as u8];
+    elements.extend_from_slice(ssid);
+
+    // basic rates are flagged with the most significant bit
+    if matches!(wifi.band(), Some(Band::TwoPointFourGhz) | None) {
+        elements.extend_from_slice(&[ie::SUPPORTED_RATES, 8]);
+        elements.extend_from_slice(&[0x82, 0x84, 0x8b, 0x96, 0x0c, 0x12, 0x18, 0x24]);
+        if wifi.channel != 0 {
+            elements.extend_from_slice(&[ie::DS_PARAMETER_SET, 1, wifi.channel as u8]);
+        }
+    } else {
+        elements.extend_from_slice(&[ie::SUPPORTED_RATES, 8]);
+        elements.extend_from_slice(&[0x8c, 0x12, 0x98, 0x24, 0xb0, 0x48, 0x60, 0x6c]);
+    }
+
+    elements.extend(security::to_elements(&wifi.security));
+    elements
+}
+
+/// Appends an option, padded to 32 bits. Values are truncated to the maximum length.
+fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    let value = &value[..value.len().min(u16::MAX as usize)];
+    body.extend_from_slice(&code.to_le_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    body.extend_from_slice(value);
+    pad(body);
+}
+
+fn pad(body: &mut Vec<u8>) {
+    body.resize(body.len().next_multiple_of(4), 0);
+}
+
+fn write_block<W: Write>(writer: &mut W, block_type: u32, body: &[u8]) -> io::Result<()> {
+    let length = (12 + body.len()) as u32;
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&length.to_le_bytes())?;
+    writer.write_all(body)?;
+    writer.write_all(&length.to_le_bytes())
+}
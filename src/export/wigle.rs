@@ -0,0 +1,251 @@
+//! [WiGLE](https://wigle.net) CSV export (format version 1.4) for wardriving data.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use wifi_scan::export::{wigle::WigleWriter, GpsFix};
+//!
+//! let mut writer = WigleWriter::new(File::create("wardrive.csv").unwrap());
+//! let fix = GpsFix {
+//!     altitude: Some(34.0),
+//!     accuracy: Some(5.0),
+//!     ..GpsFix::new(52.5200, 13.4050)
+//! };
+//! writer.write(&wifi_scan::scan().unwrap(), &fix).unwrap();
+//! writer.flush().unwrap();
+//! ```
+//!
+//! Networks without BSSID (e.g. on macOS without location access) are skipped,
+//! as WiGLE identifies networks by their BSSID.
+
+use std::{
+    io::{self, Write},
+    time::SystemTime,
+};
+
+use super::{GpsFix, Observation, ScanRecord};
+use crate::{
+    misc::{escape_csv, format_mac, parse_mac, UtcDateTime},
+    security::{self, Rsn},
+    signal::normalize_rssi,
+    Wifi, WifiSecurity,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Format version written in the pre-header
+pub const FORMAT_VERSION: &str = "WigleWifi-1.4";
+
+const HEADER: &str = "MAC,SSID,AuthMode,FirstSeen,Channel,RSSI,CurrentLatitude,\
+                      CurrentLongitude,AltitudeMeters,AccuracyMeters,Type";
+
+/// Information about the recording device written in the pre-header
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceInfo {
+    pub app_release: String,
+    pub model: String,
+    pub release: String,
+    pub device: String,
+    pub display: String,
+    pub board: String,
+    pub brand: String,
+}
+
+impl Default for DeviceInfo {
+    fn default() -> Self {
+        DeviceInfo {
+            app_release: env!("CARGO_PKG_VERSION").to_string(),
+            model: "wifi_scan".to_string(),
+            release: std::env::consts::OS.to_string(),
+            device: String::new(),
+            display: String::new(),
+            board: String::new(),
+            brand: String::new(),
+        }
+    }
+}
+
+/// Writes observations as WiGLE CSV.
+/// The pre-header and header rows are written before the first observation.
+pub struct WigleWriter<W: Write> {
+    writer: W,
+    device: DeviceInfo,
+    header_written: bool,
+}
+
+impl<W: Write> WigleWriter<W> {
+    pub fn new(writer: W) -> Self {
+        WigleWriter {
+            writer,
+            device: DeviceInfo::default(),
+            header_written: false,
+        }
+    }
+
+    /// Sets the device information of the pre-header.
+    /// Has no effect once the header has been written.
+    pub fn device(mut self, device: DeviceInfo) -> Self {
+        if !self.header_written {
+            self.device = device;
+        }
+        self
+    }
+
+    /// Writes the networks of a scan made at the given position.
+    /// The time of the fix is used as time of the scan, or the current time if it has none.
+    pub fn write(&mut self, wifis: &[Wifi], fix: &GpsFix) -> io::Result<()> {
+        let timestamp = fix.timestamp.unwrap_or_else(SystemTime::now);
+        for wifi in wifis {
+            let record = ScanRecord::new(wifi.clone(), timestamp);
+            self.write_observation(&Observation::new(record, *fix))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single observation
+    pub fn write_observation(&mut self, observation: &Observation) -> io::Result<()> {
+        self.write_header()?;
+
+        let wifi = &observation.record.wifi;
+        if wifi.mac.is_empty() {
+            return Ok(());
+        }
+        let mac = parse_mac(&wifi.mac).map_or(wifi.mac.clone(), |mac| format_mac(&mac));
+        let first_seen = observation
+            .record
+            .timestamp
+            .or(observation.fix.timestamp)
+            .map(format_first_seen)
+            .unwrap_or_default();
+        let fix = &observation.fix;
+
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{},{:.8},{:.8},{},{},WIFI",
+            escape_csv(&mac),
+            escape_csv(&wifi.ssid),
+            escape_csv(&auth_mode(wifi)),
+            first_seen,
+            wifi.channel,
+            normalize_rssi(wifi.signal_level),
+            fix.latitude,
+            fix.longitude,
+            fix.altitude.unwrap_or(0.0),
+            fix.accuracy.unwrap_or(0.0)
+        )
+    }
+
+    /// Writes the header if nothing has been written yet and flushes the writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_header()?;
+        self.writer.flush()
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if self.header_written {
+            return Ok(());
+        }
+        self.header_written = true;
+
+        let d = &self.device;
+        let value = |text: &str| text.replace([',', '\n', '\r'], " ");
+        writeln!(
+            self.writer,
+            "{},appRelease={},model={},release={},device={},display={},board={},brand={}",
+            FORMAT_VERSION,
+            value(&d.app_release),
+            value(&d.model),
+            value(&d.release),
+            value(&d.device),
+            value(&d.display),
+            value(&d.board),
+            value(&d.brand)
+        )?;
+        writeln!(self.writer, "{}", HEADER)
+    }
+}
+
+/// Returns the capabilities string WiGLE expects as `AuthMode`, in the format used by
+/// Android, e.g. `[WPA2-PSK-CCMP][WPS][ESS]`.
+/// Ciphers are only included if the information elements are available.
+pub fn auth_mode(wifi: &Wifi) -> String {
+    let ies = &wifi.information_elements;
+    let rsn = security::rsn(ies);
+    let ciphers = |element: Option<&Rsn>| {
+        element.map_or(String::new(), |e| {
+            e.pairwise_ciphers
+                .iter()
+                .filter_map(|c| security::cipher_name(*c))
+                .collect::<Vec<_>>()
+                .join("+")
+        })
+    };
+    let rsn_ciphers = ciphers(rsn.as_ref());
+    let wpa_ciphers = ciphers(security::wpa(ies).as_ref());
+    let owe = rsn
+        .as_ref()
+        .is_some_and(|r| r.akms.contains(&security::AKM_OWE));
+
+    let mut mode = String::new();
+    for s in &wifi.security {
+        let (protocol, akm, ciphers) = match s {
+            WifiSecurity::Open | WifiSecurity::TunneledDirectLinkSetup => continue,
+            WifiSecurity::Unknown if owe => continue,
+            WifiSecurity::Unknown => {
+                mode.push_str("[UNKNOWN]");
+                continue;
+            }
+            WifiSecurity::Other(name) => {
+                mode.push_str(&format!("[{}]", name.replace(['[', ']'], "")));
+                continue;
+            }
+            WifiSecurity::Wep => {
+                mode.push_str("[WEP]");
+                continue;
+            }
+            WifiSecurity::WpaPersonalPsk => ("WPA", "PSK", &wpa_ciphers),
+            WifiSecurity::WpaEnterpriseEap => ("WPA", "EAP", &wpa_ciphers),
+            WifiSecurity::Wpa2PersonalPsk => ("WPA2", "PSK", &rsn_ciphers),
+            WifiSecurity::Wpa2PersonalPskFt => ("WPA2", "FT/PSK", &rsn_ciphers),
+            WifiSecurity::Wpa2PersonalPsk256 => ("WPA2", "PSK-SHA256", &rsn_ciphers),
+            WifiSecurity::Wpa2EnterpriseEap => ("WPA2", "EAP", &rsn_ciphers),
+            WifiSecurity::Wpa2EnterpriseEapFt => ("WPA2", "FT/EAP", &rsn_ciphers),
+            WifiSecurity::Wpa2EnterpriseEap256 => ("WPA2", "EAP-SHA256", &rsn_ciphers),
+            WifiSecurity::Wpa3PersonalSae => ("WPA3", "SAE", &rsn_ciphers),
+            WifiSecurity::Wpa3PersonalSaeFt => ("WPA3", "FT/SAE", &rsn_ciphers),
+            WifiSecurity::Wpa3EnterpriseEap => ("WPA3", "EAP", &rsn_ciphers),
+            WifiSecurity::Wpa3EnterpriseEap256 => ("WPA3", "EAP-SHA256", &rsn_ciphers),
+            WifiSecurity::Wpa3EnterpriseSuiteBEap256 => ("WPA3", "EAP-SUITE-B-192", &rsn_ciphers),
+        };
+
+        mode.push_str(&format!("[{}-{}", protocol, akm));
+        if !ciphers.is_empty() {
+            mode.push_str(&format!("-{}", ciphers));
+        }
+        mode.push(']');
+    }
+
+    if owe {
+        mode.push_str("[OWE]");
+    }
+    if wifi.is_wps_enabled() {
+        mode.push_str("[WPS]");
+    }
+    mode.push_str("[ESS]");
+    mode
+}
+
+/// Formats a time as `yyyy-MM-dd HH:mm:ss` in UTC
+fn format_first_seen(time: SystemTime) -> String {
+    let t = UtcDateTime::from_system_time(time);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        t.year, t.month, t.day, t.hour, t.minute, t.second
+    )
+}
@@ -0,0 +1,318 @@
+//! Positioning by matching scans against recorded RF fingerprints.
+//!
+//! ```no_run
+//! use wifi_scan::fingerprint::FingerprintDb;
+//!
+//! let mut db = FingerprintDb::new();
+//! db.record("Kitchen", &wifi_scan::scan().unwrap());
+//! db.save("fingerprints.csv").unwrap();
+//!
+//! let db = FingerprintDb::load("fingerprints.csv").unwrap();
+//! if let Some(best) = db.best_match(&wifi_scan::scan().unwrap()) {
+//!     println!("{} ({:.0} %)", best.label, best.confidence * 100.0);
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    misc::{escape_csv, format_mac, parse_mac, split_csv_line},
+    Wifi,
+};
+
+/// Number of nearest fingerprints considered by default
+pub const DEFAULT_K: usize = 3;
+/// Signal level in dBm assumed for access points missing from a scan or fingerprint
+pub const MISSING_SIGNAL: i32 = -100;
+
+/// Signal levels observed at a labelled location
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fingerprint {
+    /// Name of the location
+    pub label: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::mac_map"))]
+    signals: HashMap<[u8; 6], i32>,
+}
+
+/// A location matching a scan
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocationMatch {
+    /// Name of the location
+    pub label: String,
+    /// Share of the neighbour weight voting for this location, from 0.0 to 1.0
+    pub confidence: f64,
+    /// RMS signal difference in dB to the closest fingerprint of this location
+    pub distance: f64,
+}
+
+/// A collection of recorded fingerprints
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FingerprintDb {
+    fingerprints: Vec<Fingerprint>,
+}
+
+impl Fingerprint {
+    /// Creates a fingerprint without any signal levels
+    pub fn new(label: &str) -> Self {
+        Fingerprint {
+            label: label.to_string(),
+            signals: HashMap::new(),
+        }
+    }
+
+    /// Creates a fingerprint from scan results. Networks with an unknown signal level or
+    /// invalid BSSID are skipped; if a BSSID appears more than once the strongest level is kept.
+    pub fn from_scan(label: &str, wifis: &[Wifi]) -> Self {
+        let mut fingerprint = Fingerprint::new(label);
+        for wifi in wifis {
+            let Some(mac) = parse_mac(&wifi.mac) else {
+                continue;
+            };
+            if wifi.signal_level == 0 {
+                continue;
+            }
+            let level = fingerprint.signals.entry(mac).or_insert(i32::MIN);
+            *level = (*level).max(wifi.signal_level);
+        }
+
+        fingerprint
+    }
+
+    /// Adds or replaces the signal level in dBm of an access point.
+    /// Returns `false` if the BSSID is invalid.
+    pub fn insert(&mut self, bssid: &str, level: i32) -> bool {
+        match parse_mac(bssid) {
+            Some(mac) => {
+                self.signals.insert(mac, level);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the signal level in dBm of an access point
+    pub fn get(&self, bssid: &str) -> Option<i32> {
+        self.signals.get(&parse_mac(bssid)?).copied()
+    }
+
+    /// Returns the signal levels in dBm by lowercase, colon separated BSSID, sorted by BSSID
+    pub fn signals(&self) -> Vec<(String, i32)> {
+        let mut signals: Vec<(&[u8; 6], &i32)> = self.signals.iter().collect();
+        signals.sort();
+        signals
+            .into_iter()
+            .map(|(mac, level)| (format_mac(mac), *level))
+            .collect()
+    }
+
+    /// Returns the number of access points in the fingerprint
+    pub fn len(&self) -> usize {
+        self.signals.len()
+    }
+
+    /// Returns `true` if the fingerprint has no signal levels
+    pub fn is_empty(&self) -> bool {
+        self.signals.is_empty()
+    }
+
+    /// Returns the RMS signal difference in dB to another set of signal levels.
+    /// Access points missing on one side count as [`MISSING_SIGNAL`].
+    /// Returns `None` if both have no access point in common.
+    fn distance(&self, signals: &HashMap<[u8; 6], i32>) -> Option<f64> {
+        if !self.signals.keys().any(|bssid| signals.contains_key(bssid)) {
+            return None;
+        }
+
+        let mut sum = 0.0;
+        let mut count = 0;
+        for (bssid, level) in &self.signals {
+            let other = signals.get(bssid).copied().unwrap_or(MISSING_SIGNAL);
+            sum += ((level - other) as f64).powi(2);
+            count += 1;
+        }
+        for (_, level) in signals
+            .iter()
+            .filter(|(b, _)| !self.signals.contains_key(*b))
+        {
+            sum += ((level - MISSING_SIGNAL) as f64).powi(2);
+            count += 1;
+        }
+
+        Some((sum / count as f64).sqrt())
+    }
+}
+
+impl FingerprintDb {
+    /// Creates an empty database
+    pub fn new() -> Self {
+        FingerprintDb::default()
+    }
+
+    /// Records scan results taken at the location `label`.
+    /// Recording several scans per location improves matching.
+    pub fn record(&mut self, label: &str, wifis: &[Wifi]) {
+        self.add(Fingerprint::from_scan(label, wifis));
+    }
+
+    /// Adds a fingerprint. Fingerprints without any signal are ignored.
+    pub fn add(&mut self, fingerprint: Fingerprint) {
+        if !fingerprint.is_empty() {
+            self.fingerprints.push(fingerprint);
+        }
+    }
+
+    /// Removes all fingerprints of a location
+    pub fn remove_label(&mut self, label: &str) {
+        self.fingerprints.retain(|f| f.label != label);
+    }
+
+    /// Returns all recorded fingerprints
+    pub fn fingerprints(&self) -> &[Fingerprint] {
+        &self.fingerprints
+    }
+
+    /// Returns the recorded locations without duplicates
+    pub fn labels(&self) -> Vec<&str> {
+        let mut labels: Vec<&str> = Vec::new();
+        for fingerprint in &self.fingerprints {
+            if !labels.contains(&fingerprint.label.as_str()) {
+                labels.push(&fingerprint.label);
+            }
+        }
+        labels
+    }
+
+    /// Returns the number of recorded fingerprints
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Returns `true` if no fingerprints are recorded
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    /// Matches scan results against the database using [`DEFAULT_K`] neighbours,
+    /// see [`FingerprintDb::matches_with`]
+    pub fn matches(&self, wifis: &[Wifi]) -> Vec<LocationMatch> {
+        self.matches_with(wifis, DEFAULT_K)
+    }
+
+    /// Matches scan results against the database with weighted k-nearest-neighbour.
+    /// The `k` fingerprints with the smallest RMS signal difference vote for their location,
+    /// weighted by the inverse of the difference. Returns the locations sorted by confidence,
+    /// most likely first. Fingerprints sharing no access point with the scan are ignored.
+    pub fn matches_with(&self, wifis: &[Wifi], k: usize) -> Vec<LocationMatch> {
+        let scan = Fingerprint::from_scan("", wifis);
+
+        let mut neighbours: Vec<(&Fingerprint, f64)> = self
+            .fingerprints
+            .iter()
+            .filter_map(|f| f.distance(&scan.signals).map(|d| (f, d)))
+            .collect();
+        neighbours.sort_by(|a, b| a.1.total_cmp(&b.1));
+        neighbours.truncate(k.max(1));
+
+        let mut matches: Vec<LocationMatch> = Vec::new();
+        let mut total_weight = 0.0;
+        for (fingerprint, distance) in neighbours {
+            let weight = 1.0 / distance.max(0.1);
+            total_weight += weight;
+
+            match matches.iter_mut().find(|m| m.label == fingerprint.label) {
+                Some(m) => {
+                    m.confidence += weight;
+                    m.distance = m.distance.min(distance);
+                }
+                None => matches.push(LocationMatch {
+                    label: fingerprint.label.clone(),
+                    confidence: weight,
+                    distance,
+                }),
+            }
+        }
+
+        for m in &mut matches {
+            m.confidence /= total_weight;
+        }
+        matches.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        matches
+    }
+
+    /// Returns the most likely location of scan results
+    pub fn best_match(&self, wifis: &[Wifi]) -> Option<LocationMatch> {
+        self.matches(wifis).into_iter().next()
+    }
+
+    /// Writes the database as CSV, one fingerprint per line:
+    /// the label followed by pairs of BSSID and signal level.
+    /// Line breaks in labels are replaced with spaces.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for fingerprint in &self.fingerprints {
+            let label = fingerprint.label.replace(['\n', '\r'], " ");
+            write!(writer, "{}", escape_csv(&label))?;
+
+            for (bssid, level) in fingerprint.signals() {
+                write!(writer, ",{},{}", bssid, level)?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a database written by [`FingerprintDb::write`]
+    pub fn read<R: BufRead>(reader: R) -> io::Result<FingerprintDb> {
+        let mut db = FingerprintDb::new();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields = split_csv_line(&line);
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid fingerprint on line {}", number + 1),
+                )
+            };
+            if fields.len() % 2 != 1 {
+                return Err(invalid());
+            }
+
+            let mut fingerprint = Fingerprint::new(&fields[0]);
+            for pair in fields[1..].chunks_exact(2) {
+                let level = pair[1].trim().parse::<i32>().map_err(|_| invalid())?;
+                if !fingerprint.insert(pair[0].trim(), level) {
+                    return Err(invalid());
+                }
+            }
+
+            db.add(fingerprint);
+        }
+
+        Ok(db)
+    }
+
+    /// Saves the database to a file, see [`FingerprintDb::write`]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(BufWriter::new(File::create(path)?))
+    }
+
+    /// Loads a database from a file, see [`FingerprintDb::read`]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<FingerprintDb> {
+        FingerprintDb::read(BufReader::new(File::open(path)?))
+    }
+}
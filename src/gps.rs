@@ -0,0 +1,105 @@
+//! Geotagged scans, available with the `gps` feature.
+//!
+//! Positions come from a [`GpsSource`]: [`gpsd::GpsdClient`] talks to a local gpsd over
+//! TCP and [`nmea::NmeaReader`] reads NMEA 0183 sentences from a serial port or log file.
+//!
+//! ```no_run
+//! use wifi_scan::gps::{self, gpsd::{GpsdClient, DEFAULT_ADDRESS}};
+//!
+//! let mut gpsd = GpsdClient::connect(DEFAULT_ADDRESS).unwrap();
+//! let snapshot = gps::scan(&mut gpsd).unwrap();
+//! if let Some(fix) = snapshot.fix {
+//!     println!("{} networks at {}, {}", snapshot.wifis.len(), fix.latitude, fix.longitude);
+//! }
+//! ```
+
+pub mod gpsd;
+pub mod nmea;
+
+use std::{io, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    export::{FixQuality, GpsFix, Observation, ScanRecord},
+    Error, Result, Wifi, WlanScanner,
+};
+
+/// A source of position fixes
+pub trait GpsSource {
+    /// Returns the current position, or `None` if none is available
+    fn current_fix(&mut self) -> io::Result<Option<GpsFix>>;
+}
+
+/// The networks of a single scan together with the position at the end of the scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[serde(with = "timestamp")]
+    pub timestamp: SystemTime,
+    pub wifis: Vec<Wifi>,
+    /// Position at the end of the scan. `None` if the receiver had no valid position.
+    pub fix: Option<GpsFix>,
+}
+
+impl Snapshot {
+    /// Tags the networks of a scan made now with a position.
+    /// Fixes without a valid position are dropped.
+    pub fn new(wifis: Vec<Wifi>, fix: Option<GpsFix>) -> Self {
+        Snapshot {
+            timestamp: SystemTime::now(),
+            wifis,
+            fix: fix.filter(|f| f.quality != Some(FixQuality::NoFix)),
+        }
+    }
+
+    /// Scans with the given scanner and tags the result with the current position
+    pub fn capture<S: WlanScanner, G: GpsSource>(scanner: &mut S, gps: &mut G) -> Result<Self> {
+        let wifis = scanner.scan()?;
+        let fix = gps.current_fix().map_err(|e| Error::Gps(e.to_string()))?;
+        Ok(Snapshot::new(wifis, fix))
+    }
+
+    /// Returns the networks as timestamped records
+    pub fn records(&self) -> Vec<ScanRecord> {
+        self.wifis
+            .iter()
+            .map(|wifi| ScanRecord::new(wifi.clone(), self.timestamp))
+            .collect()
+    }
+
+    /// Returns the networks as observations for geographic exports.
+    /// Empty if the snapshot has no position.
+    pub fn observations(&self) -> Vec<Observation> {
+        match self.fix {
+            Some(fix) => self
+                .records()
+                .into_iter()
+                .map(|record| Observation::new(record, fix))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Scans for networks and tags the result with the current position of the GPS source
+pub fn scan<G: GpsSource>(gps: &mut G) -> Result<Snapshot> {
+    let wifis = crate::scan()?;
+    let fix = gps.current_fix().map_err(|e| Error::Gps(e.to_string()))?;
+    Ok(Snapshot::new(wifis, fix))
+}
+
+/// Snapshot timestamps as RFC 3339 strings
+mod timestamp {
+    use std::time::SystemTime;
+
+    use serde::{de::Error, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::schema::timestamp::serialize(&Some(*time), serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        crate::schema::timestamp::deserialize(deserializer)?
+            .ok_or_else(|| D::Error::custom("missing timestamp"))
+    }
+}
@@ -0,0 +1,172 @@
+//! Client for the JSON protocol of [gpsd](https://gpsd.io/gpsd_json.html).
+//!
+//! ```no_run
+//! use wifi_scan::gps::gpsd::{GpsdClient, DEFAULT_ADDRESS};
+//!
+//! let mut gpsd = GpsdClient::connect(DEFAULT_ADDRESS).unwrap();
+//! loop {
+//!     let fix = gpsd.next_fix().unwrap();
+//!     println!("{:?}: {}, {}", fix.quality, fix.latitude, fix.longitude);
+//! }
+//! ```
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::GpsSource;
+use crate::{
+    export::{FixQuality, GpsFix},
+    misc::parse_rfc3339,
+};
+
+/// Address gpsd listens on by default
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:2947";
+
+/// Read timeout set on new connections
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const WATCH: &str = "?WATCH={\"enable\":true,\"json\":true};\n";
+const POLL: &str = "?POLL;\n";
+
+/// Time-position-velocity report
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Tpv {
+    #[serde(default)]
+    mode: u8,
+    status: Option<u8>,
+    time: Option<String>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// Deprecated in favour of `altMSL`
+    alt: Option<f64>,
+    #[serde(rename = "altMSL")]
+    alt_msl: Option<f64>,
+    eph: Option<f64>,
+    epx: Option<f64>,
+    epy: Option<f64>,
+}
+
+impl Tpv {
+    fn into_fix(self) -> GpsFix {
+        let quality = match (self.mode, self.status) {
+            (0 | 1, _) | (_, Some(7)) => FixQuality::NoFix,
+            (_, Some(2)) => FixQuality::Dgps,
+            (_, Some(3)) => FixQuality::RtkFixed,
+            (_, Some(4)) => FixQuality::RtkFloat,
+            (_, Some(5 | 6)) => FixQuality::Estimated,
+            (_, Some(8)) => FixQuality::Simulated,
+            (2, _) => FixQuality::Fix2d,
+            _ => FixQuality::Fix3d,
+        };
+        let (latitude, longitude, quality) = match (self.lat, self.lon) {
+            (Some(lat), Some(lon)) => (lat, lon, quality),
+            _ => (0.0, 0.0, FixQuality::NoFix),
+        };
+        let accuracy = self.eph.or(match (self.epx, self.epy) {
+            (Some(x), Some(y)) => Some(x.max(y)),
+            _ => None,
+        });
+
+        GpsFix {
+            latitude,
+            longitude,
+            altitude: self
+                .alt_msl
+                .or(self.alt)
+                .filter(|_| quality != FixQuality::Fix2d),
+            accuracy,
+            quality: Some(quality),
+            timestamp: self.time.as_deref().and_then(parse_rfc3339),
+        }
+    }
+}
+
+/// A connection to gpsd with watching enabled.
+/// Check [`GpsFix::quality`] of returned fixes: without fix the position is zero.
+pub struct GpsdClient {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl GpsdClient {
+    /// Connects to gpsd and enables watching for JSON reports
+    pub fn connect<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+        let mut stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(DEFAULT_TIMEOUT))?;
+        stream.write_all(WATCH.as_bytes())?;
+        let reader = BufReader::new(stream.try_clone()?);
+        Ok(GpsdClient { stream, reader })
+    }
+
+    /// Sets the read timeout. `None` blocks until gpsd sends data.
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.stream.set_read_timeout(timeout)
+    }
+
+    /// Waits for the next time-position-velocity report
+    pub fn next_fix(&mut self) -> io::Result<GpsFix> {
+        loop {
+            let message = self.read_message()?;
+            if message["class"] == "TPV" {
+                return tpv(message).map(Tpv::into_fix);
+            }
+        }
+    }
+
+    /// Asks gpsd for the last fix of the active receivers.
+    /// Returns `None` if no receiver has reported yet.
+    pub fn poll(&mut self) -> io::Result<Option<GpsFix>> {
+        self.stream.write_all(POLL.as_bytes())?;
+        loop {
+            let mut message = self.read_message()?;
+            if message["class"] == "POLL" {
+                let reports = match message["tpv"].take() {
+                    Value::Array(reports) => reports,
+                    _ => Vec::new(),
+                };
+                let fixes = reports
+                    .into_iter()
+                    .map(|report| tpv(report).map(Tpv::into_fix))
+                    .collect::<io::Result<Vec<GpsFix>>>()?;
+                // prefer the best receiver if several are active
+                return Ok(fixes
+                    .into_iter()
+                    .max_by_key(|f| f.quality.is_some_and(|q| q.has_position())));
+            }
+        }
+    }
+
+    fn read_message(&mut self) -> io::Result<Value> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "gpsd closed the connection",
+                ));
+            }
+            if !line.trim().is_empty() {
+                return serde_json::from_str(&line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+        }
+    }
+}
+
+impl GpsSource for GpsdClient {
+    fn current_fix(&mut self) -> io::Result<Option<GpsFix>> {
+        self.poll()
+    }
+}
+
+fn tpv(value: Value) -> io::Result<Tpv> {
+    serde_json::from_value(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
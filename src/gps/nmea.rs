@@ -0,0 +1,264 @@
+//! Parser for NMEA 0183 sentences from serial GPS receivers or log files.
+//!
+//! Positions are taken from `GGA` and `RMC` sentences. `RMC` and `ZDA` provide the date,
+//! `GSA` distinguishes 2D and 3D fixes and `GST` provides the accuracy. The fix quality
+//! is the one of the latest `GGA` sentence, unless a later `RMC` sentence reports a
+//! different mode.
+//! Sentences from all talkers (`GP`, `GN`, `GL`, ...) are accepted. Checksums are
+//! verified if present.
+//!
+//! ```no_run
+//! use std::{fs::File, io::BufReader};
+//! use wifi_scan::gps::nmea::NmeaReader;
+//!
+//! let port = File::open("/dev/ttyUSB0").unwrap();
+//! for fix in NmeaReader::new(BufReader::new(port)) {
+//!     let fix = fix.unwrap();
+//!     println!("{:?}: {}, {}", fix.quality, fix.latitude, fix.longitude);
+//! }
+//! ```
+
+use std::io::{self, BufRead};
+
+use super::GpsSource;
+use crate::{
+    export::{FixQuality, GpsFix},
+    misc::UtcDateTime,
+};
+
+/// Combines NMEA sentences into position fixes
+#[derive(Debug, Clone, Default)]
+pub struct NmeaParser {
+    fix: GpsFix,
+    /// Year, month and day of the last `RMC` or `ZDA` sentence
+    date: Option<(i64, u32, u32)>,
+    /// `true` for a 3D fix according to the last `GSA` sentence
+    three_d: Option<bool>,
+}
+
+impl NmeaParser {
+    pub fn new() -> Self {
+        NmeaParser::default()
+    }
+
+    /// Returns the current fix, or `None` if no position sentence has been parsed yet
+    pub fn fix(&self) -> Option<GpsFix> {
+        self.fix.quality.map(|_| self.fix)
+    }
+
+    /// Parses a single sentence like `$GPGGA,...*47`.
+    /// Returns the updated fix after `GGA` and `RMC` sentences, `None` for other
+    /// sentences and sentences that are malformed or have a wrong checksum.
+    pub fn parse(&mut self, sentence: &str) -> Option<GpsFix> {
+        let body = sentence.trim().strip_prefix('$')?;
+        let body = match body.split_once('*') {
+            Some((body, checksum)) => {
+                let expected = u8::from_str_radix(checksum.trim(), 16).ok()?;
+                if body.bytes().fold(0, |sum, b| sum ^ b) != expected {
+                    return None;
+                }
+                body
+            }
+            None => body,
+        };
+
+        let fields: Vec<&str> = body.split(',').collect();
+        let address = fields[0];
+        if address.len() != 5 || address.starts_with('P') {
+            return None;
+        }
+
+        match address.get(2..)? {
+            "GGA" => self.parse_gga(&fields),
+            "RMC" => self.parse_rmc(&fields),
+            "GSA" => {
+                self.three_d = match *fields.get(2)? {
+                    "3" => Some(true),
+                    "2" => Some(false),
+                    _ => None,
+                };
+                None
+            }
+            "GST" => {
+                let latitude_error: f64 = fields.get(6)?.parse().ok()?;
+                let longitude_error: f64 = fields.get(7)?.parse().ok()?;
+                self.fix.accuracy = Some(latitude_error.hypot(longitude_error));
+                None
+            }
+            "ZDA" => {
+                self.date = Some((
+                    fields.get(4)?.parse().ok()?,
+                    fields.get(3)?.parse().ok()?,
+                    fields.get(2)?.parse().ok()?,
+                ));
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// `$--GGA,time,lat,N/S,lon,E/W,quality,satellites,hdop,altitude,M,...`
+    fn parse_gga(&mut self, fields: &[&str]) -> Option<GpsFix> {
+        let quality = match fields.get(6)?.parse::<u8>().ok()? {
+            0 => FixQuality::NoFix,
+            1 | 3 if self.three_d == Some(false) => FixQuality::Fix2d,
+            1 | 3 => FixQuality::Fix3d,
+            2 => FixQuality::Dgps,
+            4 => FixQuality::RtkFixed,
+            5 => FixQuality::RtkFloat,
+            6 => FixQuality::Estimated,
+            7 => FixQuality::Manual,
+            8 => FixQuality::Simulated,
+            _ => return None,
+        };
+
+        if quality.has_position() {
+            self.fix.latitude = coordinate(fields.get(2)?, fields.get(3)?)?;
+            self.fix.longitude = coordinate(fields.get(4)?, fields.get(5)?)?;
+            self.fix.altitude = fields.get(9).and_then(|a| a.parse().ok());
+        }
+        self.fix.quality = Some(quality);
+        self.set_time(fields.get(1)?);
+        Some(self.fix)
+    }
+
+    /// `$--RMC,time,status,lat,N/S,lon,E/W,speed,course,date,variation,E/W,mode`
+    fn parse_rmc(&mut self, fields: &[&str]) -> Option<GpsFix> {
+        if let Some(date) = fields.get(9).and_then(|d| parse_date(d)) {
+            self.date = Some(date);
+        }
+
+        if *fields.get(2)? == "A" {
+            self.fix.latitude = coordinate(fields.get(3)?, fields.get(4)?)?;
+            self.fix.longitude = coordinate(fields.get(5)?, fields.get(6)?)?;
+            let autonomous = if self.three_d == Some(true) {
+                FixQuality::Fix3d
+            } else {
+                FixQuality::Fix2d
+            };
+            // the mode indicator was added in NMEA 2.3
+            let mode = match fields.get(12).copied() {
+                Some("A") => Some(autonomous),
+                Some("D") => Some(FixQuality::Dgps),
+                Some("E") => Some(FixQuality::Estimated),
+                Some("M") => Some(FixQuality::Manual),
+                Some("S") => Some(FixQuality::Simulated),
+                Some("R") => Some(FixQuality::RtkFixed),
+                Some("F") => Some(FixQuality::RtkFloat),
+                _ => None,
+            };
+            let current = self.fix.quality.filter(|q| q.has_position());
+            self.fix.quality = match (mode, current) {
+                // GGA also knows 2D from 3D fixes, keep it if the mode still agrees
+                (
+                    Some(FixQuality::Fix2d | FixQuality::Fix3d),
+                    Some(FixQuality::Fix2d | FixQuality::Fix3d),
+                ) => current,
+                (Some(mode), _) => Some(mode),
+                (None, Some(_)) => current,
+                (None, None) => Some(autonomous),
+            };
+        } else {
+            self.fix.quality = Some(FixQuality::NoFix);
+        }
+
+        self.set_time(fields.get(1)?);
+        Some(self.fix)
+    }
+
+    /// Sets the timestamp from a `hhmmss.ss` time and the last known date
+    fn set_time(&mut self, time: &str) {
+        let parse = |range: std::ops::Range<usize>| time.get(range)?.parse::<u32>().ok();
+        let nanosecond = time
+            .get(6..)
+            .and_then(|f| f.parse::<f64>().ok())
+            .map_or(0, |f| (f * 1e9).round() as u32);
+
+        self.fix.timestamp = self.date.and_then(|(year, month, day)| {
+            UtcDateTime {
+                year,
+                month,
+                day,
+                hour: parse(0..2)?,
+                minute: parse(2..4)?,
+                second: parse(4..6)?,
+                nanosecond: nanosecond.min(999_999_999),
+            }
+            .to_system_time()
+        });
+    }
+}
+
+/// Converts a `ddmm.mmmm` / `dddmm.mmmm` coordinate and hemisphere to degrees
+fn coordinate(value: &str, hemisphere: &str) -> Option<f64> {
+    let value: f64 = value.parse().ok()?;
+    let degrees = (value / 100.0).trunc();
+    let degrees = degrees + (value - degrees * 100.0) / 60.0;
+    match hemisphere {
+        "N" | "E" => Some(degrees),
+        "S" | "W" => Some(-degrees),
+        _ => None,
+    }
+}
+
+/// Parses a `ddmmyy` date. Two digit years are interpreted as 1980 to 2079.
+fn parse_date(date: &str) -> Option<(i64, u32, u32)> {
+    if date.len() != 6 {
+        return None;
+    }
+    let day = date.get(0..2)?.parse().ok()?;
+    let month = date.get(2..4)?.parse().ok()?;
+    let year: i64 = date.get(4..6)?.parse().ok()?;
+    let year = if year >= 80 { 1900 + year } else { 2000 + year };
+    Some((year, month, day))
+}
+
+/// Reads NMEA sentences line by line and yields a fix for every `GGA` and `RMC` sentence.
+/// Other and malformed lines are skipped.
+pub struct NmeaReader<R: BufRead> {
+    reader: R,
+    parser: NmeaParser,
+}
+
+impl<R: BufRead> NmeaReader<R> {
+    pub fn new(reader: R) -> Self {
+        NmeaReader {
+            reader,
+            parser: NmeaParser::new(),
+        }
+    }
+
+    /// Returns the parser with the state of all sentences read so far
+    pub fn parser(&self) -> &NmeaParser {
+        &self.parser
+    }
+}
+
+impl<R: BufRead> Iterator for NmeaReader<R> {
+    type Item = io::Result<GpsFix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            match self.reader.read_until(b'\n', &mut line) {
+                Ok(0) => return None,
+                // serial ports may deliver garbage, which is skipped as malformed
+                Ok(_) => {
+                    if let Some(fix) = self.parser.parse(&String::from_utf8_lossy(&line)) {
+                        return Some(Ok(fix));
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> GpsSource for NmeaReader<R> {
+    /// Reads sentences until the next fix
+    fn current_fix(&mut self) -> io::Result<Option<GpsFix>> {
+        self.next().transpose()
+    }
+}
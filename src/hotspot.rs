@@ -0,0 +1,253 @@
+//! Decoding of Hotspot 2.0 / Passpoint and Interworking information elements.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ie::{self, Elements},
+    misc::format_mac,
+};
+
+const WFA_OUI: [u8; 3] = [0x50, 0x6f, 0x9a];
+const HS20_INDICATION_TYPE: u8 = 0x10;
+
+/// Access network type advertised in the Interworking element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AccessNetworkType {
+    Private,
+    PrivateWithGuestAccess,
+    ChargeablePublic,
+    FreePublic,
+    PersonalDevice,
+    EmergencyServicesOnly,
+    TestOrExperimental,
+    Wildcard,
+    Other(u8),
+}
+
+/// Venue information advertised in the Interworking element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Venue {
+    /// Venue group code (IEEE 802.11 Table 9-62)
+    pub group: u8,
+    /// Venue type code within the group
+    pub venue_type: u8,
+}
+
+/// Advertisement protocol advertised in the Advertisement Protocol element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AdvertisementProtocol {
+    Anqp,
+    MihInformationService,
+    MihCommandAndEventServices,
+    EmergencyAlertSystem,
+    VendorSpecific,
+    Other(u8),
+}
+
+/// Hotspot 2.0 / Passpoint and Interworking information of a network
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HotspotInfo {
+    /// Access network type. `None` if the Interworking element is missing.
+    pub access_network_type: Option<AccessNetworkType>,
+    /// `true` if the network advertises internet access
+    pub internet: bool,
+    /// `true` if additional steps are required for access (e.g. a captive portal)
+    pub additional_step_required: bool,
+    /// `true` if emergency services are reachable
+    pub emergency_services_reachable: bool,
+    /// `true` if unauthenticated emergency service access is possible
+    pub unauthenticated_emergency_access: bool,
+    /// Venue information, if advertised
+    pub venue: Option<Venue>,
+    /// Homogeneous ESS identifier, if advertised
+    pub hessid: Option<String>,
+    /// Roaming consortium OIs as uppercase hex strings
+    pub roaming_consortium: Vec<String>,
+    /// Number of additional OIs that can be queried through ANQP
+    pub anqp_oi_count: u8,
+    /// Advertisement protocols supported by the network
+    pub advertisement_protocols: Vec<AdvertisementProtocol>,
+    /// Hotspot 2.0 release (1, 2 or 3). `None` if the HS2.0 Indication element is missing.
+    pub hs20_release: Option<u8>,
+    /// `true` if downstream group-addressed forwarding is disabled
+    pub dgaf_disabled: bool,
+}
+
+impl fmt::Display for AccessNetworkType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccessNetworkType::Private => write!(f, "Private"),
+            AccessNetworkType::PrivateWithGuestAccess => write!(f, "Private with guest access"),
+            AccessNetworkType::ChargeablePublic => write!(f, "Chargeable public"),
+            AccessNetworkType::FreePublic => write!(f, "Free public"),
+            AccessNetworkType::PersonalDevice => write!(f, "Personal device"),
+            AccessNetworkType::EmergencyServicesOnly => write!(f, "Emergency services only"),
+            AccessNetworkType::TestOrExperimental => write!(f, "Test or experimental"),
+            AccessNetworkType::Wildcard => write!(f, "Wildcard"),
+            AccessNetworkType::Other(value) => write!(f, "Reserved ({})", value),
+        }
+    }
+}
+
+impl From<u8> for AccessNetworkType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AccessNetworkType::Private,
+            1 => AccessNetworkType::PrivateWithGuestAccess,
+            2 => AccessNetworkType::ChargeablePublic,
+            3 => AccessNetworkType::FreePublic,
+            4 => AccessNetworkType::PersonalDevice,
+            5 => AccessNetworkType::EmergencyServicesOnly,
+            14 => AccessNetworkType::TestOrExperimental,
+            15 => AccessNetworkType::Wildcard,
+            other => AccessNetworkType::Other(other),
+        }
+    }
+}
+
+impl From<u8> for AdvertisementProtocol {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AdvertisementProtocol::Anqp,
+            1 => AdvertisementProtocol::MihInformationService,
+            2 => AdvertisementProtocol::MihCommandAndEventServices,
+            3 => AdvertisementProtocol::EmergencyAlertSystem,
+            221 => AdvertisementProtocol::VendorSpecific,
+            other => AdvertisementProtocol::Other(other),
+        }
+    }
+}
+
+impl Venue {
+    /// Returns the name of the venue group
+    pub fn group_name(&self) -> &'static str {
+        match self.group {
+            0 => "Unspecified",
+            1 => "Assembly",
+            2 => "Business",
+            3 => "Educational",
+            4 => "Factory and Industrial",
+            5 => "Institutional",
+            6 => "Mercantile",
+            7 => "Residential",
+            8 => "Storage",
+            9 => "Utility and Miscellaneous",
+            10 => "Vehicular",
+            11 => "Outdoor",
+            _ => "Reserved",
+        }
+    }
+}
+
+impl HotspotInfo {
+    /// Parses the Interworking, Roaming Consortium, Advertisement Protocol and
+    /// HS2.0 Indication elements. Returns `None` if none of them is present.
+    pub fn from_elements(ies: &[u8]) -> Option<HotspotInfo> {
+        let mut info = HotspotInfo::default();
+        let mut found = false;
+
+        if let Some(interworking) = ie::find(ies, ie::INTERWORKING).filter(|d| !d.is_empty()) {
+            found = true;
+            let options = interworking[0];
+            info.access_network_type = Some(AccessNetworkType::from(options & 0x0f));
+            info.internet = options & 0x10 != 0;
+            info.additional_step_required = options & 0x20 != 0;
+            info.emergency_services_reachable = options & 0x40 != 0;
+            info.unauthenticated_emergency_access = options & 0x80 != 0;
+
+            // venue info (2) and HESSID (6) are optional
+            let (venue, hessid) = match interworking.len() {
+                3 => (Some(&interworking[1..3]), None),
+                7 => (None, Some(&interworking[1..7])),
+                9 => (Some(&interworking[1..3]), Some(&interworking[3..9])),
+                _ => (None, None),
+            };
+            info.venue = venue.map(|v| Venue {
+                group: v[0],
+                venue_type: v[1],
+            });
+            info.hessid = hessid.map(format_mac);
+        }
+
+        if let Some(consortium) = ie::find(ies, ie::ROAMING_CONSORTIUM).filter(|d| d.len() >= 2) {
+            found = true;
+            info.anqp_oi_count = consortium[0];
+            let first_length = (consortium[1] & 0x0f) as usize;
+            let second_length = (consortium[1] >> 4) as usize;
+            let ois = &consortium[2..];
+
+            let mut offsets = vec![(0, first_length)];
+            if second_length > 0 {
+                offsets.push((first_length, first_length + second_length));
+            }
+            if ois.len() > first_length + second_length {
+                offsets.push((first_length + second_length, ois.len()));
+            }
+            for (start, end) in offsets {
+                if let Some(oi) = ois.get(start..end).filter(|oi| !oi.is_empty()) {
+                    info.roaming_consortium
+                        .push(oi.iter().map(|b| format!("{:02X}", b)).collect());
+                }
+            }
+        }
+
+        if let Some(protocols) = ie::find(ies, ie::ADVERTISEMENT_PROTOCOL) {
+            found = true;
+            info.advertisement_protocols = advertisement_protocols(protocols);
+        }
+
+        let indication = Elements::new(ies)
+            .filter_map(|e| e.vendor())
+            .find(|(oui, vendor_type, _)| *oui == WFA_OUI && *vendor_type == HS20_INDICATION_TYPE);
+        if let Some((_, _, body)) = indication {
+            found = true;
+            let config = body.first().copied().unwrap_or(0);
+            info.dgaf_disabled = config & 0x01 != 0;
+            info.hs20_release = Some((config >> 4) + 1);
+        }
+
+        if found {
+            Some(info)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the network advertises Hotspot 2.0 / Passpoint
+    pub fn is_passpoint(&self) -> bool {
+        self.hs20_release.is_some()
+    }
+}
+
+/// Parses the Advertisement Protocol tuples (query response info and protocol ID).
+/// Vendor specific protocol IDs carry a length and body, like a vendor specific element.
+fn advertisement_protocols(mut data: &[u8]) -> Vec<AdvertisementProtocol> {
+    let mut protocols = Vec::new();
+
+    while data.len() >= 2 {
+        let id = data[1];
+        let length = if id == ie::VENDOR_SPECIFIC {
+            match data.get(2) {
+                Some(body_length) => 3 + *body_length as usize,
+                None => break,
+            }
+        } else {
+            2
+        };
+        if data.len() < length {
+            break;
+        }
+
+        protocols.push(AdvertisementProtocol::from(id));
+        data = &data[length..];
+    }
+
+    protocols
+}
@@ -0,0 +1,119 @@
+//! Helpers for walking the 802.11 information elements of a beacon or probe response.
+
+pub const SSID: u8 = 0;
+pub const SUPPORTED_RATES: u8 = 1;
+pub const DS_PARAMETER_SET: u8 = 3;
+pub const COUNTRY: u8 = 7;
+pub const BSS_LOAD: u8 = 11;
+pub const POWER_CONSTRAINT: u8 = 32;
+pub const TPC_REPORT: u8 = 35;
+pub const HT_CAPABILITIES: u8 = 45;
+pub const RSN: u8 = 48;
+pub const EXTENDED_SUPPORTED_RATES: u8 = 50;
+pub const HT_OPERATION: u8 = 61;
+pub const MULTIPLE_BSSID: u8 = 71;
+pub const NONTRANSMITTED_BSSID_CAPABILITY: u8 = 83;
+pub const MULTIPLE_BSSID_INDEX: u8 = 85;
+pub const INTERWORKING: u8 = 107;
+pub const ADVERTISEMENT_PROTOCOL: u8 = 108;
+pub const ROAMING_CONSORTIUM: u8 = 111;
+pub const VHT_CAPABILITIES: u8 = 191;
+pub const VHT_OPERATION: u8 = 192;
+pub const REDUCED_NEIGHBOR_REPORT: u8 = 201;
+pub const VENDOR_SPECIFIC: u8 = 221;
+pub const EXTENSION: u8 = 255;
+
+pub const EXT_HE_CAPABILITIES: u8 = 35;
+pub const EXT_HE_OPERATION: u8 = 36;
+pub const EXT_NON_INHERITANCE: u8 = 56;
+pub const EXT_EHT_OPERATION: u8 = 106;
+pub const EXT_MULTI_LINK: u8 = 107;
+pub const EXT_EHT_CAPABILITIES: u8 = 108;
+
+/// A single information element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Element<'a> {
+    pub id: u8,
+    pub data: &'a [u8],
+}
+
+impl<'a> Element<'a> {
+    /// Returns the extension ID and body if this is an extension element
+    pub fn extension(&self) -> Option<(u8, &'a [u8])> {
+        if self.id == EXTENSION && !self.data.is_empty() {
+            Some((self.data[0], &self.data[1..]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the OUI, vendor type and body if this is a vendor specific element
+    pub fn vendor(&self) -> Option<([u8; 3], u8, &'a [u8])> {
+        if self.id == VENDOR_SPECIFIC && self.data.len() >= 4 {
+            Some((
+                [self.data[0], self.data[1], self.data[2]],
+                self.data[3],
+                &self.data[4..],
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterator over the information elements in a buffer.
+/// Stops at the first truncated element.
+pub struct Elements<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Elements<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Elements { data }
+    }
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = Element<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < 2 {
+            return None;
+        }
+
+        let id = self.data[0];
+        let length = self.data[1] as usize;
+        if self.data.len() < 2 + length {
+            self.data = &[];
+            return None;
+        }
+
+        let data = &self.data[2..2 + length];
+        self.data = &self.data[2 + length..];
+        Some(Element { id, data })
+    }
+}
+
+/// Returns the body of the first element with the given ID
+pub fn find(data: &[u8], id: u8) -> Option<&[u8]> {
+    Elements::new(data).find(|e| e.id == id).map(|e| e.data)
+}
+
+/// Returns the body of the first extension element with the given extension ID
+pub fn find_extension(data: &[u8], ext_id: u8) -> Option<&[u8]> {
+    Elements::new(data)
+        .filter_map(|e| e.extension())
+        .find(|(id, _)| *id == ext_id)
+        .map(|(_, body)| body)
+}
+
+/// Returns the SSID advertised in the elements. Empty if missing or hidden.
+pub fn ssid(data: &[u8]) -> String {
+    find(data, SSID)
+        .map(|ssid| {
+            String::from_utf8_lossy(ssid)
+                .trim_end_matches('\0')
+                .to_string()
+        })
+        .unwrap_or_default()
+}
@@ -18,7 +18,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! wifi_scan = "0.7.*"
+//! wifi_scan = "0.8.*"
 //! ```
 //!
 //! # Example
@@ -31,26 +31,76 @@
 //! Alternatively if you've cloned the the Git repo, you can run the above example
 //! using: `cargo run --example scan`.
 
+pub mod audit;
+pub mod congestion;
+pub mod distance;
+pub mod export;
+pub mod fingerprint;
+#[cfg(feature = "gps")]
+pub mod gps;
+pub mod hotspot;
+mod ie;
 mod misc;
+pub mod mlo;
+pub mod neighbor;
+#[cfg(feature = "oui")]
+pub mod oui;
+pub mod pcap;
+pub mod phy;
+pub mod positioning;
+pub mod query;
+pub mod radiotap;
+pub mod results;
+pub mod rogue;
+#[cfg(feature = "serde")]
+pub mod schema;
+mod security;
+pub mod signal;
 mod sys;
+pub mod tracker;
+pub mod virtual_ap;
+pub mod wps;
 
+use std::convert::Infallible;
 use std::fmt;
+use std::str::FromStr;
 
-use crate::misc::yes_or_no;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::audit::Finding;
+use crate::congestion::BssLoad;
+use crate::distance::{DistanceConfig, DistanceEstimate};
+use crate::hotspot::HotspotInfo;
+use crate::misc::{parse_mac, yes_or_no};
+use crate::mlo::MultiLinkInfo;
+use crate::neighbor::{NeighborAp, NontransmittedBss};
+use crate::phy::{PhyInfo, WifiGeneration};
+use crate::signal::SignalThresholds;
+use crate::wps::WpsInfo;
 
 type Result<T> = std::result::Result<T, Error>;
 
-/// Erros for wifi_scan
+/// Erros for wifi_scan.
+/// Variants may be added in minor versions, so matches need a wildcard arm.
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum Error {
     InterfaceError(String),
     SocketError(String),
     ScanFailed(String),
+    /// Serialized data was written with an unsupported schema version
+    UnsupportedSchema(u32),
+    /// Reading a position from a GPS receiver or gpsd failed
+    #[cfg(feature = "gps")]
+    Gps(String),
 }
 
 /// Enum of WiFi Securities wifi_scan can output.
 /// Not all implementations support all securities.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum WifiSecurity {
     Open,
     Wep,
@@ -73,7 +123,13 @@ pub enum WifiSecurity {
 }
 
 /// Wifi struct used to return information about wifi hotspots. Shows security on Linux since version 0.6.0.
-#[derive(Debug, PartialEq, Eq, Default, Clone)]
+///
+/// Fields may be added in minor versions, so create instances with `Wifi::default()`
+/// and set the fields you need. Networks are compared without their raw information
+/// elements, which change between beacons (e.g. TIM and BSS Load) for the same network.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct Wifi {
     /// MAC Address. May be empty on macOS.
     pub mac: String,
@@ -81,13 +137,36 @@ pub struct Wifi {
     pub ssid: String,
     /// Channel the hotspot is on. Returns 0 if unknown.
     pub channel: u32,
+    /// Center frequency in MHz. Returns 0 if unknown, use [`Wifi::get_frequency`]
+    /// to fall back to the frequency of the channel.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub frequency: u32,
     /// Wifi signal strength in dBm. Returns 0 if unknown.
     pub signal_level: i32,
     /// A list of all supported securities by the network
     pub security: Vec<WifiSecurity>,
+    /// Raw information elements of the beacon or probe response.
+    /// Empty if the platform does not expose them.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::schema::hex"))]
+    information_elements: Vec<u8>,
+    /// `true` if the network was not seen directly but inferred from the
+    /// Multiple BSSID or Reduced Neighbor Report element of another network
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inferred: bool,
+}
+
+/// Frequency band a network operates on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Band {
+    TwoPointFourGhz,
+    FiveGhz,
+    SixGhz,
 }
 
 /// Human readable signal strength
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SignalStrength {
     Unknown,
     Weak,
@@ -96,6 +175,20 @@ pub enum SignalStrength {
     Excellent,
 }
 
+impl PartialEq for Wifi {
+    fn eq(&self, other: &Self) -> bool {
+        self.mac == other.mac
+            && self.ssid == other.ssid
+            && self.channel == other.channel
+            && self.frequency == other.frequency
+            && self.signal_level == other.signal_level
+            && self.security == other.security
+            && self.inferred == other.inferred
+    }
+}
+
+impl Eq for Wifi {}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -108,6 +201,13 @@ impl fmt::Display for Error {
             Error::ScanFailed(detail) => {
                 write!(f, "Scan Failed: {}", detail)
             }
+            Error::UnsupportedSchema(version) => {
+                write!(f, "Unsupported schema version: {}", version)
+            }
+            #[cfg(feature = "gps")]
+            Error::Gps(detail) => {
+                write!(f, "GPS error: {}", detail)
+            }
         }
     }
 }
@@ -139,11 +239,39 @@ impl fmt::Display for WifiSecurity {
     }
 }
 
+impl FromStr for WifiSecurity {
+    type Err = Infallible;
+
+    /// Parses the display name of a security. Unrecognised names become `Other`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "Open" => WifiSecurity::Open,
+            "Tunneled Direct Link Setup" => WifiSecurity::TunneledDirectLinkSetup,
+            "Unknown" => WifiSecurity::Unknown,
+            "WEP" => WifiSecurity::Wep,
+            "WPA2-Enterprise (EAP)" => WifiSecurity::Wpa2EnterpriseEap,
+            "WPA2-Enterprise (EAP-FT)" => WifiSecurity::Wpa2EnterpriseEapFt,
+            "WPA2-Personal (PSK)" => WifiSecurity::Wpa2PersonalPsk,
+            "WPA2-Personal (PSK-FT)" => WifiSecurity::Wpa2PersonalPskFt,
+            "WPA3-Enterprise (EAP-256)" => WifiSecurity::Wpa3EnterpriseEap256,
+            "WPA3-Enterprise (Suite B EAP-256)" => WifiSecurity::Wpa3EnterpriseSuiteBEap256,
+            "WPA2-Personal (PSK-256)" => WifiSecurity::Wpa2PersonalPsk256,
+            "WPA3-Personal (SAE)" => WifiSecurity::Wpa3PersonalSae,
+            "WPA3-Personal (SAE-FT)" => WifiSecurity::Wpa3PersonalSaeFt,
+            "WPA-Enterprise" => WifiSecurity::WpaEnterpriseEap,
+            "WPA-Personal" => WifiSecurity::WpaPersonalPsk,
+            "WPA2-Enterprise (EPA-256)" => WifiSecurity::Wpa2EnterpriseEap256,
+            "WPA3-Enterprise (EAP)" => WifiSecurity::Wpa3EnterpriseEap,
+            other => WifiSecurity::Other(other.to_string()),
+        })
+    }
+}
+
 impl fmt::Display for Wifi {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "BSSID: {} | SSID: {} | Channel: {} | RSSI: {} dBm | Security: {} | Hidden: {}",
+            "BSSID: {} | SSID: {} | Channel: {} | RSSI: {} dBm | Security: {} | Hidden: {} | Standard: {}",
             self.mac,
             self.ssid,
             self.channel,
@@ -153,12 +281,70 @@ impl fmt::Display for Wifi {
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
                 .join(", "),
-            yes_or_no(self.is_hidden())
+            yes_or_no(self.is_hidden()),
+            self.generation()
+                .map_or("Unknown".to_string(), |g| g.to_string())
         )
     }
 }
 
+impl fmt::Display for SignalStrength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignalStrength::Unknown => write!(f, "Unknown"),
+            SignalStrength::Weak => write!(f, "Weak"),
+            SignalStrength::Fair => write!(f, "Fair"),
+            SignalStrength::Good => write!(f, "Good"),
+            SignalStrength::Excellent => write!(f, "Excellent"),
+        }
+    }
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Band::TwoPointFourGhz => write!(f, "2.4 GHz"),
+            Band::FiveGhz => write!(f, "5 GHz"),
+            Band::SixGhz => write!(f, "6 GHz"),
+        }
+    }
+}
+
+impl Band {
+    /// Returns the band of a global operating class (IEEE 802.11 Annex E)
+    pub fn from_operating_class(operating_class: u8) -> Option<Band> {
+        match operating_class {
+            81..=84 => Some(Band::TwoPointFourGhz),
+            115..=130 => Some(Band::FiveGhz),
+            131..=137 => Some(Band::SixGhz),
+            _ => None,
+        }
+    }
+
+    /// Returns the center frequency in MHz of a 20 MHz channel in this band
+    pub fn channel_frequency(&self, channel: u32) -> u32 {
+        match (self, channel) {
+            (Band::TwoPointFourGhz, 14) => 2484,
+            (Band::TwoPointFourGhz, _) => 2407 + channel * 5,
+            (Band::FiveGhz, _) => 5000 + channel * 5,
+            (Band::SixGhz, 2) => 5935,
+            (Band::SixGhz, _) => 5950 + channel * 5,
+        }
+    }
+}
+
 impl Wifi {
+    /// Returns the raw information elements of the beacon or probe response.
+    /// Empty if the platform does not expose them.
+    pub fn information_elements(&self) -> &[u8] {
+        &self.information_elements
+    }
+
+    /// Sets the raw information elements, e.g. of a frame captured elsewhere
+    pub fn set_information_elements(&mut self, elements: Vec<u8>) {
+        self.information_elements = elements;
+    }
+
     /// Returns `true` if the network is open
     pub fn is_open(&self) -> bool {
         self.security.len() == 1 && self.security[0] == WifiSecurity::Open
@@ -224,15 +410,30 @@ impl Wifi {
         })
     }
 
-    /// Returns signal strength as a categorial value
+    /// Returns signal strength as a categorial value using the default thresholds
     pub fn readable_signal(&self) -> SignalStrength {
-        match self.signal_level {
-            0 => SignalStrength::Unknown,
-            -50..=0 => SignalStrength::Excellent,
-            -70..=-61 => SignalStrength::Good,
-            -80..=-71 => SignalStrength::Fair,
-            _ => SignalStrength::Weak,
-        }
+        self.readable_signal_with(&SignalThresholds::default())
+    }
+
+    /// Returns signal strength as a categorial value using custom thresholds
+    pub fn readable_signal_with(&self, thresholds: &SignalThresholds) -> SignalStrength {
+        thresholds.classify(self.signal_level)
+    }
+
+    /// Returns signal quality in percent. Returns `None` if the signal level is unknown.
+    pub fn signal_quality(&self) -> Option<u8> {
+        signal::quality(self.signal_level)
+    }
+
+    /// Returns signal strength as 0 to 4 bars using the default thresholds
+    pub fn signal_bars(&self) -> u8 {
+        signal::bars(self.readable_signal())
+    }
+
+    /// Returns `true` if the BSSID is a locally administered address,
+    /// as used by mobile hotspots, randomized and virtual access points
+    pub fn is_locally_administered(&self) -> bool {
+        parse_mac(&self.mac).is_some_and(|mac| mac[0] & 0x02 != 0)
     }
 
     /// Returns `true` if the network is hidden
@@ -240,8 +441,12 @@ impl Wifi {
         self.ssid.is_empty()
     }
 
-    /// Returns WiFi frequency in MHz
+    /// Returns WiFi frequency in MHz, derived from the channel if the frequency is unknown
     pub fn get_frequency(&self) -> u32 {
+        if self.frequency != 0 {
+            return self.frequency;
+        }
+
         match self.channel {
             1..=13 => 2407 + self.channel * 5,          // 2.4 GHz
             14 => 2484,                                 // 2.4 GHz (Japan)
@@ -250,6 +455,101 @@ impl Wifi {
             _ => 0,                                     // Invalid
         }
     }
+
+    /// Returns the security findings of the network, most severe first
+    pub fn audit(&self) -> Vec<Finding> {
+        audit::findings(self)
+    }
+
+    /// Returns the station count and channel utilisation advertised in the BSS Load element
+    pub fn bss_load(&self) -> Option<BssLoad> {
+        BssLoad::from_elements(&self.information_elements)
+    }
+
+    /// Estimates the distance to the network from its signal level.
+    /// Returns `None` if the signal level or frequency is unknown.
+    pub fn distance(&self, config: &DistanceConfig) -> Option<DistanceEstimate> {
+        distance::estimate(self, config)
+    }
+
+    /// Returns the frequency band of the network, if known
+    pub fn band(&self) -> Option<Band> {
+        if self.frequency == 0 && phy::he_6ghz_operation(&self.information_elements).is_some() {
+            return Some(Band::SixGhz);
+        }
+
+        match self.get_frequency() {
+            2400..=2500 => Some(Band::TwoPointFourGhz),
+            5000..=5900 => Some(Band::FiveGhz),
+            5925..=7125 => Some(Band::SixGhz),
+            _ => None,
+        }
+    }
+
+    /// Returns PHY capabilities (generation, spatial streams, channel width and max rate)
+    /// derived from the information elements. Returns `None` if they are not available.
+    pub fn phy(&self) -> Option<PhyInfo> {
+        PhyInfo::from_elements(&self.information_elements, self.channel)
+    }
+
+    /// Returns the Wi-Fi generation of the network, if it can be determined
+    pub fn generation(&self) -> Option<WifiGeneration> {
+        self.phy().map(|phy| phy.generation)
+    }
+
+    /// Returns the Wi-Fi Protected Setup information if the network advertises WPS
+    pub fn wps(&self) -> Option<WpsInfo> {
+        WpsInfo::from_elements(&self.information_elements)
+    }
+
+    /// Returns `true` if the network advertises Wi-Fi Protected Setup
+    pub fn is_wps_enabled(&self) -> bool {
+        self.wps().is_some()
+    }
+
+    /// Returns Hotspot 2.0 / Passpoint and Interworking information, if advertised
+    pub fn hotspot(&self) -> Option<HotspotInfo> {
+        HotspotInfo::from_elements(&self.information_elements)
+    }
+
+    /// Returns `true` if the network advertises Hotspot 2.0 / Passpoint
+    pub fn is_passpoint(&self) -> bool {
+        self.hotspot().is_some_and(|h| h.is_passpoint())
+    }
+
+    /// Returns the vendor of the access point based on the OUI of its BSSID.
+    /// For locally administered BSSIDs the OUIs of vendor specific elements are used instead.
+    #[cfg(feature = "oui")]
+    pub fn vendor(&self) -> Option<String> {
+        oui::vendor(self)
+    }
+
+    /// Returns the access points listed in the Reduced Neighbor Report element
+    pub fn reduced_neighbor_report(&self) -> Vec<NeighborAp> {
+        neighbor::parse_reduced_neighbor_report(&self.information_elements)
+    }
+
+    /// Returns the non-transmitted BSSes listed in the Multiple BSSID element
+    pub fn nontransmitted_bsses(&self) -> Vec<NontransmittedBss> {
+        neighbor::parse_multiple_bssid(&self.mac, &self.information_elements)
+    }
+
+    /// Returns the networks advertised by this network through its Multiple BSSID and
+    /// Reduced Neighbor Report elements, marked as inferred.
+    /// Signal level is copied from this network for non-transmitted BSSes and 0 for neighbors.
+    pub fn inferred_bsses(&self) -> Vec<Wifi> {
+        neighbor::inferred_bsses(self)
+    }
+
+    /// Returns Wi-Fi 7 multi-link information if the network is part of an AP MLD
+    pub fn multi_link(&self) -> Option<MultiLinkInfo> {
+        MultiLinkInfo::from_wifi(self)
+    }
+
+    /// Returns the MLD MAC address if the network is part of an AP MLD
+    pub fn mld_mac(&self) -> Option<String> {
+        self.multi_link().map(|info| info.mld_mac)
+    }
 }
 
 impl std::error::Error for Error {}
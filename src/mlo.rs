@@ -0,0 +1,201 @@
+//! Parsing of Wi-Fi 7 multi-link (MLO) information.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ie,
+    misc::{format_mac, same_mac},
+    Band, Wifi,
+};
+
+/// Multi-link information of an access point affiliated with an AP MLD
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiLinkInfo {
+    /// MAC address of the AP MLD
+    pub mld_mac: String,
+    /// All links of the AP MLD, including the reporting link
+    pub links: Vec<AffiliatedLink>,
+}
+
+/// A single link of an AP MLD
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AffiliatedLink {
+    /// Link ID within the MLD, if advertised
+    pub link_id: Option<u8>,
+    /// Band of the link, if known
+    pub band: Option<Band>,
+    /// Primary channel of the link. Returns 0 if unknown.
+    pub channel: u32,
+    /// BSSID of the link. Empty if unknown.
+    pub bssid: String,
+}
+
+/// A logical multi-link access point made up of several per-band BSSes
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiLinkAp {
+    /// MAC address of the AP MLD
+    pub mld_mac: String,
+    /// All advertised links of the AP MLD
+    pub links: Vec<AffiliatedLink>,
+    /// Scan results belonging to this AP MLD
+    pub bsses: Vec<Wifi>,
+}
+
+impl MultiLinkInfo {
+    /// Parses the Basic Multi-Link and Reduced Neighbor Report elements of a network
+    pub(crate) fn from_wifi(wifi: &Wifi) -> Option<MultiLinkInfo> {
+        let element = ie::find_extension(&wifi.information_elements, ie::EXT_MULTI_LINK)?;
+        if element.len() < 9 {
+            return None;
+        }
+
+        let control = u16::from_le_bytes([element[0], element[1]]);
+        // only the Basic variant carries the MLD MAC address
+        if control & 0x07 != 0 {
+            return None;
+        }
+
+        let common_info_length = element[2] as usize;
+        let common_info = element.get(2..2 + common_info_length)?;
+        if common_info.len() < 7 {
+            return None;
+        }
+        let mld_mac = format_mac(&common_info[1..7]);
+        let own_link_id = if control & 0x10 != 0 {
+            common_info.get(7).map(|id| id & 0x0f)
+        } else {
+            None
+        };
+
+        let mut links = vec![AffiliatedLink {
+            link_id: own_link_id,
+            band: wifi.band(),
+            channel: wifi.channel,
+            bssid: wifi.mac.clone(),
+        }];
+
+        for neighbor in wifi.reduced_neighbor_report() {
+            if let Some(mld) = neighbor.mld {
+                if mld.mld_id == 0 && Some(mld.link_id) != own_link_id {
+                    links.push(AffiliatedLink {
+                        link_id: Some(mld.link_id),
+                        band: neighbor.band,
+                        channel: neighbor.channel,
+                        bssid: neighbor.bssid,
+                    });
+                }
+            }
+        }
+
+        for (link_id, sta_mac) in per_sta_profiles(&element[2 + common_info_length..]) {
+            match links.iter_mut().find(|l| l.link_id == Some(link_id)) {
+                Some(link) => {
+                    if link.bssid.is_empty() {
+                        if let Some(mac) = sta_mac {
+                            link.bssid = mac;
+                        }
+                    }
+                }
+                None => links.push(AffiliatedLink {
+                    link_id: Some(link_id),
+                    band: None,
+                    channel: 0,
+                    bssid: sta_mac.unwrap_or_default(),
+                }),
+            }
+        }
+
+        Some(MultiLinkInfo { mld_mac, links })
+    }
+}
+
+impl MultiLinkAp {
+    /// Returns the bands covered by the links of this AP MLD
+    pub fn bands(&self) -> Vec<Band> {
+        let mut bands: Vec<Band> = self.links.iter().filter_map(|l| l.band).collect();
+        bands.sort();
+        bands.dedup();
+        bands
+    }
+}
+
+/// Returns the link ID and STA MAC address of each Per-STA Profile subelement
+fn per_sta_profiles(mut data: &[u8]) -> Vec<(u8, Option<String>)> {
+    let mut profiles = Vec::new();
+
+    while data.len() >= 2 {
+        let id = data[0];
+        let length = data[1] as usize;
+        let Some(body) = data.get(2..2 + length) else {
+            break;
+        };
+
+        if id == 0 && body.len() >= 3 {
+            let control = u16::from_le_bytes([body[0], body[1]]);
+            let sta_mac = if control & 0x20 != 0 {
+                body.get(3..9).map(format_mac)
+            } else {
+                None
+            };
+            profiles.push(((control & 0x0f) as u8, sta_mac));
+        }
+
+        data = &data[2 + length..];
+    }
+
+    profiles
+}
+
+/// Links are the same if they share a BSSID or an advertised link ID
+fn same_link(a: &AffiliatedLink, b: &AffiliatedLink) -> bool {
+    same_mac(&a.bssid, &b.bssid) || (a.link_id.is_some() && a.link_id == b.link_id)
+}
+
+/// Groups scan results that belong to the same AP MLD into one logical access point.
+/// Networks that are not affiliated with an AP MLD are left out.
+pub fn group_multi_link(wifis: &[Wifi]) -> Vec<MultiLinkAp> {
+    let mut groups: Vec<(MultiLinkInfo, Vec<Wifi>)> = Vec::new();
+
+    for wifi in wifis {
+        if let Some(info) = wifi.multi_link() {
+            match groups
+                .iter_mut()
+                .find(|(g, _)| same_mac(&g.mld_mac, &info.mld_mac))
+            {
+                Some((group, bsses)) => {
+                    for link in info.links {
+                        if !group.links.iter().any(|l| same_link(l, &link)) {
+                            group.links.push(link);
+                        }
+                    }
+                    bsses.push(wifi.clone());
+                }
+                None => groups.push((info, vec![wifi.clone()])),
+            }
+        }
+    }
+
+    // pick up links that did not advertise the Multi-Link element themselves
+    for wifi in wifis {
+        for (info, bsses) in groups.iter_mut() {
+            let listed = info.links.iter().any(|l| same_mac(&l.bssid, &wifi.mac));
+            let grouped = bsses.iter().any(|b| same_mac(&b.mac, &wifi.mac));
+            if listed && !grouped {
+                bsses.push(wifi.clone());
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(info, bsses)| MultiLinkAp {
+            mld_mac: info.mld_mac,
+            links: info.links,
+            bsses,
+        })
+        .collect()
+}
@@ -0,0 +1,297 @@
+//! Parsing of the Reduced Neighbor Report and Multiple BSSID elements.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ie::{self, Elements},
+    misc::{format_mac, parse_mac, same_mac},
+    security, Band, Wifi, WifiSecurity,
+};
+
+/// An access point listed in the Reduced Neighbor Report (RNR) element
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NeighborAp {
+    /// Global operating class of the neighbor
+    pub operating_class: u8,
+    /// Primary channel of the neighbor
+    pub channel: u32,
+    /// Band derived from the operating class
+    pub band: Option<Band>,
+    /// BSSID of the neighbor. Empty if not reported.
+    pub bssid: String,
+    /// Short SSID (CRC-32 of the SSID), if reported
+    pub short_ssid: Option<u32>,
+    /// `true` if the neighbor uses the same SSID as the reporting AP
+    pub same_ssid: bool,
+    /// `true` if the neighbor is part of a Multiple BSSID set
+    pub multiple_bssid: bool,
+    /// `true` if the neighbor is the transmitted BSSID of its Multiple BSSID set
+    pub transmitted_bssid: bool,
+    /// `true` if the neighbor is co-located with the reporting AP
+    pub co_located: bool,
+    /// MLD parameters if the neighbor is affiliated with an AP MLD
+    pub mld: Option<MldParameters>,
+}
+
+/// MLD parameters of a neighbor affiliated with an AP MLD
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MldParameters {
+    /// AP MLD ID. 0 means the neighbor belongs to the same MLD as the reporting AP.
+    pub mld_id: u8,
+    /// Link ID of the neighbor within its MLD
+    pub link_id: u8,
+}
+
+/// Parses all Reduced Neighbor Report elements in the information elements
+pub(crate) fn parse_reduced_neighbor_report(ies: &[u8]) -> Vec<NeighborAp> {
+    let mut neighbors = Vec::new();
+
+    for element in Elements::new(ies).filter(|e| e.id == ie::REDUCED_NEIGHBOR_REPORT) {
+        let mut data = element.data;
+        while data.len() >= 4 {
+            let header = u16::from_le_bytes([data[0], data[1]]);
+            let count = ((header >> 4) & 0x0f) as usize + 1;
+            let info_length = (header >> 8) as usize;
+            let operating_class = data[2];
+            let channel = data[3] as u32;
+            data = &data[4..];
+
+            if info_length == 0 || data.len() < count * info_length {
+                break;
+            }
+
+            for info in data[..count * info_length].chunks(info_length) {
+                neighbors.push(parse_tbtt_info(operating_class, channel, info));
+            }
+            data = &data[count * info_length..];
+        }
+    }
+
+    neighbors
+}
+
+/// Parses a single TBTT Information field. The layout depends on its length.
+fn parse_tbtt_info(operating_class: u8, channel: u32, info: &[u8]) -> NeighborAp {
+    let (bssid, short_ssid, bss_parameters) = match info.len() {
+        2 => (None, None, Some(info[1])),
+        5 => (None, Some(&info[1..5]), None),
+        6 => (None, Some(&info[1..5]), Some(info[5])),
+        7 => (Some(&info[1..7]), None, None),
+        8 | 9 => (Some(&info[1..7]), None, Some(info[7])),
+        11 => (Some(&info[1..7]), Some(&info[7..11]), None),
+        length if length >= 12 => (Some(&info[1..7]), Some(&info[7..11]), Some(info[11])),
+        _ => (None, None, None),
+    };
+    let bss_parameters = bss_parameters.unwrap_or(0);
+
+    let mld = if info.len() >= 16 {
+        Some(MldParameters {
+            mld_id: info[13],
+            link_id: info[14] & 0x0f,
+        })
+    } else {
+        None
+    };
+
+    NeighborAp {
+        operating_class,
+        channel,
+        band: Band::from_operating_class(operating_class),
+        bssid: bssid.map_or(String::new(), format_mac),
+        short_ssid: short_ssid.map(|s| u32::from_le_bytes([s[0], s[1], s[2], s[3]])),
+        same_ssid: bss_parameters & 0x02 != 0,
+        multiple_bssid: bss_parameters & 0x04 != 0,
+        transmitted_bssid: bss_parameters & 0x08 != 0,
+        co_located: bss_parameters & 0x40 != 0,
+        mld,
+    }
+}
+
+/// A non-transmitted BSS advertised in the Multiple BSSID element
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NontransmittedBss {
+    /// BSSID derived from the transmitted BSSID and the BSSID index
+    pub bssid: String,
+    /// Index of the BSS within its Multiple BSSID set
+    pub bssid_index: u8,
+    /// SSID of the BSS. Empty if hidden.
+    pub ssid: String,
+    /// `true` if the capability information has the privacy bit set
+    pub privacy: bool,
+    /// Information elements of the BSS, including those inherited from the transmitted BSS
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::hex"))]
+    pub information_elements: Vec<u8>,
+}
+
+/// Parses all non-transmitted BSS profiles in the Multiple BSSID elements
+pub(crate) fn parse_multiple_bssid(bssid: &str, ies: &[u8]) -> Vec<NontransmittedBss> {
+    let Some(transmitted) = parse_mac(bssid) else {
+        return Vec::new();
+    };
+    let mut bsses = Vec::new();
+
+    for element in Elements::new(ies).filter(|e| e.id == ie::MULTIPLE_BSSID) {
+        let Some((&max_indicator, subelements)) = element.data.split_first() else {
+            continue;
+        };
+
+        for profile in Elements::new(subelements).filter(|e| e.id == 0) {
+            let Some(index) = ie::find(profile.data, ie::MULTIPLE_BSSID_INDEX)
+                .and_then(|index| index.first().copied())
+            else {
+                continue;
+            };
+            let privacy = ie::find(profile.data, ie::NONTRANSMITTED_BSSID_CAPABILITY)
+                .filter(|cap| cap.len() >= 2)
+                .is_some_and(|cap| cap[0] & 0x10 != 0);
+            let information_elements = inherit_elements(profile.data, ies);
+
+            bsses.push(NontransmittedBss {
+                bssid: format_mac(&nontransmitted_bssid(transmitted, max_indicator, index)),
+                bssid_index: index,
+                ssid: ie::ssid(&information_elements),
+                privacy,
+                information_elements,
+            });
+        }
+    }
+
+    bsses
+}
+
+/// Calculates the BSSID of the non-transmitted BSS with the given index (IEEE 802.11 9.4.2.45)
+fn nontransmitted_bssid(transmitted: [u8; 6], max_indicator: u8, index: u8) -> [u8; 6] {
+    let mut value = [0u8; 8];
+    value[2..].copy_from_slice(&transmitted);
+    let reference = u64::from_be_bytes(value);
+
+    let mask = (1u64 << max_indicator.min(8)) - 1;
+    let low = ((reference & mask) + index as u64) & mask;
+    let bssid = (reference & !mask) | low;
+
+    let mut result = [0u8; 6];
+    result.copy_from_slice(&bssid.to_be_bytes()[2..]);
+    result
+}
+
+/// Builds the elements of a non-transmitted BSS from its profile and the elements
+/// of the transmitted BSS, honouring the Non-Inheritance element.
+fn inherit_elements(profile: &[u8], transmitted: &[u8]) -> Vec<u8> {
+    let (excluded_ids, excluded_ext_ids) =
+        match ie::find_extension(profile, ie::EXT_NON_INHERITANCE) {
+            Some(body) => {
+                let ids_length = *body.first().unwrap_or(&0) as usize;
+                let ids = body.get(1..1 + ids_length).unwrap_or_default();
+                let ext_length = *body.get(1 + ids_length).unwrap_or(&0) as usize;
+                let ext_ids = body
+                    .get(2 + ids_length..2 + ids_length + ext_length)
+                    .unwrap_or_default();
+                (ids, ext_ids)
+            }
+            None => (&[][..], &[][..]),
+        };
+
+    let mut elements = Vec::new();
+    let profile_elements: Vec<_> = Elements::new(profile)
+        .filter(|e| {
+            e.id != ie::NONTRANSMITTED_BSSID_CAPABILITY
+                && e.id != ie::MULTIPLE_BSSID_INDEX
+                && e.extension().map(|(id, _)| id) != Some(ie::EXT_NON_INHERITANCE)
+        })
+        .collect();
+
+    for element in &profile_elements {
+        elements.push(element.id);
+        elements.push(element.data.len() as u8);
+        elements.extend_from_slice(element.data);
+    }
+
+    for element in Elements::new(transmitted) {
+        let overridden = profile_elements.iter().any(|p| {
+            p.id == element.id
+                && p.extension().map(|(id, _)| id) == element.extension().map(|(id, _)| id)
+        });
+        let excluded = match element.extension() {
+            Some((ext_id, _)) => excluded_ext_ids.contains(&ext_id),
+            None => excluded_ids.contains(&element.id),
+        };
+
+        if !overridden && !excluded && element.id != ie::SSID && element.id != ie::MULTIPLE_BSSID {
+            elements.push(element.id);
+            elements.push(element.data.len() as u8);
+            elements.extend_from_slice(element.data);
+        }
+    }
+
+    elements
+}
+
+/// Returns the networks that are advertised by `wifi` through its Multiple BSSID and
+/// Reduced Neighbor Report elements. The returned networks are marked as inferred.
+pub(crate) fn inferred_bsses(wifi: &Wifi) -> Vec<Wifi> {
+    let mut inferred: Vec<Wifi> = Vec::new();
+
+    for bss in parse_multiple_bssid(&wifi.mac, &wifi.information_elements) {
+        inferred.push(Wifi {
+            mac: bss.bssid,
+            ssid: bss.ssid,
+            channel: wifi.channel,
+            frequency: wifi.frequency,
+            // non-transmitted BSSes share the radio of the transmitted BSS
+            signal_level: wifi.signal_level,
+            security: security::from_elements(&bss.information_elements, bss.privacy),
+            information_elements: bss.information_elements,
+            inferred: true,
+        });
+    }
+
+    for neighbor in parse_reduced_neighbor_report(&wifi.information_elements) {
+        if neighbor.bssid.is_empty()
+            || same_mac(&wifi.mac, &neighbor.bssid)
+            || inferred.iter().any(|w| same_mac(&w.mac, &neighbor.bssid))
+        {
+            continue;
+        }
+
+        inferred.push(Wifi {
+            mac: neighbor.bssid,
+            ssid: if neighbor.same_ssid {
+                wifi.ssid.clone()
+            } else {
+                String::new()
+            },
+            channel: neighbor.channel,
+            // channel numbers overlap between bands, so the band of the operating class is kept
+            frequency: neighbor
+                .band
+                .map_or(0, |band| band.channel_frequency(neighbor.channel)),
+            signal_level: 0,
+            security: vec![WifiSecurity::Unknown],
+            information_elements: Vec::new(),
+            inferred: true,
+        });
+    }
+
+    inferred
+}
+
+/// Returns the scan results together with all networks inferred from their Multiple BSSID
+/// and Reduced Neighbor Report elements. Inferred networks that were also seen directly
+/// are not duplicated.
+pub fn expand_inferred(wifis: &[Wifi]) -> Vec<Wifi> {
+    let mut expanded = wifis.to_vec();
+
+    for wifi in wifis {
+        for bss in inferred_bsses(wifi) {
+            if !expanded.iter().any(|w| same_mac(&w.mac, &bss.mac)) {
+                expanded.push(bss);
+            }
+        }
+    }
+
+    expanded
+}
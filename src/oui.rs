@@ -0,0 +1,424 @@
+//! Vendor identification through the IEEE OUI registry.
+//!
+//! A compact table of common access point and chipset vendors is embedded in the crate.
+//! For full coverage load the IEEE `oui.csv` or Wireshark `manuf` file at runtime
+//! and install it with [`set_database`].
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    sync::{OnceLock, RwLock},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ie::Elements,
+    misc::{parse_mac, split_csv_line},
+    Wifi,
+};
+
+/// OUIs used by standard bodies for their own vendor elements
+const GENERIC_OUIS: [[u8; 3]; 3] = [
+    [0x00, 0x50, 0xf2], // Microsoft (WPA, WMM, WPS)
+    [0x50, 0x6f, 0x9a], // Wi-Fi Alliance
+    [0x00, 0x0f, 0xac], // IEEE 802.11
+];
+
+/// OUIs of chipset vendors, which are only used if no other vendor element matches
+const CHIPSET_OUIS: [[u8; 3]; 6] = [
+    [0x00, 0x10, 0x18], // Broadcom
+    [0x00, 0x03, 0x7f], // Qualcomm Atheros
+    [0x00, 0x0c, 0x43], // Ralink
+    [0x00, 0x0c, 0xe7], // MediaTek
+    [0x00, 0xe0, 0x4c], // Realtek
+    [0x00, 0x50, 0x43], // Marvell
+];
+
+static EMBEDDED: OnceLock<OuiDatabase> = OnceLock::new();
+static DATABASE: RwLock<Option<OuiDatabase>> = RwLock::new(None);
+
+/// A registry mapping 24-bit OUIs to organisation names
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OuiDatabase {
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::mac_map"))]
+    entries: HashMap<[u8; 3], String>,
+}
+
+impl OuiDatabase {
+    /// Returns the database embedded in the crate
+    pub fn embedded() -> OuiDatabase {
+        OuiDatabase {
+            entries: EMBEDDED_OUIS
+                .iter()
+                .map(|(oui, name)| (*oui, name.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Parses the IEEE `oui.csv` format
+    /// (`Registry,Assignment,Organization Name,Organization Address`)
+    pub fn from_csv<R: BufRead>(reader: R) -> io::Result<OuiDatabase> {
+        let mut entries = HashMap::new();
+
+        for line in reader.lines() {
+            let fields = split_csv_line(&line?);
+            if fields.len() < 3 || fields[0] != "MA-L" {
+                continue;
+            }
+            if let Some(oui) = parse_oui(&fields[1]) {
+                entries.insert(oui, fields[2].trim().to_string());
+            }
+        }
+
+        Ok(OuiDatabase { entries })
+    }
+
+    /// Parses the Wireshark `manuf` format (`00:00:0C<TAB>Cisco<TAB>Cisco Systems, Inc`).
+    /// Entries with a prefix length other than 24 bits are skipped.
+    pub fn from_manuf<R: BufRead>(reader: R) -> io::Result<OuiDatabase> {
+        let mut entries = HashMap::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let mut fields = line.split('\t').filter(|f| !f.is_empty());
+            let (Some(prefix), Some(short_name)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            if prefix.contains('/') && !prefix.ends_with("/24") {
+                continue;
+            }
+
+            if let Some(oui) = parse_oui(prefix.trim_end_matches("/24")) {
+                let name = fields.next().unwrap_or(short_name);
+                entries.insert(oui, name.trim().to_string());
+            }
+        }
+
+        Ok(OuiDatabase { entries })
+    }
+
+    /// Loads an `oui.csv` or `manuf` file, detecting the format from its first line
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<OuiDatabase> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let is_csv = {
+            let buffer = reader.fill_buf()?;
+            buffer.starts_with(b"Registry,")
+        };
+
+        if is_csv {
+            OuiDatabase::from_csv(reader)
+        } else {
+            OuiDatabase::from_manuf(reader)
+        }
+    }
+
+    /// Returns the organisation registered for the OUI of a MAC address
+    pub fn lookup(&self, mac: &str) -> Option<&str> {
+        let mac = parse_mac(mac)?;
+        self.lookup_oui([mac[0], mac[1], mac[2]])
+    }
+
+    /// Returns the organisation registered for an OUI
+    pub fn lookup_oui(&self, oui: [u8; 3]) -> Option<&str> {
+        self.entries.get(&oui).map(|name| name.as_str())
+    }
+
+    /// Returns the number of OUIs in the database
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the database contains no OUIs
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Replaces the database used by [`Wifi::vendor`]. Defaults to the embedded database.
+pub fn set_database(database: OuiDatabase) {
+    if let Ok(mut current) = DATABASE.write() {
+        *current = Some(database);
+    }
+}
+
+/// Returns the vendor of a network.
+/// Uses the OUI of the BSSID, or the OUIs of vendor specific elements
+/// if the BSSID is locally administered.
+pub(crate) fn vendor(wifi: &Wifi) -> Option<String> {
+    let current = DATABASE.read().ok()?;
+    let database = match current.as_ref() {
+        Some(database) => database,
+        None => EMBEDDED.get_or_init(OuiDatabase::embedded),
+    };
+
+    let mac = parse_mac(&wifi.mac)?;
+    if mac[0] & 0x02 == 0 {
+        return database
+            .lookup_oui([mac[0], mac[1], mac[2]])
+            .map(String::from);
+    }
+
+    let ouis: Vec<[u8; 3]> = Elements::new(&wifi.information_elements)
+        .filter_map(|e| e.vendor())
+        .map(|(oui, _, _)| oui)
+        .filter(|oui| !GENERIC_OUIS.contains(oui))
+        .collect();
+
+    ouis.iter()
+        .filter(|oui| !CHIPSET_OUIS.contains(oui))
+        .chain(ouis.iter().filter(|oui| CHIPSET_OUIS.contains(oui)))
+        .find_map(|oui| database.lookup_oui(*oui))
+        .map(String::from)
+}
+
+/// Parses an OUI written as `001BC5`, `00-1B-C5` or `00:1B:C5`
+fn parse_oui(text: &str) -> Option<[u8; 3]> {
+    let hex: String = text.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if hex.len() != 6 || text.len() > 8 {
+        return None;
+    }
+
+    let value = u32::from_str_radix(&hex, 16).ok()?;
+    Some([(value >> 16) as u8, (value >> 8) as u8, value as u8])
+}
+
+/// Compact table of common access point and chipset vendors
+const EMBEDDED_OUIS: &[([u8; 3], &str)] = &[
+    ([0x00, 0x00, 0x0c], "Cisco Systems"),
+    ([0x00, 0x40, 0x96], "Cisco Systems"),
+    ([0x00, 0x18, 0x0a], "Cisco Meraki"),
+    ([0x0c, 0x8d, 0xdb], "Cisco Meraki"),
+    ([0x88, 0x15, 0x44], "Cisco Meraki"),
+    ([0xac, 0x17, 0xc8], "Cisco Meraki"),
+    ([0xe0, 0x55, 0x3d], "Cisco Meraki"),
+    ([0x00, 0x15, 0x6d], "Ubiquiti"),
+    ([0x00, 0x27, 0x22], "Ubiquiti"),
+    ([0x04, 0x18, 0xd6], "Ubiquiti"),
+    ([0x18, 0xe8, 0x29], "Ubiquiti"),
+    ([0x24, 0x5a, 0x4c], "Ubiquiti"),
+    ([0x24, 0xa4, 0x3c], "Ubiquiti"),
+    ([0x44, 0xd9, 0xe7], "Ubiquiti"),
+    ([0x60, 0x22, 0x32], "Ubiquiti"),
+    ([0x68, 0x72, 0x51], "Ubiquiti"),
+    ([0x70, 0xa7, 0x41], "Ubiquiti"),
+    ([0x74, 0x83, 0xc2], "Ubiquiti"),
+    ([0x74, 0xac, 0xb9], "Ubiquiti"),
+    ([0x78, 0x45, 0x58], "Ubiquiti"),
+    ([0x78, 0x8a, 0x20], "Ubiquiti"),
+    ([0x80, 0x2a, 0xa8], "Ubiquiti"),
+    ([0xac, 0x8b, 0xa9], "Ubiquiti"),
+    ([0xb4, 0xfb, 0xe4], "Ubiquiti"),
+    ([0xd0, 0x21, 0xf9], "Ubiquiti"),
+    ([0xdc, 0x9f, 0xdb], "Ubiquiti"),
+    ([0xe0, 0x63, 0xda], "Ubiquiti"),
+    ([0xf0, 0x9f, 0xc2], "Ubiquiti"),
+    ([0xfc, 0xec, 0xda], "Ubiquiti"),
+    ([0x00, 0x0b, 0x86], "Aruba"),
+    ([0x00, 0x1a, 0x1e], "Aruba"),
+    ([0x00, 0x24, 0x6c], "Aruba"),
+    ([0x04, 0xbd, 0x88], "Aruba"),
+    ([0x18, 0x64, 0x72], "Aruba"),
+    ([0x20, 0x4c, 0x03], "Aruba"),
+    ([0x20, 0xa6, 0xcd], "Aruba"),
+    ([0x24, 0xde, 0xc6], "Aruba"),
+    ([0x34, 0xfc, 0xb9], "Aruba"),
+    ([0x40, 0xe3, 0xd6], "Aruba"),
+    ([0x6c, 0xf3, 0x7f], "Aruba"),
+    ([0x70, 0x3a, 0x0e], "Aruba"),
+    ([0x84, 0xd4, 0x7e], "Aruba"),
+    ([0x88, 0x3a, 0x30], "Aruba"),
+    ([0x94, 0x64, 0x24], "Aruba"),
+    ([0x94, 0xb4, 0x0f], "Aruba"),
+    ([0x9c, 0x1c, 0x12], "Aruba"),
+    ([0xac, 0xa3, 0x1e], "Aruba"),
+    ([0xb4, 0x5d, 0x50], "Aruba"),
+    ([0xd8, 0xc7, 0xc8], "Aruba"),
+    ([0x00, 0x13, 0x92], "Ruckus Wireless"),
+    ([0x2c, 0x5d, 0x93], "Ruckus Wireless"),
+    ([0x34, 0x8f, 0x27], "Ruckus Wireless"),
+    ([0x54, 0x3d, 0x37], "Ruckus Wireless"),
+    ([0x58, 0xb6, 0x33], "Ruckus Wireless"),
+    ([0x74, 0x91, 0x1a], "Ruckus Wireless"),
+    ([0x8c, 0x0c, 0x90], "Ruckus Wireless"),
+    ([0xc4, 0x10, 0x8a], "Ruckus Wireless"),
+    ([0xd4, 0x68, 0x4d], "Ruckus Wireless"),
+    ([0xec, 0x58, 0xea], "Ruckus Wireless"),
+    ([0xf0, 0x3e, 0x90], "Ruckus Wireless"),
+    ([0x5c, 0x5b, 0x35], "Mist Systems"),
+    ([0x00, 0x19, 0x77], "Aerohive Networks"),
+    ([0x88, 0x5b, 0xdd], "Aerohive Networks"),
+    ([0x9c, 0x5d, 0x12], "Aerohive Networks"),
+    ([0xe0, 0x1c, 0x41], "Aerohive Networks"),
+    ([0x00, 0x09, 0x0f], "Fortinet"),
+    ([0x04, 0xd5, 0x90], "Fortinet"),
+    ([0x08, 0x5b, 0x0e], "Fortinet"),
+    ([0x70, 0x4c, 0xa5], "Fortinet"),
+    ([0x90, 0x6c, 0xac], "Fortinet"),
+    ([0xe8, 0x1c, 0xba], "Fortinet"),
+    ([0x00, 0x0c, 0x42], "MikroTik"),
+    ([0x08, 0x55, 0x31], "MikroTik"),
+    ([0x18, 0xfd, 0x74], "MikroTik"),
+    ([0x2c, 0xc8, 0x1b], "MikroTik"),
+    ([0x48, 0x8f, 0x5a], "MikroTik"),
+    ([0x4c, 0x5e, 0x0c], "MikroTik"),
+    ([0x64, 0xd1, 0x54], "MikroTik"),
+    ([0x6c, 0x3b, 0x6b], "MikroTik"),
+    ([0x74, 0x4d, 0x28], "MikroTik"),
+    ([0xb8, 0x69, 0xf4], "MikroTik"),
+    ([0xc4, 0xad, 0x34], "MikroTik"),
+    ([0xcc, 0x2d, 0xe0], "MikroTik"),
+    ([0xd4, 0xca, 0x6d], "MikroTik"),
+    ([0xdc, 0x2c, 0x6e], "MikroTik"),
+    ([0xe4, 0x8d, 0x8c], "MikroTik"),
+    ([0x00, 0x04, 0x0e], "AVM"),
+    ([0x24, 0x65, 0x11], "AVM"),
+    ([0x2c, 0x91, 0xab], "AVM"),
+    ([0x34, 0x31, 0xc4], "AVM"),
+    ([0x38, 0x10, 0xd5], "AVM"),
+    ([0x3c, 0xa6, 0x2f], "AVM"),
+    ([0x44, 0x4e, 0x6d], "AVM"),
+    ([0x5c, 0x49, 0x79], "AVM"),
+    ([0x7c, 0xff, 0x4d], "AVM"),
+    ([0x98, 0x9b, 0xcb], "AVM"),
+    ([0xbc, 0x05, 0x43], "AVM"),
+    ([0xc0, 0x25, 0x06], "AVM"),
+    ([0xdc, 0x39, 0x6f], "AVM"),
+    ([0xe0, 0x28, 0x6d], "AVM"),
+    ([0x10, 0xfe, 0xed], "TP-Link"),
+    ([0x14, 0xcc, 0x20], "TP-Link"),
+    ([0x18, 0xa6, 0xf7], "TP-Link"),
+    ([0x30, 0xb5, 0xc2], "TP-Link"),
+    ([0x50, 0xc7, 0xbf], "TP-Link"),
+    ([0x50, 0xd4, 0xf7], "TP-Link"),
+    ([0x54, 0xc8, 0x0f], "TP-Link"),
+    ([0x60, 0xe3, 0x27], "TP-Link"),
+    ([0x64, 0x70, 0x02], "TP-Link"),
+    ([0x90, 0xf6, 0x52], "TP-Link"),
+    ([0x98, 0xde, 0xd0], "TP-Link"),
+    ([0xa0, 0xf3, 0xc1], "TP-Link"),
+    ([0xb0, 0x4e, 0x26], "TP-Link"),
+    ([0xc0, 0x4a, 0x00], "TP-Link"),
+    ([0xc4, 0x6e, 0x1f], "TP-Link"),
+    ([0xe8, 0xde, 0x27], "TP-Link"),
+    ([0xec, 0x08, 0x6b], "TP-Link"),
+    ([0xf4, 0xf2, 0x6d], "TP-Link"),
+    ([0xf8, 0x1a, 0x67], "TP-Link"),
+    ([0x00, 0x09, 0x5b], "Netgear"),
+    ([0x00, 0x14, 0x6c], "Netgear"),
+    ([0x00, 0x1b, 0x2f], "Netgear"),
+    ([0x00, 0x1e, 0x2a], "Netgear"),
+    ([0x00, 0x22, 0x3f], "Netgear"),
+    ([0x00, 0x24, 0xb2], "Netgear"),
+    ([0x00, 0x8e, 0xf2], "Netgear"),
+    ([0x10, 0x0d, 0x7f], "Netgear"),
+    ([0x20, 0x4e, 0x7f], "Netgear"),
+    ([0x28, 0xc6, 0x8e], "Netgear"),
+    ([0x2c, 0xb0, 0x5d], "Netgear"),
+    ([0x30, 0x46, 0x9a], "Netgear"),
+    ([0x84, 0x1b, 0x5e], "Netgear"),
+    ([0x9c, 0xd3, 0x6d], "Netgear"),
+    ([0xa0, 0x04, 0x60], "Netgear"),
+    ([0xa0, 0x40, 0xa0], "Netgear"),
+    ([0xb0, 0x7f, 0xb9], "Netgear"),
+    ([0xc0, 0x3f, 0x0e], "Netgear"),
+    ([0xc4, 0x04, 0x15], "Netgear"),
+    ([0xe0, 0x91, 0xf5], "Netgear"),
+    ([0xe4, 0xf4, 0xc6], "Netgear"),
+    ([0x00, 0x25, 0x9c], "Cisco-Linksys"),
+    ([0x20, 0xaa, 0x4b], "Cisco-Linksys"),
+    ([0x48, 0xf8, 0xb3], "Cisco-Linksys"),
+    ([0x58, 0x6d, 0x8f], "Cisco-Linksys"),
+    ([0x00, 0x05, 0x5d], "D-Link"),
+    ([0x00, 0x0d, 0x88], "D-Link"),
+    ([0x00, 0x11, 0x95], "D-Link"),
+    ([0x00, 0x13, 0x46], "D-Link"),
+    ([0x00, 0x15, 0xe9], "D-Link"),
+    ([0x00, 0x17, 0x9a], "D-Link"),
+    ([0x00, 0x19, 0x5b], "D-Link"),
+    ([0x00, 0x1b, 0x11], "D-Link"),
+    ([0x00, 0x1c, 0xf0], "D-Link"),
+    ([0x00, 0x1e, 0x58], "D-Link"),
+    ([0x00, 0x21, 0x91], "D-Link"),
+    ([0x00, 0x22, 0xb0], "D-Link"),
+    ([0x00, 0x24, 0x01], "D-Link"),
+    ([0x00, 0x26, 0x5a], "D-Link"),
+    ([0x14, 0xd6, 0x4d], "D-Link"),
+    ([0x1c, 0x7e, 0xe5], "D-Link"),
+    ([0x28, 0x10, 0x7b], "D-Link"),
+    ([0x84, 0xc9, 0xb2], "D-Link"),
+    ([0xb8, 0xa3, 0x86], "D-Link"),
+    ([0xc4, 0xa8, 0x1d], "D-Link"),
+    ([0xc8, 0xbe, 0x19], "D-Link"),
+    ([0xf0, 0x7d, 0x68], "D-Link"),
+    ([0x00, 0x0c, 0x6e], "ASUSTek"),
+    ([0x00, 0x1a, 0x92], "ASUSTek"),
+    ([0x00, 0x1d, 0x60], "ASUSTek"),
+    ([0x00, 0x1e, 0x8c], "ASUSTek"),
+    ([0x00, 0x22, 0x15], "ASUSTek"),
+    ([0x00, 0x23, 0x54], "ASUSTek"),
+    ([0x00, 0x24, 0x8c], "ASUSTek"),
+    ([0x00, 0x26, 0x18], "ASUSTek"),
+    ([0x04, 0x92, 0x26], "ASUSTek"),
+    ([0x04, 0xd4, 0xc4], "ASUSTek"),
+    ([0x08, 0x60, 0x6e], "ASUSTek"),
+    ([0x10, 0xbf, 0x48], "ASUSTek"),
+    ([0x14, 0xda, 0xe9], "ASUSTek"),
+    ([0x1c, 0x87, 0x2c], "ASUSTek"),
+    ([0x2c, 0x56, 0xdc], "ASUSTek"),
+    ([0x30, 0x5a, 0x3a], "ASUSTek"),
+    ([0x38, 0xd5, 0x47], "ASUSTek"),
+    ([0x50, 0x46, 0x5d], "ASUSTek"),
+    ([0x60, 0x45, 0xcb], "ASUSTek"),
+    ([0x74, 0xd0, 0x2b], "ASUSTek"),
+    ([0xac, 0x9e, 0x17], "ASUSTek"),
+    ([0xbc, 0xee, 0x7b], "ASUSTek"),
+    ([0xd8, 0x50, 0xe6], "ASUSTek"),
+    ([0xe0, 0x3f, 0x49], "ASUSTek"),
+    ([0xf0, 0x79, 0x59], "ASUSTek"),
+    ([0xf4, 0x6d, 0x04], "ASUSTek"),
+    ([0x00, 0x13, 0x49], "Zyxel"),
+    ([0x00, 0x19, 0xcb], "Zyxel"),
+    ([0x00, 0x23, 0xf8], "Zyxel"),
+    ([0x00, 0xa0, 0xc5], "Zyxel"),
+    ([0x40, 0x4a, 0x03], "Zyxel"),
+    ([0x5c, 0xf4, 0xab], "Zyxel"),
+    ([0x90, 0xef, 0x68], "Zyxel"),
+    ([0xb0, 0xb2, 0xdc], "Zyxel"),
+    ([0xe4, 0x18, 0x6b], "Zyxel"),
+    ([0xfc, 0xf5, 0x28], "Zyxel"),
+    ([0x00, 0x18, 0x82], "Huawei"),
+    ([0x00, 0x1e, 0x10], "Huawei"),
+    ([0x00, 0x25, 0x9e], "Huawei"),
+    ([0x00, 0x46, 0x4b], "Huawei"),
+    ([0x00, 0xe0, 0xfc], "Huawei"),
+    ([0x04, 0xf9, 0x38], "Huawei"),
+    ([0x20, 0xf3, 0xa3], "Huawei"),
+    ([0x28, 0x6e, 0xd4], "Huawei"),
+    ([0x48, 0x46, 0xfb], "Huawei"),
+    ([0x70, 0x72, 0x3c], "Huawei"),
+    ([0xac, 0xe2, 0x15], "Huawei"),
+    ([0x00, 0x1a, 0x11], "Google"),
+    ([0x3c, 0x5a, 0xb4], "Google"),
+    ([0x54, 0x60, 0x09], "Google"),
+    ([0xf4, 0xf5, 0xd8], "Google"),
+    ([0xf4, 0xf5, 0xe8], "Google"),
+    ([0x00, 0x03, 0x93], "Apple"),
+    ([0x00, 0x0a, 0x95], "Apple"),
+    ([0x00, 0x17, 0xf2], "Apple"),
+    ([0x00, 0x1c, 0xb3], "Apple"),
+    ([0x00, 0x1e, 0x52], "Apple"),
+    ([0x00, 0x23, 0x12], "Apple"),
+    ([0x00, 0x25, 0x00], "Apple"),
+    ([0x00, 0x10, 0x18], "Broadcom"),
+    ([0x00, 0x03, 0x7f], "Qualcomm Atheros"),
+    ([0x00, 0x0c, 0x43], "Ralink Technology"),
+    ([0x00, 0x0c, 0xe7], "MediaTek"),
+    ([0x00, 0xe0, 0x4c], "Realtek"),
+    ([0x00, 0x50, 0x43], "Marvell"),
+];
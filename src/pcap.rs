@@ -0,0 +1,609 @@
+//! Offline scanning of monitor mode captures.
+//!
+//! [`PcapScanner`] reads beacons and probe responses from pcap and pcapng files and
+//! turns them into [`Wifi`] records, so captures can be analysed like live scans.
+//! Supported link types are raw 802.11, 802.11 with radiotap header and 802.11 with
+//! PPI header. Signal level and frequency are taken from the radiotap or PPI header,
+//! the channel falls back to the information elements if the capture has none.
+//!
+//! ```no_run
+//! use wifi_scan::{pcap::PcapScanner, WlanScanner};
+//!
+//! let mut scanner = PcapScanner::new("capture.pcapng");
+//! for wifi in scanner.scan().unwrap() {
+//!     println!("{}", wifi);
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    export::ScanRecord,
+    ie,
+    misc::{format_mac, get_channel},
+    phy,
+    radiotap::Radiotap,
+    security, Error, Result, Wifi, WlanScanner,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Link type of 802.11 frames without radio header
+pub const LINKTYPE_IEEE802_11: u32 = 105;
+/// Link type of 802.11 frames with radiotap header
+pub const LINKTYPE_IEEE802_11_RADIOTAP: u32 = 127;
+/// Link type of frames with Per-Packet Information header
+pub const LINKTYPE_PPI: u32 = 192;
+
+const PCAP_MICROSECONDS: u32 = 0xa1b2_c3d4;
+const PCAP_NANOSECONDS: u32 = 0xa1b2_3c4d;
+pub(crate) const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+pub(crate) const PCAPNG_BYTE_ORDER: u32 = 0x1a2b_3c4d;
+pub(crate) const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_PACKET: u32 = 2;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+pub(crate) const PCAPNG_NAME_RESOLUTION: u32 = 4;
+pub(crate) const PCAPNG_ENHANCED_PACKET: u32 = 6;
+
+pub(crate) const OPTION_END: u16 = 0;
+pub(crate) const OPTION_COMMENT: u16 = 1;
+pub(crate) const OPTION_IF_NAME: u16 = 2;
+pub(crate) const OPTION_SHB_OS: u16 = 3;
+pub(crate) const OPTION_SHB_USERAPPL: u16 = 4;
+const OPTION_IF_TSRESOL: u16 = 9;
+const OPTION_IF_FCSLEN: u16 = 13;
+
+/// Frame control subtypes of management frames
+const SUBTYPE_PROBE_RESPONSE: u8 = 5;
+pub(crate) const SUBTYPE_BEACON: u8 = 8;
+
+/// A packet read from a capture file
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Packet {
+    /// Link type of the interface the packet was captured on
+    pub link_type: u32,
+    /// Capture time. `None` for pcapng simple packet blocks.
+    #[cfg_attr(feature = "serde", serde(default, with = "crate::schema::timestamp"))]
+    pub timestamp: Option<SystemTime>,
+    /// Number of FCS bytes at the end of the frame, if declared by the capture file
+    pub fcs_len: Option<u8>,
+    /// Captured bytes, starting with the radio header if the link type has one
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::hex"))]
+    pub data: Vec<u8>,
+}
+
+impl Packet {
+    /// Decodes the packet if it is a beacon or probe response.
+    /// Frames with a bad FCS according to the radio header are ignored.
+    pub fn wifi(&self) -> Option<Wifi> {
+        decode(
+            self.link_type,
+            &self.data,
+            self.fcs_len.unwrap_or(0) as usize,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    link_type: u32,
+    /// Timestamp units per second
+    resolution: u64,
+    fcs_len: Option<u8>,
+}
+
+enum Format {
+    Pcap {
+        big_endian: bool,
+        interface: Interface,
+    },
+    Pcapng {
+        big_endian: bool,
+        interfaces: Vec<Interface>,
+    },
+}
+
+/// Reads the packets of a pcap or pcapng capture.
+/// The format and byte order are detected from the file header.
+pub struct PcapReader<R: Read> {
+    reader: R,
+    format: Format,
+    done: bool,
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Reads the file header
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        let format = if u32::from_le_bytes(magic) == PCAPNG_SECTION_HEADER {
+            let mut length = [0; 4];
+            reader.read_exact(&mut length)?;
+            let big_endian = read_section_header(&mut reader, length)?;
+            Format::Pcapng {
+                big_endian,
+                interfaces: Vec::new(),
+            }
+        } else {
+            let (big_endian, resolution) =
+                match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+                    (PCAP_MICROSECONDS, _) => (false, 1_000_000),
+                    (PCAP_NANOSECONDS, _) => (false, 1_000_000_000),
+                    (_, PCAP_MICROSECONDS) => (true, 1_000_000),
+                    (_, PCAP_NANOSECONDS) => (true, 1_000_000_000),
+                    _ => return Err(invalid("not a pcap or pcapng file")),
+                };
+
+            // version (4), time zone (4), significant figures (4), snapshot length (4)
+            let mut header = [0; 20];
+            reader.read_exact(&mut header)?;
+            let network = u32_at(&header, 16, big_endian);
+            // the upper bits may declare the FCS length in 16 bit words
+            let fcs_len = if network & 0x1000_0000 != 0 {
+                Some((network >> 29) as u8 * 2)
+            } else {
+                None
+            };
+
+            Format::Pcap {
+                big_endian,
+                interface: Interface {
+                    link_type: network & 0x0fff_ffff,
+                    resolution,
+                    fcs_len,
+                },
+            }
+        };
+
+        Ok(PcapReader {
+            reader,
+            format,
+            done: false,
+        })
+    }
+
+    /// Reads the next packet. Returns `None` at the end of the file.
+    fn read_packet(&mut self) -> io::Result<Option<Packet>> {
+        match &mut self.format {
+            Format::Pcap {
+                big_endian,
+                interface,
+            } => {
+                let Some(header) = read_header::<_, 16>(&mut self.reader)? else {
+                    return Ok(None);
+                };
+                let seconds = u32_at(&header, 0, *big_endian) as u64;
+                let fraction = u32_at(&header, 4, *big_endian) as u64;
+                let length = u32_at(&header, 8, *big_endian) as usize;
+                let data = read_bytes(&mut self.reader, length)?;
+
+                Ok(Some(Packet {
+                    link_type: interface.link_type,
+                    timestamp: timestamp(
+                        seconds * interface.resolution + fraction,
+                        interface.resolution,
+                    ),
+                    fcs_len: interface.fcs_len,
+                    data,
+                }))
+            }
+            Format::Pcapng {
+                big_endian,
+                interfaces,
+            } => loop {
+                let Some(header) = read_header::<_, 8>(&mut self.reader)? else {
+                    return Ok(None);
+                };
+
+                let block_type = u32_at(&header, 0, *big_endian);
+                if block_type == PCAPNG_SECTION_HEADER {
+                    // a new section may change the byte order and starts without interfaces
+                    let length = [header[4], header[5], header[6], header[7]];
+                    *big_endian = read_section_header(&mut self.reader, length)?;
+                    interfaces.clear();
+                    continue;
+                }
+
+                let length = u32_at(&header, 4, *big_endian) as usize;
+                if length < 12 || length % 4 != 0 {
+                    return Err(invalid("invalid pcapng block length"));
+                }
+                let body = read_bytes(&mut self.reader, length - 8)?;
+                let body = &body[..body.len() - 4];
+
+                match block_type {
+                    PCAPNG_INTERFACE_DESCRIPTION => {
+                        interfaces.push(interface_description(body, *big_endian)?);
+                    }
+                    PCAPNG_ENHANCED_PACKET | PCAPNG_PACKET => {
+                        if body.len() < 20 {
+                            return Err(invalid("truncated pcapng packet block"));
+                        }
+                        let id = if block_type == PCAPNG_PACKET {
+                            u16_at(body, 0, *big_endian) as usize
+                        } else {
+                            u32_at(body, 0, *big_endian) as usize
+                        };
+                        let interface = interfaces
+                            .get(id)
+                            .ok_or_else(|| invalid("packet of an undeclared interface"))?;
+                        let time = (u32_at(body, 4, *big_endian) as u64) << 32
+                            | u32_at(body, 8, *big_endian) as u64;
+                        let length = u32_at(body, 12, *big_endian) as usize;
+                        let data = body
+                            .get(20..20 + length)
+                            .ok_or_else(|| invalid("truncated pcapng packet block"))?;
+
+                        return Ok(Some(Packet {
+                            link_type: interface.link_type,
+                            timestamp: timestamp(time, interface.resolution),
+                            fcs_len: interface.fcs_len,
+                            data: data.to_vec(),
+                        }));
+                    }
+                    PCAPNG_SIMPLE_PACKET => {
+                        let interface = interfaces
+                            .first()
+                            .ok_or_else(|| invalid("packet of an undeclared interface"))?;
+                        if body.len() < 4 {
+                            return Err(invalid("truncated pcapng packet block"));
+                        }
+                        // the captured length is only implied by the block length
+                        let length = u32_at(body, 0, *big_endian) as usize;
+                        let data = &body[4..];
+
+                        return Ok(Some(Packet {
+                            link_type: interface.link_type,
+                            timestamp: None,
+                            fcs_len: interface.fcs_len,
+                            data: data[..length.min(data.len())].to_vec(),
+                        }));
+                    }
+                    _ => {}
+                }
+            },
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = io::Result<Packet>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let packet = self.read_packet().transpose();
+        if !matches!(packet, Some(Ok(_))) {
+            self.done = true;
+        }
+        packet
+    }
+}
+
+/// Reads the rest of a section header block after the block type and length.
+/// Returns `true` if the section is big endian.
+fn read_section_header<R: Read>(reader: &mut R, length: [u8; 4]) -> io::Result<bool> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    let big_endian = match u32::from_le_bytes(magic) {
+        PCAPNG_BYTE_ORDER => false,
+        magic if magic.swap_bytes() == PCAPNG_BYTE_ORDER => true,
+        _ => return Err(invalid("invalid pcapng byte order magic")),
+    };
+
+    let length = u32_at(&length, 0, big_endian) as usize;
+    if length < 28 || length % 4 != 0 {
+        return Err(invalid("invalid pcapng block length"));
+    }
+    // version, section length and options are not needed
+    read_bytes(reader, length - 12)?;
+    Ok(big_endian)
+}
+
+/// Parses the body of an interface description block
+fn interface_description(body: &[u8], big_endian: bool) -> io::Result<Interface> {
+    if body.len() < 8 {
+        return Err(invalid("truncated pcapng interface description block"));
+    }
+
+    let mut interface = Interface {
+        link_type: u16_at(body, 0, big_endian) as u32,
+        resolution: 1_000_000,
+        fcs_len: None,
+    };
+
+    let mut options = &body[8..];
+    while options.len() >= 4 {
+        let code = u16_at(options, 0, big_endian);
+        let length = u16_at(options, 2, big_endian) as usize;
+        let Some(value) = options.get(4..4 + length) else {
+            break;
+        };
+
+        match (code, value.first()) {
+            (OPTION_END, _) => break,
+            (OPTION_IF_TSRESOL, Some(&resolution)) => {
+                // the most significant bit selects powers of two instead of ten
+                interface.resolution = if resolution & 0x80 != 0 {
+                    1u64.checked_shl((resolution & 0x7f) as u32)
+                } else {
+                    10u64.checked_pow(resolution as u32)
+                }
+                .ok_or_else(|| invalid("unsupported pcapng timestamp resolution"))?;
+            }
+            (OPTION_IF_FCSLEN, Some(&fcs_len)) => interface.fcs_len = Some(fcs_len),
+            _ => {}
+        }
+
+        options = options
+            .get(4 + length.div_ceil(4) * 4..)
+            .unwrap_or_default();
+    }
+
+    Ok(interface)
+}
+
+/// Reads a fixed size header. Returns `None` at the end of the file.
+fn read_header<R: Read, const N: usize>(reader: &mut R) -> io::Result<Option<[u8; N]>> {
+    let mut header = [0; N];
+    let mut read = 0;
+    while read < N {
+        match reader.read(&mut header[read..]) {
+            Ok(0) if read == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(Some(header))
+}
+
+/// Reads `length` bytes without trusting the length for the allocation
+fn read_bytes<R: Read>(reader: &mut R, length: usize) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.take(length as u64).read_to_end(&mut data)?;
+    if data.len() < length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(data)
+}
+
+fn u16_at(data: &[u8], offset: usize, big_endian: bool) -> u16 {
+    let bytes = [data[offset], data[offset + 1]];
+    if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    }
+}
+
+fn u32_at(data: &[u8], offset: usize, big_endian: bool) -> u32 {
+    let bytes = [
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ];
+    if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    }
+}
+
+/// Converts a timestamp in units of `1 / resolution` seconds since the epoch
+fn timestamp(time: u64, resolution: u64) -> Option<SystemTime> {
+    let nanoseconds = (time % resolution) as u128 * 1_000_000_000 / resolution as u128;
+    UNIX_EPOCH.checked_add(Duration::new(time / resolution, nanoseconds as u32))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Radio information from a radiotap or PPI header
+#[derive(Debug, Default)]
+struct Radio {
+    frequency: Option<u16>,
+    signal: Option<i8>,
+    fcs: bool,
+    bad_fcs: bool,
+}
+
+/// Parses the PPI header and its 802.11-Common field.
+/// Returns the radio information, the link type of the frame and the header length.
+fn ppi(data: &[u8]) -> Option<(Radio, u32, usize)> {
+    if data.len() < 8 || data[0] != 0 {
+        return None;
+    }
+    let aligned = data[1] & 0x01 != 0;
+    let length = u16::from_le_bytes([data[2], data[3]]) as usize;
+    let link_type = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    let header = data.get(..length)?;
+
+    let mut radio = Radio::default();
+    let mut offset = 8;
+    while let Some(field_header) = header.get(offset..offset + 4) {
+        let field_type = u16::from_le_bytes([field_header[0], field_header[1]]);
+        let field_length = u16::from_le_bytes([field_header[2], field_header[3]]) as usize;
+        let field = header.get(offset + 4..offset + 4 + field_length)?;
+
+        // 802.11-Common
+        if field_type == 2 && field.len() >= 20 {
+            let flags = u16::from_le_bytes([field[8], field[9]]);
+            radio.fcs = flags & 0x0001 != 0;
+            radio.bad_fcs = flags & 0x0004 != 0;
+            radio.frequency = Some(u16::from_le_bytes([field[12], field[13]]));
+            radio.signal = Some(field[18] as i8);
+        }
+
+        offset += 4 + field_length;
+        if aligned {
+            offset = offset.next_multiple_of(4);
+        }
+    }
+
+    Some((radio, link_type, length))
+}
+
+/// Decodes a beacon or probe response of the given link type
+fn decode(link_type: u32, data: &[u8], fcs_len: usize) -> Option<Wifi> {
+    let (radio, frame) = match link_type {
+        LINKTYPE_IEEE802_11 => (Radio::default(), data),
+        LINKTYPE_IEEE802_11_RADIOTAP => {
+            let radiotap = Radiotap::parse(data)?;
+            let radio = Radio {
+                frequency: radiotap.frequency(),
+                signal: radiotap.signal(),
+                fcs: radiotap.has_fcs(),
+                bad_fcs: radiotap.bad_fcs(),
+            };
+            (radio, &data[radiotap.length..])
+        }
+        LINKTYPE_PPI => {
+            let (radio, link_type, length) = ppi(data)?;
+            if link_type != LINKTYPE_IEEE802_11 {
+                return None;
+            }
+            (radio, &data[length..])
+        }
+        _ => return None,
+    };
+
+    if radio.bad_fcs {
+        return None;
+    }
+    let fcs_len = if radio.fcs { 4 } else { fcs_len };
+    let frame = frame.get(..frame.len().checked_sub(fcs_len)?)?;
+
+    // protocol version 0, management frame
+    let frame_control = *frame.first()?;
+    let subtype = frame_control >> 4;
+    if frame_control & 0x0f != 0 || !matches!(subtype, SUBTYPE_BEACON | SUBTYPE_PROBE_RESPONSE) {
+        return None;
+    }
+
+    // the order bit announces an HT Control field
+    let header_length = if frame.get(1)? & 0x80 != 0 { 28 } else { 24 };
+    let bssid = frame.get(16..22)?;
+    // timestamp (8), beacon interval (2), capability information (2)
+    let body = frame.get(header_length..)?;
+    let capability = u16::from_le_bytes([*body.get(10)?, *body.get(11)?]);
+    let ies = &body[12..];
+
+    let frequency = radio
+        .frequency
+        .map(|f| f as u32)
+        .filter(|f| get_channel(*f) != 0);
+    let channel = frequency
+        .map(get_channel)
+        .or_else(|| channel_from_elements(ies))
+        .unwrap_or(0);
+
+    Some(Wifi {
+        mac: format_mac(bssid),
+        ssid: ie::ssid(ies),
+        channel,
+        frequency: frequency.unwrap_or(0),
+        signal_level: radio.signal.map_or(0, |s| s as i32),
+        security: security::from_elements(ies, capability & 0x0010 != 0),
+        information_elements: ies.to_vec(),
+        inferred: false,
+    })
+}
+
+/// Returns the primary channel advertised in the DS Parameter Set, HT Operation
+/// or HE Operation element
+fn channel_from_elements(ies: &[u8]) -> Option<u32> {
+    ie::find(ies, ie::DS_PARAMETER_SET)
+        .or_else(|| ie::find(ies, ie::HT_OPERATION))
+        .or_else(|| phy::he_6ghz_operation(ies))
+        .and_then(|data| data.first())
+        .map(|channel| *channel as u32)
+        .filter(|channel| *channel != 0)
+}
+
+/// Reads all beacons and probe responses of a capture and returns one record per BSS,
+/// in the order the networks were first seen.
+///
+/// Each record holds the last frame of the BSS and its capture time. The SSID of a
+/// hidden network is kept if an earlier probe response revealed it.
+/// A capture that was cut off in the middle of a packet is read up to the last
+/// complete packet.
+pub fn read_records<R: Read>(reader: R) -> io::Result<Vec<ScanRecord>> {
+    let mut records: Vec<ScanRecord> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for packet in PcapReader::new(reader)? {
+        let packet = match packet {
+            Ok(packet) => packet,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e),
+        };
+        let Some(mut wifi) = packet.wifi() else {
+            continue;
+        };
+
+        match index.get(&wifi.mac) {
+            Some(&i) => {
+                if wifi.is_hidden() && !records[i].wifi.is_hidden() {
+                    wifi.ssid = std::mem::take(&mut records[i].wifi.ssid);
+                }
+                records[i] = ScanRecord {
+                    timestamp: packet.timestamp,
+                    wifi,
+                };
+            }
+            None => {
+                index.insert(wifi.mac.clone(), records.len());
+                records.push(ScanRecord {
+                    timestamp: packet.timestamp,
+                    wifi,
+                });
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+/// Scanner that reads networks from a pcap or pcapng capture instead of a WiFi adapter
+#[derive(Debug, Clone)]
+pub struct PcapScanner {
+    path: PathBuf,
+}
+
+impl PcapScanner {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        PcapScanner { path: path.into() }
+    }
+
+    /// Reads the networks of the capture together with the time they were last seen.
+    /// See [`read_records`].
+    pub fn records(&self) -> io::Result<Vec<ScanRecord>> {
+        read_records(BufReader::new(File::open(&self.path)?))
+    }
+}
+
+impl WlanScanner for PcapScanner {
+    /// Returns the networks of all beacons and probe responses in the capture,
+    /// one per BSSID
+    fn scan(&mut self) -> Result<Vec<Wifi>> {
+        let records = self
+            .records()
+            .map_err(|e| Error::ScanFailed(format!("{}: {}", self.path.display(), e)))?;
+        Ok(records.into_iter().map(|r| r.wifi).collect())
+    }
+}
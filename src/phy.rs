@@ -0,0 +1,369 @@
+//! Detection of the Wi-Fi generation and PHY capabilities of a network.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ie::{self, Elements};
+
+/// Wi-Fi generation / 802.11 PHY standard spoken by an access point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WifiGeneration {
+    /// 802.11b (DSSS/CCK rates only)
+    Legacy80211b,
+    /// 802.11a (OFDM rates on 5 GHz)
+    Legacy80211a,
+    /// 802.11g (OFDM rates on 2.4 GHz)
+    Legacy80211g,
+    /// Wi-Fi 4 (802.11n, HT)
+    Wifi4,
+    /// Wi-Fi 5 (802.11ac, VHT)
+    Wifi5,
+    /// Wi-Fi 6 (802.11ax, HE)
+    Wifi6,
+    /// Wi-Fi 6E (802.11ax on 6 GHz)
+    Wifi6E,
+    /// Wi-Fi 7 (802.11be, EHT)
+    Wifi7,
+}
+
+/// PHY capabilities derived from the information elements of a network
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhyInfo {
+    /// Highest Wi-Fi generation the network supports
+    pub generation: WifiGeneration,
+    /// Maximum number of spatial streams
+    pub max_spatial_streams: u8,
+    /// Channel width in MHz the network operates on (or supports if not advertised)
+    pub channel_width: u16,
+    /// Theoretical maximum PHY rate in Mbit/s
+    pub max_phy_rate: f64,
+}
+
+impl fmt::Display for WifiGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WifiGeneration::Legacy80211b => write!(f, "802.11b"),
+            WifiGeneration::Legacy80211a => write!(f, "802.11a"),
+            WifiGeneration::Legacy80211g => write!(f, "802.11g"),
+            WifiGeneration::Wifi4 => write!(f, "Wi-Fi 4 (802.11n)"),
+            WifiGeneration::Wifi5 => write!(f, "Wi-Fi 5 (802.11ac)"),
+            WifiGeneration::Wifi6 => write!(f, "Wi-Fi 6 (802.11ax)"),
+            WifiGeneration::Wifi6E => write!(f, "Wi-Fi 6E (802.11ax)"),
+            WifiGeneration::Wifi7 => write!(f, "Wi-Fi 7 (802.11be)"),
+        }
+    }
+}
+
+impl WifiGeneration {
+    /// Returns `true` for pre-802.11n networks (802.11a/b/g)
+    pub fn is_legacy(&self) -> bool {
+        matches!(
+            self,
+            WifiGeneration::Legacy80211a
+                | WifiGeneration::Legacy80211b
+                | WifiGeneration::Legacy80211g
+        )
+    }
+}
+
+impl PhyInfo {
+    /// Derives PHY capabilities from raw information elements.
+    /// `channel` is used to tell 802.11a and 802.11g apart.
+    /// Returns `None` if the elements contain no rate or capability information.
+    pub fn from_elements(ies: &[u8], channel: u32) -> Option<PhyInfo> {
+        let ht_cap = ie::find(ies, ie::HT_CAPABILITIES).filter(|d| d.len() >= 26);
+        let vht_cap = ie::find(ies, ie::VHT_CAPABILITIES).filter(|d| d.len() >= 12);
+        let he_cap = ie::find_extension(ies, ie::EXT_HE_CAPABILITIES).filter(|d| d.len() >= 19);
+        let eht_cap = ie::find_extension(ies, ie::EXT_EHT_CAPABILITIES).filter(|d| d.len() >= 14);
+
+        let operating_width = operating_width(ies);
+        let is_2ghz = (1..=14).contains(&channel) && he_6ghz_operation(ies).is_none();
+
+        if let Some(eht) = eht_cap {
+            let (nss, mcs) = eht_nss_mcs(&eht[11..]);
+            let supports_320 = eht[2] & 0x02 != 0;
+            let width = operating_width.unwrap_or(if supports_320 {
+                320
+            } else {
+                he_cap.map_or(20, |he| he_capability_width(he, is_2ghz))
+            });
+            return Some(PhyInfo::new(
+                WifiGeneration::Wifi7,
+                nss,
+                width,
+                mcs,
+                Symbol::He,
+            ));
+        }
+
+        if let Some(he) = he_cap {
+            let (nss, mcs) = two_bit_nss_mcs(u16::from_le_bytes([he[17], he[18]]), [7, 9, 11]);
+            let six_ghz = he_6ghz_operation(ies).is_some() || ht_cap.is_none();
+            let generation = if six_ghz {
+                WifiGeneration::Wifi6E
+            } else {
+                WifiGeneration::Wifi6
+            };
+            let width = operating_width.unwrap_or(he_capability_width(he, is_2ghz));
+            return Some(PhyInfo::new(generation, nss, width, mcs, Symbol::He));
+        }
+
+        if let Some(vht) = vht_cap {
+            let info = u32::from_le_bytes([vht[0], vht[1], vht[2], vht[3]]);
+            let (nss, mcs) = two_bit_nss_mcs(u16::from_le_bytes([vht[4], vht[5]]), [7, 8, 9]);
+            let width = operating_width.unwrap_or(if info & 0x0c != 0 { 160 } else { 80 });
+            let short_gi = match width {
+                160 => info & 0x40 != 0,
+                80 => info & 0x20 != 0,
+                _ => ht_cap.is_some_and(|ht| ht_short_gi(ht, width)),
+            };
+            return Some(PhyInfo::new(
+                WifiGeneration::Wifi5,
+                nss,
+                width,
+                mcs,
+                Symbol::Vht(short_gi),
+            ));
+        }
+
+        if let Some(ht) = ht_cap {
+            let nss = ht[3..7].iter().filter(|b| **b != 0).count().max(1) as u8;
+            let cap_width = if ht[0] & 0x02 != 0 { 40 } else { 20 };
+            let width = operating_width.unwrap_or(cap_width).min(40);
+            return Some(PhyInfo::new(
+                WifiGeneration::Wifi4,
+                nss,
+                width,
+                7,
+                Symbol::Vht(ht_short_gi(ht, width)),
+            ));
+        }
+
+        let rates: Vec<u8> = Elements::new(ies)
+            .filter(|e| e.id == ie::SUPPORTED_RATES || e.id == ie::EXTENDED_SUPPORTED_RATES)
+            .flat_map(|e| e.data.iter().map(|r| r & 0x7f))
+            .filter(|r| *r <= 108) // skip BSS membership selectors
+            .collect();
+        if rates.is_empty() {
+            return None;
+        }
+
+        let has_ofdm = rates
+            .iter()
+            .any(|r| [12, 18, 24, 36, 48, 72, 96, 108].contains(r));
+        let generation = if channel > 14 {
+            WifiGeneration::Legacy80211a
+        } else if has_ofdm {
+            WifiGeneration::Legacy80211g
+        } else {
+            WifiGeneration::Legacy80211b
+        };
+
+        Some(PhyInfo {
+            generation,
+            max_spatial_streams: 1,
+            channel_width: 20,
+            max_phy_rate: *rates.iter().max().unwrap_or(&0) as f64 / 2.0,
+        })
+    }
+
+    fn new(generation: WifiGeneration, nss: u8, width: u16, mcs: u8, symbol: Symbol) -> PhyInfo {
+        PhyInfo {
+            generation,
+            max_spatial_streams: nss,
+            channel_width: width,
+            max_phy_rate: phy_rate(nss, width, mcs, symbol),
+        }
+    }
+}
+
+/// OFDM symbol layout used for rate calculation
+#[derive(Clone, Copy)]
+enum Symbol {
+    /// HT/VHT symbols, `true` if the short guard interval is supported
+    Vht(bool),
+    /// HE/EHT symbols with 0.8 µs guard interval
+    He,
+}
+
+/// Calculates the PHY rate in Mbit/s for the given stream count, width and MCS index
+fn phy_rate(nss: u8, width: u16, mcs: u8, symbol: Symbol) -> f64 {
+    let (bits, rate) = match mcs {
+        0 => (1.0, 1.0 / 2.0),
+        1 => (2.0, 1.0 / 2.0),
+        2 => (2.0, 3.0 / 4.0),
+        3 => (4.0, 1.0 / 2.0),
+        4 => (4.0, 3.0 / 4.0),
+        5 => (6.0, 2.0 / 3.0),
+        6 => (6.0, 3.0 / 4.0),
+        7 => (6.0, 5.0 / 6.0),
+        8 => (8.0, 3.0 / 4.0),
+        9 => (8.0, 5.0 / 6.0),
+        10 => (10.0, 3.0 / 4.0),
+        11 => (10.0, 5.0 / 6.0),
+        12 => (12.0, 3.0 / 4.0),
+        _ => (12.0, 5.0 / 6.0),
+    };
+
+    let (subcarriers, symbol_time) = match symbol {
+        Symbol::Vht(short_gi) => {
+            let subcarriers = match width {
+                20 => 52.0,
+                40 => 108.0,
+                80 => 234.0,
+                _ => 468.0,
+            };
+            (subcarriers, if short_gi { 3.6 } else { 4.0 })
+        }
+        Symbol::He => {
+            let subcarriers = match width {
+                20 => 234.0,
+                40 => 468.0,
+                80 => 980.0,
+                160 => 1960.0,
+                _ => 3920.0,
+            };
+            (subcarriers, 13.6)
+        }
+    };
+
+    let mbps = subcarriers * bits * rate * nss as f64 / symbol_time;
+    (mbps * 10.0).round() / 10.0
+}
+
+/// Returns `true` if the HT capabilities advertise a short guard interval for the width
+fn ht_short_gi(ht: &[u8], width: u16) -> bool {
+    if width >= 40 {
+        ht[0] & 0x40 != 0
+    } else {
+        ht[0] & 0x20 != 0
+    }
+}
+
+/// Decodes a VHT/HE style MCS map (2 bits per spatial stream).
+/// Returns the number of streams and highest MCS index of the first stream.
+fn two_bit_nss_mcs(map: u16, mcs_table: [u8; 3]) -> (u8, u8) {
+    let mut nss = 0;
+    for stream in 0..8 {
+        if (map >> (stream * 2)) & 0x03 != 0x03 {
+            nss = stream + 1;
+        }
+    }
+    let mcs = match map & 0x03 {
+        0x03 => mcs_table[0],
+        value => mcs_table[value as usize],
+    };
+    (nss.max(1), mcs)
+}
+
+/// Decodes the EHT-MCS map for widths up to 80 MHz
+fn eht_nss_mcs(map: &[u8]) -> (u8, u8) {
+    let nss = map.iter().take(3).map(|b| b & 0x0f).max().unwrap_or(1);
+    let mcs = if map[2] & 0x0f != 0 {
+        13
+    } else if map[1] & 0x0f != 0 {
+        11
+    } else {
+        9
+    };
+    (nss.max(1), mcs)
+}
+
+/// Returns the widest channel supported according to the HE PHY capabilities
+fn he_capability_width(he: &[u8], is_2ghz: bool) -> u16 {
+    let width_set = he[6] >> 1;
+    if is_2ghz {
+        if width_set & 0x01 != 0 {
+            40
+        } else {
+            20
+        }
+    } else if width_set & 0x0c != 0 {
+        160
+    } else if width_set & 0x02 != 0 {
+        80
+    } else {
+        20
+    }
+}
+
+/// Returns the 6 GHz Operation Information of the HE Operation element, if present
+pub(crate) fn he_6ghz_operation(ies: &[u8]) -> Option<&[u8]> {
+    let he_op = ie::find_extension(ies, ie::EXT_HE_OPERATION).filter(|d| d.len() >= 6)?;
+    let params = u32::from_le_bytes([he_op[0], he_op[1], he_op[2], 0]);
+    if params & (1 << 17) == 0 {
+        return None;
+    }
+
+    let mut offset = 6;
+    if params & (1 << 14) != 0 {
+        offset += 3;
+    }
+    if params & (1 << 15) != 0 {
+        offset += 1;
+    }
+    he_op.get(offset..offset + 5)
+}
+
+/// Returns the center channel of the whole operating channel from the EHT Operation element
+pub(crate) fn eht_center_channel(ies: &[u8]) -> Option<u32> {
+    let eht_op = ie::find_extension(ies, ie::EXT_EHT_OPERATION)?;
+    if eht_op.first()? & 0x01 == 0 {
+        return None;
+    }
+    // CCFS0 holds the center of 20 to 80 MHz channels, CCFS1 that of 160 and 320 MHz ones
+    let ccfs = if eht_op.get(5)? & 0x07 >= 3 {
+        eht_op.get(7)?
+    } else {
+        eht_op.get(6)?
+    };
+    Some(*ccfs as u32).filter(|c| *c != 0)
+}
+
+/// Returns the channel width in MHz the network is operating on, if advertised
+pub(crate) fn operating_width(ies: &[u8]) -> Option<u16> {
+    if let Some(eht_op) = ie::find_extension(ies, ie::EXT_EHT_OPERATION) {
+        if eht_op.first().is_some_and(|p| p & 0x01 != 0) {
+            if let Some(control) = eht_op.get(5) {
+                return Some(match control & 0x07 {
+                    0 => 20,
+                    1 => 40,
+                    2 => 80,
+                    3 => 160,
+                    _ => 320,
+                });
+            }
+        }
+    }
+
+    if let Some(info) = he_6ghz_operation(ies) {
+        return Some(match info[1] & 0x03 {
+            0 => 20,
+            1 => 40,
+            2 => 80,
+            _ => 160,
+        });
+    }
+
+    if let Some(vht_op) = ie::find(ies, ie::VHT_OPERATION).filter(|d| d.len() >= 3) {
+        match vht_op[0] {
+            0 => {}
+            1 => {
+                // 160 MHz may be signalled through the second center frequency segment
+                if vht_op[2] != 0 && vht_op[2].abs_diff(vht_op[1]) == 8 {
+                    return Some(160);
+                }
+                return Some(80);
+            }
+            _ => return Some(160),
+        }
+    }
+
+    ie::find(ies, ie::HT_OPERATION)
+        .filter(|d| d.len() >= 2)
+        .map(|ht_op| if ht_op[1] & 0x04 != 0 { 40 } else { 20 })
+}
@@ -0,0 +1,271 @@
+//! Indoor positioning by trilateration against known access point locations.
+//!
+//! ```no_run
+//! use wifi_scan::{distance::DistanceConfig, positioning::{ApLocation, ApMap}};
+//!
+//! let mut map = ApMap::new();
+//! map.insert("00:11:22:33:44:55", ApLocation::new(0.0, 0.0, 0));
+//! map.insert("00:11:22:33:44:66", ApLocation::new(20.0, 0.0, 0));
+//! map.insert("00:11:22:33:44:77", ApLocation::new(10.0, 15.0, 0));
+//!
+//! let wifis = wifi_scan::scan().unwrap();
+//! if let Some(position) = map.locate(&wifis, &DistanceConfig::indoor()) {
+//!     println!("{}", position);
+//! }
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    distance::{DistanceConfig, DistanceEstimate},
+    misc::{format_mac, parse_mac},
+    Wifi,
+};
+
+/// Maximum number of Gauss-Newton iterations
+const ITERATIONS: usize = 50;
+
+/// Location of an access point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApLocation {
+    /// X coordinate in metres
+    pub x: f64,
+    /// Y coordinate in metres
+    pub y: f64,
+    /// Floor number
+    pub floor: i32,
+}
+
+/// Table of known access point locations by BSSID
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApMap {
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::mac_map"))]
+    locations: HashMap<[u8; 6], ApLocation>,
+}
+
+/// An access point used for a position estimate
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UsedAp {
+    /// BSSID of the access point
+    pub bssid: String,
+    /// Known location of the access point
+    pub location: ApLocation,
+    /// Estimated distance to the access point
+    pub distance: DistanceEstimate,
+}
+
+/// Estimated position of the scanner
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Position {
+    /// X coordinate in metres
+    pub x: f64,
+    /// Y coordinate in metres
+    pub y: f64,
+    /// Floor number
+    pub floor: i32,
+    /// Radius in metres the actual position is likely to be within
+    pub error_radius: f64,
+    /// Access points the estimate is based on
+    pub aps_used: Vec<UsedAp>,
+}
+
+impl ApLocation {
+    pub fn new(x: f64, y: f64, floor: i32) -> Self {
+        ApLocation { x, y, floor }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({:.1}, {:.1}) on floor {} ± {:.1} m using {} APs",
+            self.x,
+            self.y,
+            self.floor,
+            self.error_radius,
+            self.aps_used.len()
+        )
+    }
+}
+
+impl ApMap {
+    /// Creates an empty table
+    pub fn new() -> Self {
+        ApMap::default()
+    }
+
+    /// Adds or replaces the location of an access point.
+    /// Returns `false` if the BSSID is invalid.
+    pub fn insert(&mut self, bssid: &str, location: ApLocation) -> bool {
+        match parse_mac(bssid) {
+            Some(mac) => {
+                self.locations.insert(mac, location);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes an access point, returning its location
+    pub fn remove(&mut self, bssid: &str) -> Option<ApLocation> {
+        self.locations.remove(&parse_mac(bssid)?)
+    }
+
+    /// Returns the location of an access point
+    pub fn get(&self, bssid: &str) -> Option<ApLocation> {
+        self.locations.get(&parse_mac(bssid)?).copied()
+    }
+
+    /// Returns the number of known access points
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Returns `true` if no access points are known
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Estimates the position of the scanner from scan results.
+    ///
+    /// The floor is the one with the most confident distance estimates. On that floor the
+    /// position is found by weighted least-squares trilateration, weighting each AP by the
+    /// inverse variance of its distance estimate. With fewer than three usable APs, or APs
+    /// in a line, the weighted centroid is returned with a correspondingly large error radius.
+    /// Returns `None` if no scanned network is in the table.
+    pub fn locate(&self, wifis: &[Wifi], config: &DistanceConfig) -> Option<Position> {
+        let mut candidates: Vec<(UsedAp, f64)> = Vec::new();
+        for wifi in wifis {
+            let Some(mac) = parse_mac(&wifi.mac) else {
+                continue;
+            };
+            let Some(location) = self.locations.get(&mac) else {
+                continue;
+            };
+            let Some(distance) = wifi.distance(config) else {
+                continue;
+            };
+            // ignore duplicate reports of a BSSID
+            if candidates
+                .iter()
+                .any(|(ap, _)| parse_mac(&ap.bssid) == Some(mac))
+            {
+                continue;
+            }
+
+            let spread = ((distance.max_meters - distance.min_meters) / 2.0).max(0.1);
+            candidates.push((
+                UsedAp {
+                    bssid: format_mac(&mac),
+                    location: *location,
+                    distance,
+                },
+                1.0 / (spread * spread),
+            ));
+        }
+
+        let floor = best_floor(&candidates)?;
+        let aps: Vec<(UsedAp, f64)> = candidates
+            .into_iter()
+            .filter(|(ap, _)| ap.location.floor == floor)
+            .collect();
+
+        let total_weight: f64 = aps.iter().map(|(_, w)| w).sum();
+        let centroid = (
+            aps.iter().map(|(ap, w)| ap.location.x * w).sum::<f64>() / total_weight,
+            aps.iter().map(|(ap, w)| ap.location.y * w).sum::<f64>() / total_weight,
+        );
+
+        let (x, y) = if aps.len() >= 3 {
+            solve(&aps, centroid)
+        } else {
+            None
+        }
+        .unwrap_or(centroid);
+
+        // weighted RMS of the residuals combined with the uncertainty of the distances
+        let residuals: f64 = aps
+            .iter()
+            .map(|(ap, w)| {
+                let r = (x - ap.location.x).hypot(y - ap.location.y) - ap.distance.meters;
+                w * r * r
+            })
+            .sum();
+        let error_radius = (residuals / total_weight + aps.len() as f64 / total_weight).sqrt();
+
+        Some(Position {
+            x,
+            y,
+            floor,
+            error_radius,
+            aps_used: aps.into_iter().map(|(ap, _)| ap).collect(),
+        })
+    }
+}
+
+/// Returns the floor with the highest total weight
+fn best_floor(candidates: &[(UsedAp, f64)]) -> Option<i32> {
+    let mut floors: Vec<(i32, f64)> = Vec::new();
+    for (ap, weight) in candidates {
+        match floors.iter_mut().find(|(f, _)| *f == ap.location.floor) {
+            Some((_, total)) => *total += weight,
+            None => floors.push((ap.location.floor, *weight)),
+        }
+    }
+
+    floors
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(floor, _)| floor)
+}
+
+/// Minimises the weighted squared distance residuals with Gauss-Newton iterations.
+/// Returns `None` if the geometry doesn't allow a unique solution.
+fn solve(aps: &[(UsedAp, f64)], start: (f64, f64)) -> Option<(f64, f64)> {
+    let (mut x, mut y) = start;
+
+    for _ in 0..ITERATIONS {
+        // normal equations (JᵀWJ) Δ = -JᵀWr
+        let (mut a11, mut a12, mut a22, mut b1, mut b2) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for (ap, w) in aps {
+            let dx = x - ap.location.x;
+            let dy = y - ap.location.y;
+            let range = dx.hypot(dy).max(1e-6);
+            let (jx, jy) = (dx / range, dy / range);
+            let r = range - ap.distance.meters;
+
+            a11 += w * jx * jx;
+            a12 += w * jx * jy;
+            a22 += w * jy * jy;
+            b1 -= w * jx * r;
+            b2 -= w * jy * r;
+        }
+
+        let det = a11 * a22 - a12 * a12;
+        if det.abs() < 1e-9 * (a11 + a22).powi(2).max(f64::MIN_POSITIVE) {
+            return None;
+        }
+        let step_x = (a22 * b1 - a12 * b2) / det;
+        let step_y = (a11 * b2 - a12 * b1) / det;
+        x += step_x;
+        y += step_y;
+
+        if step_x.hypot(step_y) < 1e-4 {
+            break;
+        }
+    }
+
+    if x.is_finite() && y.is_finite() {
+        Some((x, y))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,198 @@
+//! Filtering and sorting of scan results.
+//!
+//! ```no_run
+//! use wifi_scan::{query::{Query, SecurityClass, SortKey}, Band};
+//!
+//! let wifis = wifi_scan::scan().unwrap();
+//! let strongest = Query::new(&wifis)
+//!     .band(Band::FiveGhz)
+//!     .min_signal(-70)
+//!     .security(SecurityClass::Wpa3)
+//!     .sort_by(SortKey::Signal)
+//!     .top_n(3)
+//!     .collect();
+//! ```
+
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{misc::glob_match, Band, Wifi};
+
+/// Security classes that can be filtered for, backed by the `Wifi::is_*` predicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SecurityClass {
+    Open,
+    Wpa2,
+    Wpa3,
+    Enterprise,
+    Personal,
+}
+
+/// Keys scan results can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SortKey {
+    /// Strongest signal first, unknown signal levels last
+    Signal,
+    /// Lowest channel first
+    Channel,
+    /// SSID in alphabetical order, ignoring case
+    Ssid,
+}
+
+type Predicate<'a> = Box<dyn Fn(&Wifi) -> bool + 'a>;
+
+/// A query over a slice of scan results
+pub struct Query<'a> {
+    wifis: &'a [Wifi],
+    filters: Vec<Predicate<'a>>,
+    sort: Option<SortKey>,
+    reversed: bool,
+    limit: Option<usize>,
+}
+
+impl SecurityClass {
+    /// Returns `true` if the network belongs to this security class
+    pub fn matches(&self, wifi: &Wifi) -> bool {
+        match self {
+            SecurityClass::Open => wifi.is_open(),
+            SecurityClass::Wpa2 => wifi.is_wpa2(),
+            SecurityClass::Wpa3 => wifi.is_wpa3(),
+            SecurityClass::Enterprise => wifi.is_enterprise(),
+            SecurityClass::Personal => wifi.is_personal(),
+        }
+    }
+}
+
+impl<'a> Query<'a> {
+    /// Creates a query matching all networks
+    pub fn new(wifis: &'a [Wifi]) -> Self {
+        Query {
+            wifis,
+            filters: Vec::new(),
+            sort: None,
+            reversed: false,
+            limit: None,
+        }
+    }
+
+    /// Keeps networks matching a custom predicate
+    pub fn filter<F: Fn(&Wifi) -> bool + 'a>(mut self, predicate: F) -> Self {
+        self.filters.push(Box::new(predicate));
+        self
+    }
+
+    /// Keeps networks on the given band
+    pub fn band(self, band: Band) -> Self {
+        self.filter(move |w| w.band() == Some(band))
+    }
+
+    /// Keeps networks on the given channel
+    pub fn channel(self, channel: u32) -> Self {
+        self.filter(move |w| w.channel == channel)
+    }
+
+    /// Keeps networks with a known signal level of at least `dbm`
+    pub fn min_signal(self, dbm: i32) -> Self {
+        self.filter(move |w| w.signal_level != 0 && w.signal_level >= dbm)
+    }
+
+    /// Keeps networks of the given security class
+    pub fn security(self, class: SecurityClass) -> Self {
+        self.filter(move |w| class.matches(w))
+    }
+
+    /// Keeps networks whose SSID matches a glob pattern (`*` and `?`)
+    pub fn ssid_glob(self, pattern: &str) -> Self {
+        let pattern = pattern.to_string();
+        self.filter(move |w| glob_match(&pattern, &w.ssid))
+    }
+
+    /// Keeps networks whose SSID matches a regular expression
+    #[cfg(feature = "regex")]
+    pub fn ssid_regex(self, regex: regex::Regex) -> Self {
+        self.filter(move |w| regex.is_match(&w.ssid))
+    }
+
+    /// Keeps networks whose vendor contains `name`, ignoring case
+    #[cfg(feature = "oui")]
+    pub fn vendor(self, name: &str) -> Self {
+        let name = name.to_lowercase();
+        self.filter(move |w| w.vendor().is_some_and(|v| v.to_lowercase().contains(&name)))
+    }
+
+    /// Keeps hidden networks if `hidden` is `true`, visible networks otherwise
+    pub fn hidden(self, hidden: bool) -> Self {
+        self.filter(move |w| w.is_hidden() == hidden)
+    }
+
+    /// Sorts the results by the given key
+    pub fn sort_by(mut self, key: SortKey) -> Self {
+        self.sort = Some(key);
+        self
+    }
+
+    /// Reverses the sort order
+    pub fn reversed(mut self) -> Self {
+        self.reversed = !self.reversed;
+        self
+    }
+
+    /// Limits the results to the first `n` networks
+    pub fn top_n(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Runs the query
+    pub fn collect(self) -> Vec<&'a Wifi> {
+        let mut results: Vec<&Wifi> = self
+            .wifis
+            .iter()
+            .filter(|w| self.filters.iter().all(|f| f(w)))
+            .collect();
+
+        if let Some(key) = self.sort {
+            results.sort_by(|a, b| compare(key, a, b));
+        }
+        if self.reversed {
+            results.reverse();
+        }
+        if let Some(limit) = self.limit {
+            results.truncate(limit);
+        }
+
+        results
+    }
+
+    /// Runs the query and returns owned copies of the matching networks
+    pub fn collect_owned(self) -> Vec<Wifi> {
+        self.collect().into_iter().cloned().collect()
+    }
+
+    /// Returns the number of matching networks
+    pub fn count(self) -> usize {
+        self.collect().len()
+    }
+}
+
+/// Compares two networks by the given sort key
+fn compare(key: SortKey, a: &Wifi, b: &Wifi) -> Ordering {
+    match key {
+        SortKey::Signal => {
+            let signal = |w: &Wifi| {
+                if w.signal_level == 0 {
+                    i32::MIN
+                } else {
+                    w.signal_level
+                }
+            };
+            signal(b).cmp(&signal(a))
+        }
+        SortKey::Channel => a.channel.cmp(&b.channel),
+        SortKey::Ssid => a.ssid.to_lowercase().cmp(&b.ssid.to_lowercase()),
+    }
+}
@@ -0,0 +1,715 @@
+//! Parser for the radiotap headers of monitor mode captures.
+//!
+//! Radiotap prefixes captured 802.11 frames with radio information like the channel
+//! and the signal level. Fields are aligned to their natural size relative to the start
+//! of the header, and the presence bitmap can be extended with further bitmaps,
+//! additional radiotap namespaces (typically one per antenna) and vendor namespaces.
+//! See <https://www.radiotap.org> for the field definitions.
+//!
+//! ```
+//! use wifi_scan::radiotap::Radiotap;
+//!
+//! // flags, channel 2437 MHz and antenna signal -42 dBm
+//! let packet = [
+//!     0x00, 0x00, 0x0f, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x10, 0x00, 0x85, 0x09, 0xa0, 0x00,
+//!     0xd6, 0x80,
+//! ];
+//! let radiotap = Radiotap::parse(&packet).unwrap();
+//! assert_eq!(radiotap.frequency(), Some(2437));
+//! assert_eq!(radiotap.signal(), Some(-42));
+//! assert!(radiotap.has_fcs());
+//! assert_eq!(&packet[radiotap.length..], &[0x80]);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const PRESENT_TLV: u32 = 28;
+const PRESENT_RADIOTAP_NAMESPACE: u32 = 29;
+const PRESENT_VENDOR_NAMESPACE: u32 = 30;
+const PRESENT_EXT: u32 = 31;
+
+/// Flags of the Flags field
+pub mod flags {
+    /// Sent or received during the contention free period
+    pub const CFP: u8 = 0x01;
+    pub const SHORT_PREAMBLE: u8 = 0x02;
+    /// Sent or received with WEP encryption
+    pub const WEP: u8 = 0x04;
+    /// Sent or received with fragmentation
+    pub const FRAGMENTATION: u8 = 0x08;
+    /// The frame includes the FCS
+    pub const FCS: u8 = 0x10;
+    /// The frame has padding between the 802.11 header and the payload
+    pub const DATA_PAD: u8 = 0x20;
+    /// The frame failed the FCS check
+    pub const BAD_FCS: u8 = 0x40;
+    pub const SHORT_GI: u8 = 0x80;
+}
+
+/// Flags of the Channel field
+pub mod channel_flags {
+    pub const TURBO: u16 = 0x0010;
+    pub const CCK: u16 = 0x0020;
+    pub const OFDM: u16 = 0x0040;
+    pub const SPECTRUM_2GHZ: u16 = 0x0080;
+    pub const SPECTRUM_5GHZ: u16 = 0x0100;
+    pub const PASSIVE: u16 = 0x0200;
+    pub const DYNAMIC_CCK_OFDM: u16 = 0x0400;
+    pub const GFSK: u16 = 0x0800;
+    pub const GSM: u16 = 0x1000;
+    pub const STATIC_TURBO: u16 = 0x2000;
+    pub const HALF_RATE: u16 = 0x4000;
+    pub const QUARTER_RATE: u16 = 0x8000;
+}
+
+/// Channel field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Channel {
+    /// Center frequency in MHz
+    pub frequency: u16,
+    /// See [`channel_flags`]
+    pub flags: u16,
+}
+
+/// XChannel field, an extended channel field used by some drivers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct XChannel {
+    pub flags: u32,
+    /// Center frequency in MHz
+    pub frequency: u16,
+    pub channel: u8,
+    /// Maximum transmit power in dBm
+    pub max_power: u8,
+}
+
+/// Frequency hopping field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fhss {
+    pub hop_set: u8,
+    pub hop_pattern: u8,
+}
+
+/// MCS field of HT (802.11n) frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mcs {
+    /// Which of the flags and the index are known
+    pub known: u8,
+    pub flags: u8,
+    pub index: u8,
+}
+
+impl Mcs {
+    /// Returns the bandwidth in MHz, if known
+    pub fn bandwidth(&self) -> Option<u16> {
+        (self.known & 0x01 != 0).then_some(if self.flags & 0x03 == 1 { 40 } else { 20 })
+    }
+
+    /// Returns the MCS index, if known
+    pub fn mcs(&self) -> Option<u8> {
+        (self.known & 0x02 != 0).then_some(self.index)
+    }
+
+    /// Returns `true` for the short guard interval, if known
+    pub fn short_gi(&self) -> Option<bool> {
+        (self.known & 0x04 != 0).then_some(self.flags & 0x04 != 0)
+    }
+
+    /// Returns `true` for greenfield format, if known
+    pub fn greenfield(&self) -> Option<bool> {
+        (self.known & 0x08 != 0).then_some(self.flags & 0x08 != 0)
+    }
+
+    /// Returns `true` for LDPC coding, if known
+    pub fn ldpc(&self) -> Option<bool> {
+        (self.known & 0x10 != 0).then_some(self.flags & 0x10 != 0)
+    }
+
+    /// Returns the number of space-time block coding streams, if known
+    pub fn stbc_streams(&self) -> Option<u8> {
+        (self.known & 0x20 != 0).then_some((self.flags >> 5) & 0x03)
+    }
+}
+
+/// A-MPDU status field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ampdu {
+    /// Identifies the subframes of the same A-MPDU
+    pub reference: u32,
+    pub flags: u16,
+    /// Delimiter CRC, valid if flagged as known
+    pub delimiter_crc: u8,
+}
+
+impl Ampdu {
+    /// Returns `true` if this is the last subframe, if known
+    pub fn is_last(&self) -> Option<bool> {
+        (self.flags & 0x0004 != 0).then_some(self.flags & 0x0008 != 0)
+    }
+
+    /// Returns `true` if this is a zero length subframe
+    pub fn is_zero_length(&self) -> bool {
+        self.flags & 0x0002 != 0
+    }
+}
+
+/// VHT field of VHT (802.11ac) frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vht {
+    /// Which of the fields are known
+    pub known: u16,
+    pub flags: u8,
+    pub bandwidth: u8,
+    /// MCS in the upper and number of spatial streams in the lower four bits, per user
+    pub mcs_nss: [u8; 4],
+    pub coding: u8,
+    pub group_id: u8,
+    pub partial_aid: u16,
+}
+
+impl Vht {
+    /// Returns the bandwidth in MHz, if known
+    pub fn bandwidth(&self) -> Option<u16> {
+        if self.known & 0x0040 == 0 {
+            return None;
+        }
+        match self.bandwidth {
+            0 => Some(20),
+            1..=3 => Some(40),
+            4..=10 => Some(80),
+            11..=25 => Some(160),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for the short guard interval, if known
+    pub fn short_gi(&self) -> Option<bool> {
+        (self.known & 0x0004 != 0).then_some(self.flags & 0x04 != 0)
+    }
+
+    /// Returns the MCS and number of spatial streams of a user (0 to 3),
+    /// or `None` if the user is not present
+    pub fn user(&self, user: usize) -> Option<(u8, u8)> {
+        let mcs_nss = *self.mcs_nss.get(user)?;
+        let nss = mcs_nss & 0x0f;
+        (nss != 0).then_some((mcs_nss >> 4, nss))
+    }
+
+    /// Returns `true` if a user is LDPC coded
+    pub fn ldpc(&self, user: usize) -> bool {
+        user < 4 && self.coding & (1 << user) != 0
+    }
+}
+
+/// Timestamp field, e.g. of the start or end of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timestamp {
+    pub timestamp: u64,
+    /// Accuracy in units of the timestamp, valid if flagged as known
+    pub accuracy: u16,
+    /// Unit in the lower and sampling position in the upper four bits
+    pub unit_position: u8,
+    pub flags: u8,
+}
+
+impl Timestamp {
+    /// Returns the length of a timestamp unit in nanoseconds
+    pub fn unit_nanoseconds(&self) -> Option<u64> {
+        match self.unit_position & 0x0f {
+            0 => Some(1_000_000),
+            1 => Some(1_000),
+            2 => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the timestamp is only 32 bits wide
+    pub fn is_32_bit(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+}
+
+/// PPDU format of an HE frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HeFormat {
+    Su,
+    ExtendedRangeSu,
+    Mu,
+    TriggerBased,
+}
+
+/// HE field of HE (802.11ax) frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct He {
+    /// `data1` and `data2` flag which of the values in `data3` to `data6` are known
+    pub data: [u16; 6],
+}
+
+impl He {
+    pub fn format(&self) -> HeFormat {
+        match self.data[0] & 0x03 {
+            0 => HeFormat::Su,
+            1 => HeFormat::ExtendedRangeSu,
+            2 => HeFormat::Mu,
+            _ => HeFormat::TriggerBased,
+        }
+    }
+
+    /// Returns the BSS color, if known
+    pub fn bss_color(&self) -> Option<u8> {
+        (self.data[0] & 0x0004 != 0).then_some((self.data[2] & 0x3f) as u8)
+    }
+
+    /// Returns the MCS index, if known
+    pub fn mcs(&self) -> Option<u8> {
+        (self.data[0] & 0x0020 != 0).then_some(((self.data[2] >> 8) & 0x0f) as u8)
+    }
+
+    /// Returns `true` for LDPC coding, if known
+    pub fn ldpc(&self) -> Option<bool> {
+        (self.data[0] & 0x0080 != 0).then_some(self.data[2] & 0x2000 != 0)
+    }
+
+    /// Returns the bandwidth in MHz, if known and not an RU allocation
+    pub fn bandwidth(&self) -> Option<u16> {
+        if self.data[0] & 0x4000 == 0 {
+            return None;
+        }
+        match self.data[4] & 0x0f {
+            0 => Some(20),
+            1 => Some(40),
+            2 => Some(80),
+            3 => Some(160),
+            _ => None,
+        }
+    }
+
+    /// Returns the guard interval in nanoseconds, if known
+    pub fn guard_interval(&self) -> Option<u16> {
+        if self.data[1] & 0x0002 == 0 {
+            return None;
+        }
+        match (self.data[4] >> 4) & 0x03 {
+            0 => Some(800),
+            1 => Some(1600),
+            2 => Some(3200),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of spatial streams, if known
+    pub fn nss(&self) -> Option<u8> {
+        (self.data[5] & 0x0f != 0).then_some((self.data[5] & 0x0f) as u8)
+    }
+}
+
+/// HE-MU field with the HE-SIG-A and HE-SIG-B information of HE MU frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeMu {
+    pub flags1: u16,
+    pub flags2: u16,
+    /// RU allocations of the first content channel
+    pub ru_channel1: [u8; 4],
+    /// RU allocations of the second content channel
+    pub ru_channel2: [u8; 4],
+}
+
+impl HeMu {
+    /// Returns the SIG-B MCS index, if known
+    pub fn sig_b_mcs(&self) -> Option<u8> {
+        (self.flags1 & 0x0010 != 0).then_some((self.flags1 & 0x0f) as u8)
+    }
+
+    /// Returns the bandwidth in MHz from HE-SIG-A, if known
+    pub fn bandwidth(&self) -> Option<u16> {
+        (self.flags2 & 0x0004 != 0).then_some(match self.flags2 & 0x03 {
+            0 => 20,
+            1 => 40,
+            2 => 80,
+            _ => 160,
+        })
+    }
+
+    /// Returns the number of HE-SIG-B symbols or MU-MIMO users, if known
+    pub fn sig_b_symbols_users(&self) -> Option<u8> {
+        (self.flags2 & 0x0800 != 0).then_some(((self.flags2 >> 4) & 0x0f) as u8)
+    }
+}
+
+/// HE-MU-other-user field with the per-user information of HE MU frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeMuOtherUser {
+    pub per_user_1: u16,
+    pub per_user_2: u16,
+    pub position: u8,
+    pub known: u8,
+}
+
+/// L-SIG field of legacy signal information
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LSig {
+    pub data1: u16,
+    pub data2: u16,
+}
+
+impl LSig {
+    /// Returns the legacy rate field, if known
+    pub fn rate(&self) -> Option<u8> {
+        (self.data1 & 0x0001 != 0).then_some((self.data2 & 0x0f) as u8)
+    }
+
+    /// Returns the legacy length field, if known
+    pub fn length(&self) -> Option<u16> {
+        (self.data1 & 0x0002 != 0).then_some(self.data2 >> 4)
+    }
+}
+
+/// Signal information of an additional radiotap namespace, usually one per antenna
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AntennaSignal {
+    pub antenna: Option<u8>,
+    /// Signal level in dBm
+    pub signal: Option<i8>,
+    /// Noise level in dBm
+    pub noise: Option<i8>,
+}
+
+/// Data of a vendor namespace
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VendorNamespace {
+    pub oui: [u8; 3],
+    pub sub_namespace: u8,
+    /// Presence bitmaps of the namespace, defined by the vendor
+    pub present: Vec<u32>,
+    pub data: Vec<u8>,
+}
+
+/// A parsed radiotap header. Fields that are not present are `None`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Radiotap {
+    /// Length of the header. The 802.11 frame starts at this offset.
+    pub length: usize,
+    /// Value of the MAC's time synchronization function timer in microseconds
+    pub tsft: Option<u64>,
+    /// See [`flags`]
+    pub flags: Option<u8>,
+    /// Legacy data rate in units of 500 kbps
+    pub rate: Option<u8>,
+    pub channel: Option<Channel>,
+    pub fhss: Option<Fhss>,
+    /// Signal level at the antenna in dBm
+    pub antenna_signal: Option<i8>,
+    /// Noise level at the antenna in dBm
+    pub antenna_noise: Option<i8>,
+    /// Barker code lock quality
+    pub lock_quality: Option<u16>,
+    /// Transmit power relative to the maximum power, unitless
+    pub tx_attenuation: Option<u16>,
+    /// Transmit power relative to the maximum power in dB
+    pub db_tx_attenuation: Option<u16>,
+    /// Transmit power in dBm
+    pub dbm_tx_power: Option<i8>,
+    /// Index of the antenna the frame was received or sent with
+    pub antenna: Option<u8>,
+    /// Signal level relative to an arbitrary reference in dB
+    pub db_antenna_signal: Option<u8>,
+    /// Noise level relative to an arbitrary reference in dB
+    pub db_antenna_noise: Option<u8>,
+    pub rx_flags: Option<u16>,
+    pub tx_flags: Option<u16>,
+    pub rts_retries: Option<u8>,
+    pub data_retries: Option<u8>,
+    pub xchannel: Option<XChannel>,
+    pub mcs: Option<Mcs>,
+    pub ampdu: Option<Ampdu>,
+    pub vht: Option<Vht>,
+    pub timestamp: Option<Timestamp>,
+    pub he: Option<He>,
+    pub he_mu: Option<HeMu>,
+    pub he_mu_other_user: Option<HeMuOtherUser>,
+    /// Type of a zero length PSDU
+    pub zero_length_psdu: Option<u8>,
+    pub lsig: Option<LSig>,
+    /// Signal information of the additional radiotap namespaces
+    pub antennas: Vec<AntennaSignal>,
+    pub vendor_namespaces: Vec<VendorNamespace>,
+}
+
+/// Returns the alignment and size of a field in the radiotap namespace
+fn field_layout(field: u32) -> Option<(usize, usize)> {
+    Some(match field {
+        0 => (8, 8),
+        1 | 2 => (1, 1),
+        3 => (2, 4),
+        4 => (2, 2),
+        5 | 6 => (1, 1),
+        7..=9 => (2, 2),
+        10..=13 => (1, 1),
+        14 | 15 => (2, 2),
+        16 | 17 => (1, 1),
+        18 => (4, 8),
+        19 => (1, 3),
+        20 => (4, 8),
+        21 => (2, 12),
+        22 => (8, 12),
+        23 | 24 => (2, 12),
+        25 => (2, 6),
+        26 => (1, 1),
+        27 => (2, 4),
+        _ => return None,
+    })
+}
+
+fn u16_at(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+impl Radiotap {
+    /// Parses the radiotap header at the start of a packet.
+    /// Returns `None` if the header is malformed or truncated.
+    ///
+    /// Parsing stops at the first field that is not defined, as the position of the
+    /// following fields is unknown. Fields before it are still returned.
+    pub fn parse(data: &[u8]) -> Option<Radiotap> {
+        if data.len() < 8 || data[0] != 0 {
+            return None;
+        }
+        let length = u16_at(data, 2) as usize;
+        let header = data.get(..length)?;
+
+        let mut present = Vec::new();
+        let mut offset = 4;
+        loop {
+            let word = u32_at(header.get(offset..offset + 4)?, 0);
+            present.push(word);
+            offset += 4;
+            if word & (1 << PRESENT_EXT) == 0 {
+                break;
+            }
+        }
+
+        let mut radiotap = Radiotap {
+            length,
+            ..Radiotap::default()
+        };
+        // bit offset of the current bitmap within its namespace
+        let mut base = 0;
+        let mut vendor: Option<usize> = None;
+
+        for (i, &word) in present.iter().enumerate() {
+            match vendor {
+                // the data of vendor namespaces was skipped with the namespace header
+                Some(namespace) => radiotap.vendor_namespaces[namespace].present.push(word),
+                None => {
+                    for bit in 0..PRESENT_TLV {
+                        if word & (1 << bit) == 0 {
+                            continue;
+                        }
+                        let Some((align, size)) = field_layout(base + bit) else {
+                            return Some(radiotap);
+                        };
+                        offset = offset.next_multiple_of(align);
+                        let field = header.get(offset..offset + size)?;
+                        radiotap.set_field(base + bit, field);
+                        offset += size;
+                    }
+                    if word & (1 << PRESENT_TLV) != 0 && base == 0 {
+                        // TLVs fill the rest of the header and are not decoded
+                        return Some(radiotap);
+                    }
+                }
+            }
+
+            if i + 1 == present.len() {
+                break;
+            }
+            if word & (1 << PRESENT_RADIOTAP_NAMESPACE) != 0 {
+                radiotap.antennas.push(AntennaSignal::default());
+                base = 0;
+                vendor = None;
+            } else if word & (1 << PRESENT_VENDOR_NAMESPACE) != 0 {
+                offset = offset.next_multiple_of(2);
+                let namespace = header.get(offset..offset + 6)?;
+                let skip = u16_at(namespace, 4) as usize;
+                let data = header.get(offset + 6..offset + 6 + skip)?;
+                radiotap.vendor_namespaces.push(VendorNamespace {
+                    oui: [namespace[0], namespace[1], namespace[2]],
+                    sub_namespace: namespace[3],
+                    present: Vec::new(),
+                    data: data.to_vec(),
+                });
+                offset += 6 + skip;
+                base = 0;
+                vendor = Some(radiotap.vendor_namespaces.len() - 1);
+            } else {
+                base += 32;
+            }
+        }
+
+        Some(radiotap)
+    }
+
+    /// Stores a field of the radiotap namespace
+    fn set_field(&mut self, field: u32, data: &[u8]) {
+        // additional namespaces repeat fields per antenna
+        if let Some(antenna) = self.antennas.last_mut() {
+            match field {
+                5 => antenna.signal = Some(data[0] as i8),
+                6 => antenna.noise = Some(data[0] as i8),
+                11 => antenna.antenna = Some(data[0]),
+                _ => {}
+            }
+            return;
+        }
+
+        match field {
+            0 => self.tsft = Some(u64::from_le_bytes(data.try_into().unwrap_or_default())),
+            1 => self.flags = Some(data[0]),
+            2 => self.rate = Some(data[0]),
+            3 => {
+                self.channel = Some(Channel {
+                    frequency: u16_at(data, 0),
+                    flags: u16_at(data, 2),
+                })
+            }
+            4 => {
+                self.fhss = Some(Fhss {
+                    hop_set: data[0],
+                    hop_pattern: data[1],
+                })
+            }
+            5 => self.antenna_signal = Some(data[0] as i8),
+            6 => self.antenna_noise = Some(data[0] as i8),
+            7 => self.lock_quality = Some(u16_at(data, 0)),
+            8 => self.tx_attenuation = Some(u16_at(data, 0)),
+            9 => self.db_tx_attenuation = Some(u16_at(data, 0)),
+            10 => self.dbm_tx_power = Some(data[0] as i8),
+            11 => self.antenna = Some(data[0]),
+            12 => self.db_antenna_signal = Some(data[0]),
+            13 => self.db_antenna_noise = Some(data[0]),
+            14 => self.rx_flags = Some(u16_at(data, 0)),
+            15 => self.tx_flags = Some(u16_at(data, 0)),
+            16 => self.rts_retries = Some(data[0]),
+            17 => self.data_retries = Some(data[0]),
+            18 => {
+                self.xchannel = Some(XChannel {
+                    flags: u32_at(data, 0),
+                    frequency: u16_at(data, 4),
+                    channel: data[6],
+                    max_power: data[7],
+                })
+            }
+            19 => {
+                self.mcs = Some(Mcs {
+                    known: data[0],
+                    flags: data[1],
+                    index: data[2],
+                })
+            }
+            20 => {
+                self.ampdu = Some(Ampdu {
+                    reference: u32_at(data, 0),
+                    flags: u16_at(data, 4),
+                    delimiter_crc: data[6],
+                })
+            }
+            21 => {
+                self.vht = Some(Vht {
+                    known: u16_at(data, 0),
+                    flags: data[2],
+                    bandwidth: data[3],
+                    mcs_nss: [data[4], data[5], data[6], data[7]],
+                    coding: data[8],
+                    group_id: data[9],
+                    partial_aid: u16_at(data, 10),
+                })
+            }
+            22 => {
+                self.timestamp = Some(Timestamp {
+                    timestamp: u64::from_le_bytes(data[..8].try_into().unwrap_or_default()),
+                    accuracy: u16_at(data, 8),
+                    unit_position: data[10],
+                    flags: data[11],
+                })
+            }
+            23 => {
+                self.he = Some(He {
+                    data: std::array::from_fn(|i| u16_at(data, i * 2)),
+                })
+            }
+            24 => {
+                self.he_mu = Some(HeMu {
+                    flags1: u16_at(data, 0),
+                    flags2: u16_at(data, 2),
+                    ru_channel1: [data[4], data[5], data[6], data[7]],
+                    ru_channel2: [data[8], data[9], data[10], data[11]],
+                })
+            }
+            25 => {
+                self.he_mu_other_user = Some(HeMuOtherUser {
+                    per_user_1: u16_at(data, 0),
+                    per_user_2: u16_at(data, 2),
+                    position: data[4],
+                    known: data[5],
+                })
+            }
+            26 => self.zero_length_psdu = Some(data[0]),
+            27 => {
+                self.lsig = Some(LSig {
+                    data1: u16_at(data, 0),
+                    data2: u16_at(data, 2),
+                })
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the frequency in MHz from the Channel or XChannel field
+    pub fn frequency(&self) -> Option<u16> {
+        self.channel
+            .map(|c| c.frequency)
+            .or(self.xchannel.map(|c| c.frequency))
+            .filter(|f| *f != 0)
+    }
+
+    /// Returns the signal level in dBm, or the strongest antenna's level if the
+    /// combined level is missing
+    pub fn signal(&self) -> Option<i8> {
+        self.antenna_signal
+            .or_else(|| self.antennas.iter().filter_map(|a| a.signal).max())
+    }
+
+    /// Returns `true` if the frame ends with the FCS
+    pub fn has_fcs(&self) -> bool {
+        self.flags.is_some_and(|f| f & flags::FCS != 0)
+    }
+
+    /// Returns `true` if the frame failed the FCS check
+    pub fn bad_fcs(&self) -> bool {
+        self.flags.is_some_and(|f| f & flags::BAD_FCS != 0)
+    }
+}
@@ -0,0 +1,164 @@
+//! Collection type for scan results and grouping into extended service sets.
+
+use std::ops::Deref;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{query::Query, Band, Wifi, WifiSecurity};
+
+/// A collection of scan results
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScanResults {
+    wifis: Vec<Wifi>,
+}
+
+/// An extended service set: all BSSes sharing the same SSID and security
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ess {
+    /// Network name. Empty for hidden networks.
+    pub ssid: String,
+    /// Securities supported by the network
+    pub security: Vec<WifiSecurity>,
+    /// All access points of the network
+    pub bsses: Vec<Wifi>,
+}
+
+impl ScanResults {
+    /// Creates a collection from a list of networks
+    pub fn new(wifis: Vec<Wifi>) -> Self {
+        ScanResults { wifis }
+    }
+
+    /// Returns the underlying list of networks
+    pub fn into_inner(self) -> Vec<Wifi> {
+        self.wifis
+    }
+
+    /// Starts a query over the results
+    pub fn query(&self) -> Query<'_> {
+        Query::new(&self.wifis)
+    }
+
+    /// Groups the results by SSID and security into extended service sets.
+    /// Hidden networks can't be told apart, so every hidden BSS becomes an ESS of its own.
+    pub fn group_by_ess(&self) -> Vec<Ess> {
+        let mut groups: Vec<Ess> = Vec::new();
+
+        for wifi in &self.wifis {
+            let existing = groups.iter_mut().find(|ess| {
+                !wifi.is_hidden()
+                    && ess.ssid == wifi.ssid
+                    && same_security(&ess.security, &wifi.security)
+            });
+
+            match existing {
+                Some(ess) => ess.bsses.push(wifi.clone()),
+                None => groups.push(Ess {
+                    ssid: wifi.ssid.clone(),
+                    security: wifi.security.clone(),
+                    bsses: vec![wifi.clone()],
+                }),
+            }
+        }
+
+        groups
+    }
+}
+
+impl Ess {
+    /// Returns the BSSIDs of all access points of the network
+    pub fn bssids(&self) -> Vec<String> {
+        self.bsses.iter().map(|b| b.mac.clone()).collect()
+    }
+
+    /// Returns the bands the network is available on
+    pub fn bands(&self) -> Vec<Band> {
+        let mut bands: Vec<Band> = self.bsses.iter().filter_map(|b| b.band()).collect();
+        bands.sort();
+        bands.dedup();
+        bands
+    }
+
+    /// Returns the access point with the strongest signal
+    pub fn best(&self) -> Option<&Wifi> {
+        self.bsses
+            .iter()
+            .max_by_key(|b| known_signal(b).unwrap_or(i32::MIN))
+    }
+
+    /// Returns the weakest signal level in dBm. `None` if no signal level is known.
+    pub fn min_signal(&self) -> Option<i32> {
+        self.bsses.iter().filter_map(known_signal).min()
+    }
+
+    /// Returns the strongest signal level in dBm. `None` if no signal level is known.
+    pub fn max_signal(&self) -> Option<i32> {
+        self.bsses.iter().filter_map(known_signal).max()
+    }
+
+    /// Returns `true` if the network is hidden
+    pub fn is_hidden(&self) -> bool {
+        self.ssid.is_empty()
+    }
+}
+
+impl Deref for ScanResults {
+    type Target = [Wifi];
+
+    fn deref(&self) -> &Self::Target {
+        &self.wifis
+    }
+}
+
+impl From<Vec<Wifi>> for ScanResults {
+    fn from(wifis: Vec<Wifi>) -> Self {
+        ScanResults::new(wifis)
+    }
+}
+
+impl From<ScanResults> for Vec<Wifi> {
+    fn from(results: ScanResults) -> Self {
+        results.wifis
+    }
+}
+
+impl FromIterator<Wifi> for ScanResults {
+    fn from_iter<I: IntoIterator<Item = Wifi>>(iter: I) -> Self {
+        ScanResults::new(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for ScanResults {
+    type Item = Wifi;
+    type IntoIter = std::vec::IntoIter<Wifi>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.wifis.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ScanResults {
+    type Item = &'a Wifi;
+    type IntoIter = std::slice::Iter<'a, Wifi>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.wifis.iter()
+    }
+}
+
+/// Returns the signal level of a network, `None` if unknown
+fn known_signal(wifi: &Wifi) -> Option<i32> {
+    if wifi.signal_level == 0 {
+        None
+    } else {
+        Some(wifi.signal_level)
+    }
+}
+
+/// Compares two security lists regardless of order
+fn same_security(a: &[WifiSecurity], b: &[WifiSecurity]) -> bool {
+    a.iter().all(|s| b.contains(s)) && b.iter().all(|s| a.contains(s))
+}
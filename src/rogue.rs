@@ -0,0 +1,284 @@
+//! Detection of rogue access points and evil twins against an allow-list policy.
+//!
+//! ```no_run
+//! use wifi_scan::{rogue::{RoguePolicy, SsidPolicy, Verdict}, Band, WifiSecurity};
+//!
+//! let policy = RoguePolicy::new().with(
+//!     SsidPolicy::new("Corp")
+//!         .oui("00:11:22")
+//!         .security(WifiSecurity::Wpa3PersonalSae)
+//!         .band(Band::FiveGhz),
+//! );
+//!
+//! let wifis = wifi_scan::scan().unwrap();
+//! for assessment in policy.analyze(&wifis) {
+//!     if let Verdict::Suspicious(reasons) = &assessment.verdict {
+//!         println!("{} {}: {:?}", assessment.wifi.ssid, assessment.wifi.mac, reasons);
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{misc::parse_mac, Band, Wifi, WifiSecurity};
+
+/// Expected properties of a network we own
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SsidPolicy {
+    /// Network name
+    pub ssid: String,
+    /// Permitted BSSIDs
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::mac_list"))]
+    pub bssids: Vec<[u8; 6]>,
+    /// Permitted vendor prefixes (first three bytes of the BSSID)
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::mac_list"))]
+    pub ouis: Vec<[u8; 3]>,
+    /// Securities every access point of the network has to offer
+    pub required_security: Vec<WifiSecurity>,
+    /// Bands the network may be seen on. Any band if empty.
+    pub bands: Vec<Band>,
+}
+
+/// Allow-list of the networks we own
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoguePolicy {
+    ssids: Vec<SsidPolicy>,
+}
+
+/// Reason a network is considered suspicious
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Reason {
+    /// The BSSID isn't permitted for the SSID
+    UnknownBssid,
+    /// The vendor prefix of the BSSID isn't permitted for the SSID
+    UnknownVendor,
+    /// Required securities are missing
+    SecurityDowngrade { missing: Vec<WifiSecurity> },
+    /// The network is open while the policy or another access point of the SSID is secured
+    OpenTwin,
+    /// The network is seen on a band it isn't allowed on
+    DisallowedBand(Band),
+}
+
+/// Classification of a network
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Verdict {
+    /// The network matches its policy
+    Authorized,
+    /// The network uses an SSID we own but violates its policy
+    Suspicious(Vec<Reason>),
+    /// The SSID isn't covered by the policy
+    Unknown,
+}
+
+/// Classification of a single network of a scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Assessment<'a> {
+    pub wifi: &'a Wifi,
+    pub verdict: Verdict,
+}
+
+impl SsidPolicy {
+    /// Creates a policy for `ssid` permitting any BSSID, security and band
+    pub fn new(ssid: &str) -> Self {
+        SsidPolicy {
+            ssid: ssid.to_string(),
+            bssids: Vec::new(),
+            ouis: Vec::new(),
+            required_security: Vec::new(),
+            bands: Vec::new(),
+        }
+    }
+
+    /// Permits a BSSID. Invalid addresses are ignored.
+    pub fn bssid(mut self, bssid: &str) -> Self {
+        if let Some(mac) = parse_mac(bssid) {
+            self.bssids.push(mac);
+        }
+        self
+    }
+
+    /// Permits all BSSIDs with a vendor prefix like `00:11:22`. Invalid prefixes are ignored.
+    pub fn oui(mut self, oui: &str) -> Self {
+        if let Some(mac) = parse_mac(&format!("{}:00:00:00", oui)) {
+            self.ouis.push([mac[0], mac[1], mac[2]]);
+        }
+        self
+    }
+
+    /// Requires access points to offer `security`
+    pub fn security(mut self, security: WifiSecurity) -> Self {
+        self.required_security.push(security);
+        self
+    }
+
+    /// Allows the network on `band`
+    pub fn band(mut self, band: Band) -> Self {
+        self.bands.push(band);
+        self
+    }
+
+    /// Returns `true` if the BSSID is permitted. Any BSSID is permitted if neither
+    /// BSSIDs nor vendor prefixes are configured.
+    fn permits_bssid(&self, mac: Option<[u8; 6]>) -> bool {
+        if self.bssids.is_empty() && self.ouis.is_empty() {
+            return true;
+        }
+        mac.is_some_and(|mac| self.bssids.contains(&mac) || self.permits_vendor(mac))
+    }
+
+    fn permits_vendor(&self, mac: [u8; 6]) -> bool {
+        self.ouis.contains(&[mac[0], mac[1], mac[2]])
+    }
+
+    /// Returns `true` if the policy expects a secured network
+    fn is_secured(&self) -> bool {
+        self.required_security
+            .iter()
+            .any(|s| *s != WifiSecurity::Open)
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::UnknownBssid => write!(f, "Unknown BSSID"),
+            Reason::UnknownVendor => write!(f, "Unknown vendor"),
+            Reason::SecurityDowngrade { missing } => {
+                let missing: Vec<String> = missing.iter().map(|s| s.to_string()).collect();
+                write!(f, "Security downgrade (missing {})", missing.join(", "))
+            }
+            Reason::OpenTwin => write!(f, "Open twin of a secured network"),
+            Reason::DisallowedBand(band) => write!(f, "Not allowed on {}", band),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Authorized => write!(f, "Authorized"),
+            Verdict::Suspicious(reasons) => {
+                let reasons: Vec<String> = reasons.iter().map(|r| r.to_string()).collect();
+                write!(f, "Suspicious: {}", reasons.join("; "))
+            }
+            Verdict::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl RoguePolicy {
+    /// Creates an empty policy
+    pub fn new() -> Self {
+        RoguePolicy::default()
+    }
+
+    /// Adds the policy of an SSID, replacing an existing one for the same SSID
+    pub fn with(mut self, policy: SsidPolicy) -> Self {
+        self.add(policy);
+        self
+    }
+
+    /// Adds the policy of an SSID, replacing an existing one for the same SSID
+    pub fn add(&mut self, policy: SsidPolicy) {
+        self.ssids.retain(|p| p.ssid != policy.ssid);
+        self.ssids.push(policy);
+    }
+
+    /// Returns the policy of an SSID
+    pub fn get(&self, ssid: &str) -> Option<&SsidPolicy> {
+        self.ssids.iter().find(|p| p.ssid == ssid)
+    }
+
+    /// Classifies a single network against the policy.
+    /// Security checks are skipped if the platform doesn't expose the security of networks.
+    pub fn classify(&self, wifi: &Wifi) -> Verdict {
+        let Some(policy) = self.get(&wifi.ssid).filter(|_| !wifi.is_hidden()) else {
+            return Verdict::Unknown;
+        };
+
+        let mut reasons = Vec::new();
+        let mac = parse_mac(&wifi.mac);
+
+        if !policy.permits_bssid(mac) {
+            if !policy.bssids.is_empty() {
+                reasons.push(Reason::UnknownBssid);
+            }
+            if !policy.ouis.is_empty() && !mac.is_some_and(|mac| policy.permits_vendor(mac)) {
+                reasons.push(Reason::UnknownVendor);
+            }
+        }
+
+        let security_known =
+            !wifi.security.is_empty() && !wifi.security.iter().all(|s| *s == WifiSecurity::Unknown);
+        if security_known {
+            if wifi.is_open() && policy.is_secured() {
+                reasons.push(Reason::OpenTwin);
+            } else {
+                let missing: Vec<WifiSecurity> = policy
+                    .required_security
+                    .iter()
+                    .filter(|s| !wifi.security.contains(s))
+                    .cloned()
+                    .collect();
+                if !missing.is_empty() {
+                    reasons.push(Reason::SecurityDowngrade { missing });
+                }
+            }
+        }
+
+        if let Some(band) = wifi.band() {
+            if !policy.bands.is_empty() && !policy.bands.contains(&band) {
+                reasons.push(Reason::DisallowedBand(band));
+            }
+        }
+
+        if reasons.is_empty() {
+            Verdict::Authorized
+        } else {
+            Verdict::Suspicious(reasons)
+        }
+    }
+
+    /// Classifies every network of a scan. In addition to [`RoguePolicy::classify`],
+    /// open networks are flagged as open twins if an authorized access point
+    /// of the same SSID in the scan is secured.
+    pub fn analyze<'a>(&self, wifis: &'a [Wifi]) -> Vec<Assessment<'a>> {
+        let mut assessments: Vec<Assessment> = wifis
+            .iter()
+            .map(|wifi| Assessment {
+                wifi,
+                verdict: self.classify(wifi),
+            })
+            .collect();
+
+        let secured_ssids: Vec<String> = assessments
+            .iter()
+            .filter(|a| a.verdict == Verdict::Authorized && !a.wifi.is_open())
+            .map(|a| a.wifi.ssid.clone())
+            .collect();
+
+        for assessment in &mut assessments {
+            if !assessment.wifi.is_open() || !secured_ssids.contains(&assessment.wifi.ssid) {
+                continue;
+            }
+            match &mut assessment.verdict {
+                Verdict::Suspicious(reasons) if !reasons.contains(&Reason::OpenTwin) => {
+                    reasons.push(Reason::OpenTwin)
+                }
+                Verdict::Suspicious(_) => {}
+                verdict => *verdict = Verdict::Suspicious(vec![Reason::OpenTwin]),
+            }
+        }
+
+        assessments
+    }
+}
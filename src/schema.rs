@@ -0,0 +1,166 @@
+//! Versioned serialization schema, available with the `serde` feature.
+//!
+//! All public data types implement `Serialize` and `Deserialize`. Field names are the
+//! Rust field names and enum variants are serialized by name, externally tagged
+//! (e.g. `"Wpa2PersonalPsk"` or `{"Other": "..."}`). Besides that:
+//!
+//! - raw information elements are serialized as lowercase hex strings
+//! - MAC addresses and OUIs stored as bytes are serialized as colon separated hex strings
+//! - timestamps of exported records are serialized as RFC 3339 strings in UTC
+//! - [`Wifi`](crate::Wifi) fields added after the first schema version
+//!   (`frequency`, `information_elements`, `inferred`) may be omitted and default to
+//!   0, empty and `false`
+//!
+//! Data that is stored or sent over the network should be wrapped in [`Versioned`],
+//! so readers can reject data written with a newer, incompatible schema.
+//!
+//! ```
+//! use wifi_scan::{schema::Versioned, Wifi};
+//!
+//! let wifis = vec![Wifi::default()];
+//! let json = serde_json::to_string(&Versioned::new(wifis.clone())).unwrap();
+//! let read: Versioned<Vec<Wifi>> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(read.into_data().unwrap(), wifis);
+//! ```
+//!
+//! Schema versions:
+//!
+//! Version | Changes
+//! --------|---------------
+//! 1       | Initial schema
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// Current schema version. Incremented on incompatible changes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Data tagged with the schema version it was written with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    pub schema_version: u32,
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Tags data with the current schema version
+    pub fn new(data: T) -> Self {
+        Versioned {
+            schema_version: SCHEMA_VERSION,
+            data,
+        }
+    }
+
+    /// Returns the data if it was written with a supported schema version
+    pub fn into_data(self) -> Result<T> {
+        if self.schema_version == 0 || self.schema_version > SCHEMA_VERSION {
+            return Err(Error::UnsupportedSchema(self.schema_version));
+        }
+        Ok(self.data)
+    }
+}
+
+/// Byte buffers as lowercase hex strings
+pub(crate) mod hex {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::misc::{format_hex, parse_hex};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_hex(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_hex(&text).ok_or_else(|| D::Error::custom("invalid hex string"))
+    }
+}
+
+/// Lists of MAC addresses or OUIs as colon separated hex strings
+pub(crate) mod mac_list {
+    use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    use crate::misc::{format_mac, parse_hex_bytes};
+
+    pub fn serialize<const N: usize, S: Serializer>(
+        list: &[[u8; N]],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(list.len()))?;
+        for bytes in list {
+            seq.serialize_element(&format_mac(bytes))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, const N: usize, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<[u8; N]>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|text| {
+                parse_hex_bytes(text)
+                    .ok_or_else(|| D::Error::custom(format!("invalid address: {}", text)))
+            })
+            .collect()
+    }
+}
+
+/// Maps keyed by MAC addresses or OUIs, with colon separated hex strings as keys
+pub(crate) mod mac_map {
+    use std::collections::HashMap;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::misc::{format_mac, parse_hex_bytes};
+
+    pub fn serialize<const N: usize, V: Serialize, S: Serializer>(
+        map: &HashMap<[u8; N], V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(map.iter().map(|(key, value)| (format_mac(key), value)))
+    }
+
+    pub fn deserialize<'de, const N: usize, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<[u8; N], V>, D::Error> {
+        HashMap::<String, V>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(key, value)| match parse_hex_bytes(&key) {
+                Some(bytes) => Ok((bytes, value)),
+                None => Err(D::Error::custom(format!("invalid address: {}", key))),
+            })
+            .collect()
+    }
+}
+
+/// Optional timestamps as RFC 3339 strings in UTC
+pub(crate) mod timestamp {
+    use std::time::SystemTime;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::misc::{format_rfc3339, parse_rfc3339};
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<SystemTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match time {
+            Some(time) => serializer.serialize_str(&format_rfc3339(*time)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<SystemTime>, D::Error> {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(text) => parse_rfc3339(&text)
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("invalid timestamp: {}", text))),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Platform independent detection of network security from information elements.
+
+use crate::{
+    ie::{self, Elements},
+    WifiSecurity,
+};
+
+const IEEE_OUI: [u8; 3] = [0x00, 0x0f, 0xac];
+const MICROSOFT_OUI: [u8; 3] = [0x00, 0x50, 0xf2];
+const WFA_OUI: [u8; 3] = [0x50, 0x6f, 0x9a];
+/// Vendor type of the OWE Transition Mode element
+const OWE_TRANSITION: u8 = 0x1c;
+
+pub(crate) const CIPHER_WEP40: u8 = 1;
+pub(crate) const CIPHER_TKIP: u8 = 2;
+pub(crate) const CIPHER_CCMP: u8 = 4;
+pub(crate) const CIPHER_WEP104: u8 = 5;
+pub(crate) const AKM_PSK: u8 = 2;
+pub(crate) const AKM_SAE: u8 = 8;
+pub(crate) const AKM_OWE: u8 = 18;
+
+/// Cipher and AKM suites and capabilities of an RSN or WPA element.
+/// Only suites with the element's OUI are listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Rsn {
+    pub group_cipher: Option<u8>,
+    pub pairwise_ciphers: Vec<u8>,
+    pub akms: Vec<u8>,
+    pub mfp_capable: bool,
+    pub mfp_required: bool,
+}
+
+impl Rsn {
+    /// Parses the body of an RSN element, or of a WPA element with the Microsoft OUI.
+    /// Missing trailing fields are left at their defaults.
+    pub fn parse(body: &[u8], oui: [u8; 3]) -> Rsn {
+        let mut rsn = Rsn::default();
+
+        // version (2)
+        let Some(group) = body.get(2..6) else {
+            return rsn;
+        };
+        rsn.group_cipher = suite_type(group, oui);
+
+        let mut offset = 6;
+        let Some(pairwise) = suite_list(body, &mut offset, oui) else {
+            return rsn;
+        };
+        rsn.pairwise_ciphers = pairwise;
+        let Some(akms) = suite_list(body, &mut offset, oui) else {
+            return rsn;
+        };
+        rsn.akms = akms;
+
+        if let Some(capabilities) = body.get(offset..offset + 2) {
+            rsn.mfp_required = capabilities[0] & 0x40 != 0;
+            rsn.mfp_capable = capabilities[0] & 0x80 != 0;
+        }
+
+        rsn
+    }
+}
+
+/// Returns the type of a suite selector if it has the given OUI
+fn suite_type(suite: &[u8], oui: [u8; 3]) -> Option<u8> {
+    if suite[0..3] == oui {
+        Some(suite[3])
+    } else {
+        None
+    }
+}
+
+/// Reads a suite count followed by suite selectors at `offset` and advances it past the
+/// announced suites. A truncated list yields the complete suites present.
+/// Returns `None` if the suite count is missing.
+fn suite_list(body: &[u8], offset: &mut usize, oui: [u8; 3]) -> Option<Vec<u8>> {
+    let count = body.get(*offset..*offset + 2)?;
+    let count = u16::from_le_bytes([count[0], count[1]]) as usize;
+    let suites = body.get(*offset + 2..).unwrap_or_default();
+    *offset += 2 + count * 4;
+
+    Some(
+        suites
+            .chunks_exact(4)
+            .take(count)
+            .filter_map(|s| suite_type(s, oui))
+            .collect(),
+    )
+}
+
+/// Returns the name of a cipher suite type
+pub(crate) fn cipher_name(cipher: u8) -> Option<&'static str> {
+    match cipher {
+        CIPHER_WEP40 | CIPHER_WEP104 => Some("WEP"),
+        CIPHER_TKIP => Some("TKIP"),
+        CIPHER_CCMP => Some("CCMP"),
+        8 => Some("GCMP"),
+        9 => Some("GCMP-256"),
+        10 => Some("CCMP-256"),
+        _ => None,
+    }
+}
+
+/// Returns the parsed RSN element
+pub(crate) fn rsn(ies: &[u8]) -> Option<Rsn> {
+    ie::find(ies, ie::RSN).map(|body| Rsn::parse(body, IEEE_OUI))
+}
+
+/// Returns the parsed WPA (version 1) vendor element
+pub(crate) fn wpa(ies: &[u8]) -> Option<Rsn> {
+    wpa_element(ies).map(|body| Rsn::parse(body, MICROSOFT_OUI))
+}
+
+/// Returns `true` if the elements contain an OWE Transition Mode element
+pub(crate) fn has_owe_transition(ies: &[u8]) -> bool {
+    Elements::new(ies)
+        .filter_map(|e| e.vendor())
+        .any(|(oui, vendor_type, _)| oui == WFA_OUI && vendor_type == OWE_TRANSITION)
+}
+
+/// Returns the securities advertised in the RSN and WPA elements.
+/// `privacy` is the privacy bit of the capability information, used to detect WEP.
+pub(crate) fn from_elements(ies: &[u8], privacy: bool) -> Vec<WifiSecurity> {
+    let mut securities = Vec::new();
+    let mut has_rsn = false;
+
+    if let Some(rsn) = rsn(ies) {
+        has_rsn = true;
+        for suite in rsn.akms {
+            let security = match suite {
+                1 => WifiSecurity::Wpa2EnterpriseEap,
+                2 => WifiSecurity::Wpa2PersonalPsk,
+                3 => WifiSecurity::Wpa2EnterpriseEapFt,
+                4 => WifiSecurity::Wpa2PersonalPskFt,
+                5 => WifiSecurity::Wpa3EnterpriseEap256,
+                6 => WifiSecurity::Wpa2PersonalPsk256,
+                7 => WifiSecurity::TunneledDirectLinkSetup,
+                8 => WifiSecurity::Wpa3PersonalSae,
+                9 => WifiSecurity::Wpa3PersonalSaeFt,
+                11 => WifiSecurity::Wpa3EnterpriseSuiteBEap256,
+                _ => WifiSecurity::Unknown,
+            };
+
+            if security != WifiSecurity::Unknown && !securities.contains(&security) {
+                securities.push(security);
+            }
+        }
+    }
+
+    if let Some(wpa) = wpa(ies) {
+        let suites = wpa.akms;
+        if suites.contains(&1) {
+            securities.push(WifiSecurity::WpaEnterpriseEap);
+        }
+        if suites.contains(&2) || suites.is_empty() {
+            securities.push(WifiSecurity::WpaPersonalPsk);
+        }
+    }
+
+    if securities.is_empty() {
+        if has_rsn {
+            securities.push(WifiSecurity::Unknown);
+        } else if privacy {
+            securities.push(WifiSecurity::Wep);
+        } else {
+            securities.push(WifiSecurity::Open);
+        }
+    }
+
+    securities
+}
+
+/// Builds RSN and WPA elements advertising the securities, the inverse of [`from_elements`].
+/// Open and WEP networks and unknown securities have no elements.
+///
+/// The mapping is lossy where no dedicated AKM suite exists: `Wpa3EnterpriseEap` and
+/// `Wpa2EnterpriseEap256` are advertised as EAP-SHA256 (AKM 5) and read back as
+/// `Wpa3EnterpriseEap256`.
+pub(crate) fn to_elements(securities: &[WifiSecurity]) -> Vec<u8> {
+    let mut akms: Vec<u8> = Vec::new();
+    for security in securities {
+        let akm = match security {
+            WifiSecurity::Wpa2EnterpriseEap => 1,
+            WifiSecurity::Wpa2PersonalPsk => AKM_PSK,
+            WifiSecurity::Wpa2EnterpriseEapFt => 3,
+            WifiSecurity::Wpa2PersonalPskFt => 4,
+            WifiSecurity::Wpa2EnterpriseEap256
+            | WifiSecurity::Wpa3EnterpriseEap256
+            | WifiSecurity::Wpa3EnterpriseEap => 5,
+            WifiSecurity::Wpa2PersonalPsk256 => 6,
+            WifiSecurity::TunneledDirectLinkSetup => 7,
+            WifiSecurity::Wpa3PersonalSae => AKM_SAE,
+            WifiSecurity::Wpa3PersonalSaeFt => 9,
+            WifiSecurity::Wpa3EnterpriseSuiteBEap256 => 11,
+            _ => continue,
+        };
+        if !akms.contains(&akm) {
+            akms.push(akm);
+        }
+    }
+
+    let mut elements = Vec::new();
+    if !akms.is_empty() {
+        // Suite B requires GCMP-256, everything else is advertised with CCMP
+        let cipher = if akms == [11] { 9 } else { CIPHER_CCMP };
+        let mut body = vec![0x01, 0x00];
+        body.extend_from_slice(&IEEE_OUI);
+        body.push(cipher);
+        body.extend_from_slice(&[0x01, 0x00]);
+        body.extend_from_slice(&IEEE_OUI);
+        body.push(cipher);
+        body.extend_from_slice(&(akms.len() as u16).to_le_bytes());
+        for akm in &akms {
+            body.extend_from_slice(&IEEE_OUI);
+            body.push(*akm);
+        }
+        // WPA3 requires management frame protection, SHA-256 AKMs support it
+        let mut capabilities = 0;
+        if akms.iter().any(|a| matches!(a, 5 | 6 | 8 | 9 | 11)) {
+            capabilities |= 0x80;
+        }
+        if akms.iter().all(|a| matches!(a, 8 | 9 | 11)) {
+            capabilities |= 0x40;
+        }
+        body.extend_from_slice(&[capabilities, 0x00]);
+
+        elements.push(ie::RSN);
+        elements.push(body.len() as u8);
+        elements.extend_from_slice(&body);
+    }
+
+    let wpa_akms: Vec<u8> = [
+        (WifiSecurity::WpaEnterpriseEap, 1),
+        (WifiSecurity::WpaPersonalPsk, 2),
+    ]
+    .into_iter()
+    .filter(|(security, _)| securities.contains(security))
+    .map(|(_, akm)| akm)
+    .collect();
+    if !wpa_akms.is_empty() {
+        let mut body = MICROSOFT_OUI.to_vec();
+        body.extend_from_slice(&[0x01, 0x01, 0x00]);
+        body.extend_from_slice(&MICROSOFT_OUI);
+        body.push(CIPHER_TKIP);
+        body.extend_from_slice(&[0x01, 0x00]);
+        body.extend_from_slice(&MICROSOFT_OUI);
+        body.push(CIPHER_TKIP);
+        body.extend_from_slice(&(wpa_akms.len() as u16).to_le_bytes());
+        for akm in wpa_akms {
+            body.extend_from_slice(&MICROSOFT_OUI);
+            body.push(akm);
+        }
+
+        elements.push(ie::VENDOR_SPECIFIC);
+        elements.push(body.len() as u8);
+        elements.extend_from_slice(&body);
+    }
+
+    elements
+}
+
+/// Returns the body of the WPA vendor element without OUI and vendor type
+fn wpa_element(ies: &[u8]) -> Option<&[u8]> {
+    Elements::new(ies)
+        .filter_map(|e| e.vendor())
+        .find(|(oui, vendor_type, _)| *oui == MICROSOFT_OUI && *vendor_type == 0x01)
+        .map(|(_, _, body)| body)
+}
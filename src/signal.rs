@@ -0,0 +1,78 @@
+//! Signal level categorisation and normalisation.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::SignalStrength;
+
+/// Nominal noise floor in dBm used to convert positive, noise relative RSSI values
+pub const NOISE_FLOOR: i32 = -95;
+
+/// Lower bounds in dBm for each `SignalStrength` category.
+/// Signal levels below `fair` are categorised as `Weak`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SignalThresholds {
+    pub excellent: i32,
+    pub good: i32,
+    pub fair: i32,
+}
+
+impl Default for SignalThresholds {
+    fn default() -> Self {
+        SignalThresholds {
+            excellent: -50,
+            good: -70,
+            fair: -80,
+        }
+    }
+}
+
+impl SignalThresholds {
+    /// Categorises a signal level in dBm. 0 is treated as unknown.
+    pub fn classify(&self, dbm: i32) -> SignalStrength {
+        let dbm = normalize_rssi(dbm);
+        if dbm == 0 {
+            SignalStrength::Unknown
+        } else if dbm >= self.excellent {
+            SignalStrength::Excellent
+        } else if dbm >= self.good {
+            SignalStrength::Good
+        } else if dbm >= self.fair {
+            SignalStrength::Fair
+        } else {
+            SignalStrength::Weak
+        }
+    }
+}
+
+/// Normalises RSSI values reported by drivers to dBm.
+/// Some drivers (e.g. on NetBSD) report positive values: values above 127 are treated as
+/// unsigned 8-bit dBm, smaller ones as dB above [`NOISE_FLOOR`]. 0 stays unknown.
+pub fn normalize_rssi(rssi: i32) -> i32 {
+    match rssi {
+        128..=255 => rssi - 256,
+        1..=127 => (NOISE_FLOOR + rssi).min(-1),
+        _ => rssi,
+    }
+}
+
+/// Converts a signal level in dBm to a quality percentage (-100 dBm = 0 %, -50 dBm = 100 %).
+/// Returns `None` if the signal level is unknown.
+pub fn quality(dbm: i32) -> Option<u8> {
+    match normalize_rssi(dbm) {
+        0 => None,
+        dbm => Some((2 * (dbm + 100)).clamp(0, 100) as u8),
+    }
+}
+
+/// Converts a signal strength category into 0 to 4 bars
+pub fn bars(strength: SignalStrength) -> u8 {
+    match strength {
+        SignalStrength::Unknown => 0,
+        SignalStrength::Weak => 1,
+        SignalStrength::Fair => 2,
+        SignalStrength::Good => 3,
+        SignalStrength::Excellent => 4,
+    }
+}
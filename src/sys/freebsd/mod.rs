@@ -28,8 +28,11 @@ impl WlanScanner for ScanFreeBsd {
                     mac: ConstCharArray(network.bssid).into(),
                     ssid: ConstCharArray(network.ssid).into(),
                     channel: network.channel as u32,
+                    frequency: 0,
                     signal_level: network.rssi,
                     security: vec![WifiSecurity::Unknown], // TODO: populate
+                    information_elements: Vec::new(),
+                    inferred: false,
                 })
                 .collect();
 
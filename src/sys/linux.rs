@@ -1,13 +1,28 @@
-use std::{thread::sleep, time::Duration};
+use std::{collections::HashMap, thread::sleep, time::Duration};
 
-use crate::{misc::get_channel, Error, Result, Wifi, WifiSecurity, WlanScanner};
+use crate::{
+    misc::{format_mac, get_channel},
+    security, Error, Result, Wifi, WlanScanner,
+};
 
-use neli_wifi::Socket as SocketN;
-use netlink_rust::{generic, Protocol, Socket};
-use nl80211_rs::{
-    self as nl80211,
-    information_element::{AuthenticationKeyManagement, InformationElement},
+use neli::{
+    attr::Attribute,
+    consts::nl::{NlmF, NlmFFlags, Nlmsg},
+    consts::socket::NlFamily,
+    genl::{Genlmsghdr, Nlattr},
+    nl::{NlPayload, Nlmsghdr},
+    socket::NlSocketHandle,
+    types::GenlBuffer,
 };
+use neli_wifi::{
+    Nl80211Attr, Nl80211Bss, Nl80211Cmd, Socket as SocketN, NL_80211_GENL_NAME,
+    NL_80211_GENL_VERSION,
+};
+use netlink_rust::{generic, Protocol, Socket};
+use nl80211_rs::{self as nl80211, information_element::InformationElement};
+
+/// Privacy bit of the capability information field
+const CAPABILITY_PRIVACY: u16 = 0x0010;
 
 pub struct ScanLinux;
 
@@ -53,13 +68,20 @@ impl WlanScanner for ScanLinux {
             if let Some(index) = interface.index {
                 let mut results: Vec<Wifi> = Vec::new();
                 let bss_list = socket_conn.get_bss_info(index);
+                let privacies = get_privacy(index).unwrap_or_default();
                 if let Ok(bss_list) = bss_list {
                     for bss in bss_list {
                         if let Some(seen) = bss.seen_ms_ago {
                             if seen <= 2500 {
+                                let privacy = bss
+                                    .bssid
+                                    .as_ref()
+                                    .and_then(|bssid| privacies.get(bssid))
+                                    .copied()
+                                    .unwrap_or(false);
                                 results.push(Wifi {
                                     mac: match bss.bssid {
-                                        Some(bytes) => convert_mac(bytes),
+                                        Some(bytes) => format_mac(&bytes),
                                         None => String::new(),
                                     },
                                     ssid: match bss.information_elements.clone() {
@@ -70,14 +92,20 @@ impl WlanScanner for ScanLinux {
                                         Some(frequency) => get_channel(frequency),
                                         None => 0,
                                     },
+                                    frequency: bss.frequency.unwrap_or(0),
                                     signal_level: match bss.signal {
                                         Some(signal) => signal / 100,
                                         None => 0,
                                     },
-                                    security: match bss.information_elements.clone() {
-                                        Some(ie_data) => get_security(ie_data),
+                                    security: match &bss.information_elements {
+                                        Some(ie_data) => security::from_elements(ie_data, privacy),
                                         None => vec![],
                                     },
+                                    information_elements: bss
+                                        .information_elements
+                                        .clone()
+                                        .unwrap_or_default(),
+                                    inferred: false,
                                 });
                             }
                         }
@@ -147,14 +175,6 @@ fn trigger_scan() -> Result<()> {
     }
 }
 
-fn convert_mac(bytes: Vec<u8>) -> String {
-    bytes
-        .iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<Vec<_>>()
-        .join(":")
-}
-
 fn get_ssid(ie_data: Vec<u8>) -> String {
     let ie_data: &[u8] = &ie_data;
     match InformationElement::parse_all(ie_data) {
@@ -171,61 +191,55 @@ fn get_ssid(ie_data: Vec<u8>) -> String {
     String::new()
 }
 
-fn get_security(ie_data: Vec<u8>) -> Vec<WifiSecurity> {
-    let ie_data: &[u8] = &ie_data;
-    match InformationElement::parse_all(ie_data) {
-        Ok(ies) => {
-            for ie in ies {
-                if let InformationElement::RobustSecurityNetwork(sec_ie) = ie {
-                    let mut securities: Vec<WifiSecurity> = Vec::new();
-
-                    for akm in sec_ie.akms {
-                        let security = match akm {
-                            AuthenticationKeyManagement::PreSharedKey => {
-                                WifiSecurity::Wpa2PersonalPsk
-                            }
-                            AuthenticationKeyManagement::SimultaneousAuthenticationOfEquals => {
-                                WifiSecurity::Wpa3PersonalSae
-                            }
-                            AuthenticationKeyManagement::PairwiseMasterKeySecurityAssociation => {
-                                WifiSecurity::Wpa2EnterpriseEap
-                            }
-                            AuthenticationKeyManagement::PMKSASha256 => {
-                                WifiSecurity::Wpa3EnterpriseEap256
-                            }
-                            AuthenticationKeyManagement::FastTransitionPMKSA => {
-                                WifiSecurity::Wpa2EnterpriseEapFt
-                            }
-                            AuthenticationKeyManagement::PreSharedKeySha256 => {
-                                WifiSecurity::Wpa2PersonalPsk256
-                            }
-                            AuthenticationKeyManagement::FastTransitionPreSharedKey => {
-                                WifiSecurity::Wpa2PersonalPskFt
-                            }
-                            AuthenticationKeyManagement::FastTransitionSAE => {
-                                WifiSecurity::Wpa3PersonalSaeFt
-                            }
-                            AuthenticationKeyManagement::TunneledDirectLinkSetup => {
-                                WifiSecurity::TunneledDirectLinkSetup
-                            }
-                            _ => WifiSecurity::Unknown,
-                        };
-
-                        if security != WifiSecurity::Unknown {
-                            securities.push(security);
-                        }
-                    }
-
-                    if securities.is_empty() {
-                        securities.push(WifiSecurity::Unknown);
-                    }
-
-                    return securities;
-                }
-            }
+/// Returns the privacy bit of every BSS known to the interface, keyed by BSSID.
+/// `neli-wifi` does not expose the capability field, so the scan results are dumped again.
+fn get_privacy(index: i32) -> Option<HashMap<Vec<u8>, bool>> {
+    let mut socket = NlSocketHandle::connect(NlFamily::Generic, None, &[]).ok()?;
+    let family_id = socket.resolve_genl_family(NL_80211_GENL_NAME).ok()?;
+
+    let mut attrs = GenlBuffer::new();
+    attrs.push(Nlattr::new(false, false, Nl80211Attr::AttrIfindex, index).ok()?);
+    let request = Nlmsghdr::new(
+        None,
+        family_id,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
+        None,
+        None,
+        NlPayload::Payload(Genlmsghdr::new(
+            Nl80211Cmd::CmdGetScan,
+            NL_80211_GENL_VERSION,
+            attrs,
+        )),
+    );
+    socket.send(request).ok()?;
+
+    let mut privacy = HashMap::new();
+    for response in socket.iter::<Nlmsg, Genlmsghdr<Nl80211Cmd, Nl80211Attr>>(false) {
+        let response = response.ok()?;
+        match response.nl_type {
+            Nlmsg::Done => break,
+            Nlmsg::Error => return None,
+            _ => (),
+        }
+        let Some(payload) = response.nl_payload.get_payload() else {
+            continue;
+        };
+        let handle = payload.get_attr_handle();
+        let Some(bss) = handle.get_attribute(Nl80211Attr::AttrBss) else {
+            continue;
+        };
+        let Ok(bss) = bss.get_attr_handle::<Nl80211Bss>() else {
+            continue;
+        };
+        let bssid = bss
+            .get_attribute(Nl80211Bss::BssBssid)
+            .and_then(|attr| attr.get_payload_as_with_len::<Vec<u8>>().ok());
+        let capability = bss
+            .get_attribute(Nl80211Bss::BssCapability)
+            .and_then(|attr| attr.get_payload_as::<u16>().ok());
+        if let (Some(bssid), Some(capability)) = (bssid, capability) {
+            privacy.insert(bssid, capability & CAPABILITY_PRIVACY != 0);
         }
-        Err(_) => return vec![WifiSecurity::Unknown],
     }
-
-    vec![WifiSecurity::Open]
+    Some(privacy)
 }
@@ -36,8 +36,13 @@ impl WlanScanner for ScanMac {
                                     0u32
                                 }
                             }),
+                            frequency: 0,
                             signal_level: network.rssiValue() as i32,
                             security: get_security(&*network),
+                            information_elements: network
+                                .informationElementData()
+                                .map_or(Vec::new(), |data| data.to_vec()),
+                            inferred: false,
                         });
                     }
                     Ok(results)
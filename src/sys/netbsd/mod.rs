@@ -1,5 +1,6 @@
 use crate::{
     misc::get_channel,
+    signal::normalize_rssi,
     sys::netbsd::lswifi::{free_networks, get_networks, ConstCharArray, NetworkList, ScanResult},
     Error, Result, Wifi, WifiSecurity, WlanScanner,
 };
@@ -29,8 +30,11 @@ impl WlanScanner for ScanNetBsd {
                     mac: ConstCharArray(network.bssid).into(),
                     ssid: ConstCharArray(network.ssid).into(),
                     channel: get_channel(network.freq as u32),
-                    signal_level: network.rssi,
+                    frequency: network.freq as u32,
+                    signal_level: normalize_rssi(network.rssi),
                     security: vec![WifiSecurity::Unknown], // TODO: populate
+                    information_elements: Vec::new(),
+                    inferred: false,
                 })
                 .collect();
 
@@ -23,8 +23,11 @@ impl WlanScanner for ScanWindows {
                         mac: network.bss_id().to_string(),
                         ssid: ssid.to_string(),
                         channel: get_channel(network.ch_center_frequency() / 1000),
+                        frequency: network.ch_center_frequency() / 1000,
                         signal_level: network.rssi(),
                         security: get_security(network.information_frame()),
+                        information_elements: network.information_frame().to_vec(),
+                        inferred: false,
                     })
                 })
                 .collect();
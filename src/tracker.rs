@@ -0,0 +1,276 @@
+//! Tracking of networks across successive scans with smoothed signal levels.
+//!
+//! ```no_run
+//! use wifi_scan::tracker::Tracker;
+//!
+//! let mut tracker = Tracker::new();
+//! for _ in 0..5 {
+//!     tracker.ingest(&wifi_scan::scan().unwrap());
+//! }
+//! for bss in tracker.iter() {
+//!     println!("{} {:.1} dBm ({})", bss.bssid, bss.smoothed().unwrap_or(0.0), bss.trend());
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{misc::parse_mac, Wifi};
+
+/// Default smoothing factor of the exponential moving average
+pub const DEFAULT_ALPHA: f64 = 0.3;
+/// Default number of signal samples kept per BSS
+pub const DEFAULT_HISTORY: usize = 20;
+/// Change of the signal level per scan in dB above which a trend is reported
+const TREND_THRESHOLD: f64 = 0.5;
+
+/// Direction the signal level of a BSS is moving in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Trend {
+    Rising,
+    Falling,
+    Stable,
+    /// Not enough samples to tell
+    Unknown,
+}
+
+/// Signal history of a single BSS
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BssHistory {
+    /// BSSID of the network
+    pub bssid: String,
+    /// Last seen SSID of the network
+    pub ssid: String,
+    /// Last seen scan result of the network
+    pub last: Wifi,
+    /// Time the network was first seen
+    pub first_seen: SystemTime,
+    /// Time the network was last seen
+    pub last_seen: SystemTime,
+    /// Number of scans the network was seen in
+    pub seen_count: usize,
+    samples: VecDeque<(SystemTime, i32)>,
+    ema: Option<f64>,
+}
+
+/// Keeps per BSSID history over successive scan results
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tracker {
+    alpha: f64,
+    history: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::schema::mac_map"))]
+    entries: HashMap<[u8; 6], BssHistory>,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Trend::Rising => write!(f, "Rising"),
+            Trend::Falling => write!(f, "Falling"),
+            Trend::Stable => write!(f, "Stable"),
+            Trend::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl BssHistory {
+    fn new(wifi: &Wifi, time: SystemTime) -> Self {
+        BssHistory {
+            bssid: wifi.mac.clone(),
+            ssid: wifi.ssid.clone(),
+            last: wifi.clone(),
+            first_seen: time,
+            last_seen: time,
+            seen_count: 0,
+            samples: VecDeque::new(),
+            ema: None,
+        }
+    }
+
+    /// Returns the recorded signal levels in dBm with their timestamps, oldest first
+    pub fn samples(&self) -> impl Iterator<Item = (SystemTime, i32)> + '_ {
+        self.samples.iter().copied()
+    }
+
+    /// Returns the exponential moving average of the signal level in dBm
+    pub fn smoothed(&self) -> Option<f64> {
+        self.ema
+    }
+
+    /// Returns the median of the recorded signal levels in dBm
+    pub fn median(&self) -> Option<f64> {
+        let mut levels: Vec<i32> = self.samples.iter().map(|(_, s)| *s).collect();
+        if levels.is_empty() {
+            return None;
+        }
+        levels.sort_unstable();
+
+        let mid = levels.len() / 2;
+        if levels.len() % 2 == 0 {
+            Some((levels[mid - 1] + levels[mid]) as f64 / 2.0)
+        } else {
+            Some(levels[mid] as f64)
+        }
+    }
+
+    /// Returns the mean of the recorded signal levels in dBm
+    pub fn mean(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.samples.iter().map(|(_, s)| *s as f64).sum();
+        Some(sum / self.samples.len() as f64)
+    }
+
+    /// Returns the population variance of the recorded signal levels in dB²
+    pub fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let sum: f64 = self
+            .samples
+            .iter()
+            .map(|(_, s)| (*s as f64 - mean).powi(2))
+            .sum();
+        Some(sum / self.samples.len() as f64)
+    }
+
+    /// Returns the weakest recorded signal level in dBm
+    pub fn min(&self) -> Option<i32> {
+        self.samples.iter().map(|(_, s)| *s).min()
+    }
+
+    /// Returns the strongest recorded signal level in dBm
+    pub fn max(&self) -> Option<i32> {
+        self.samples.iter().map(|(_, s)| *s).max()
+    }
+
+    /// Returns the trend of the signal level based on the slope of the recorded samples
+    pub fn trend(&self) -> Trend {
+        let n = self.samples.len();
+        if n < 3 {
+            return Trend::Unknown;
+        }
+
+        let mean_x = (n - 1) as f64 / 2.0;
+        let mean_y = self.mean().unwrap_or_default();
+        let (mut num, mut den) = (0.0, 0.0);
+        for (i, (_, s)) in self.samples.iter().enumerate() {
+            let dx = i as f64 - mean_x;
+            num += dx * (*s as f64 - mean_y);
+            den += dx * dx;
+        }
+
+        let slope = num / den;
+        if slope > TREND_THRESHOLD {
+            Trend::Rising
+        } else if slope < -TREND_THRESHOLD {
+            Trend::Falling
+        } else {
+            Trend::Stable
+        }
+    }
+
+    fn push(&mut self, wifi: &Wifi, time: SystemTime, alpha: f64, history: usize) {
+        self.ssid = wifi.ssid.clone();
+        self.last = wifi.clone();
+        self.last_seen = time;
+        self.seen_count += 1;
+
+        // signal level 0 means unknown and is not recorded
+        if wifi.signal_level == 0 {
+            return;
+        }
+        let level = wifi.signal_level as f64;
+        self.ema = Some(match self.ema {
+            Some(ema) => alpha * level + (1.0 - alpha) * ema,
+            None => level,
+        });
+
+        self.samples.push_back((time, wifi.signal_level));
+        while self.samples.len() > history {
+            self.samples.pop_front();
+        }
+    }
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Tracker::new()
+    }
+}
+
+impl Tracker {
+    /// Creates a tracker using [`DEFAULT_ALPHA`] and [`DEFAULT_HISTORY`]
+    pub fn new() -> Self {
+        Tracker::with_settings(DEFAULT_ALPHA, DEFAULT_HISTORY)
+    }
+
+    /// Creates a tracker with a custom smoothing factor (clamped to 0.0..=1.0, higher values
+    /// follow changes faster) and number of samples kept per BSS (at least 1)
+    pub fn with_settings(alpha: f64, history: usize) -> Self {
+        Tracker {
+            alpha: alpha.clamp(0.0, 1.0),
+            history: history.max(1),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Adds the results of a scan, timestamped with the current time
+    pub fn ingest(&mut self, wifis: &[Wifi]) {
+        self.ingest_at(wifis, SystemTime::now());
+    }
+
+    /// Adds the results of a scan taken at `time`. Networks without a valid BSSID are skipped.
+    pub fn ingest_at(&mut self, wifis: &[Wifi], time: SystemTime) {
+        for wifi in wifis {
+            let Some(key) = parse_mac(&wifi.mac) else {
+                continue;
+            };
+            self.entries
+                .entry(key)
+                .or_insert_with(|| BssHistory::new(wifi, time))
+                .push(wifi, time, self.alpha, self.history);
+        }
+    }
+
+    /// Returns the history of a BSSID
+    pub fn get(&self, bssid: &str) -> Option<&BssHistory> {
+        self.entries.get(&parse_mac(bssid)?)
+    }
+
+    /// Returns the history of all tracked networks
+    pub fn iter(&self) -> impl Iterator<Item = &BssHistory> {
+        self.entries.values()
+    }
+
+    /// Returns the number of tracked networks
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no networks are tracked
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes networks that haven't been seen for longer than `max_age` before `now`
+    pub fn prune(&mut self, max_age: Duration, now: SystemTime) {
+        self.entries.retain(|_, bss| {
+            now.duration_since(bss.last_seen)
+                .map_or(true, |age| age <= max_age)
+        });
+    }
+
+    /// Removes all tracked networks
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
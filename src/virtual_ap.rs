@@ -0,0 +1,124 @@
+//! Grouping of virtual access points (multiple BSSIDs) into physical radios.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    misc::{format_mac, parse_mac},
+    Wifi,
+};
+
+/// Number of low BSSID bits that may differ between virtual APs of one radio by default
+pub const DEFAULT_LOW_BITS: u8 = 4;
+
+/// A physical radio advertising one or more BSSIDs
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicalAp {
+    /// Base MAC address of the radio (the lowest globally administered BSSID if any)
+    pub base_mac: String,
+    /// Channel the radio is on. Returns 0 if unknown.
+    pub channel: u32,
+    /// All networks advertised by this radio
+    pub bsses: Vec<Wifi>,
+}
+
+impl PhysicalAp {
+    /// Returns the SSIDs advertised by this radio, without duplicates and hidden networks
+    pub fn ssids(&self) -> Vec<String> {
+        let mut ssids: Vec<String> = Vec::new();
+        for bss in &self.bsses {
+            if !bss.is_hidden() && !ssids.contains(&bss.ssid) {
+                ssids.push(bss.ssid.clone());
+            }
+        }
+        ssids
+    }
+
+    /// Returns `true` if the radio advertises more than one BSSID
+    pub fn is_multi_bssid(&self) -> bool {
+        self.bsses.len() > 1
+    }
+
+    /// Returns `true` if any BSSID of the radio is locally administered
+    pub fn has_locally_administered(&self) -> bool {
+        self.bsses.iter().any(|b| b.is_locally_administered())
+    }
+}
+
+/// Groups scan results into physical radios, see [`group_physical_aps_with`].
+/// Uses [`DEFAULT_LOW_BITS`].
+pub fn group_physical_aps(wifis: &[Wifi]) -> Vec<PhysicalAp> {
+    group_physical_aps_with(wifis, DEFAULT_LOW_BITS)
+}
+
+/// Groups scan results into physical radios.
+/// BSSIDs on the same channel are grouped if they only differ in the lowest `low_bits` bits
+/// or in the locally administered bits of the first byte.
+/// Networks with an invalid BSSID are returned as radios of their own.
+pub fn group_physical_aps_with(wifis: &[Wifi], low_bits: u8) -> Vec<PhysicalAp> {
+    let mut groups: Vec<(Option<[u8; 6]>, PhysicalAp)> = Vec::new();
+
+    for wifi in wifis {
+        let key = parse_mac(&wifi.mac).map(|mac| radio_key(mac, low_bits));
+        let existing = groups.iter_mut().find(|(k, ap)| {
+            key.is_some()
+                && *k == key
+                && (ap.channel == wifi.channel || ap.channel == 0 || wifi.channel == 0)
+        });
+
+        match existing {
+            Some((_, ap)) => {
+                if ap.channel == 0 {
+                    ap.channel = wifi.channel;
+                }
+                ap.bsses.push(wifi.clone());
+            }
+            None => groups.push((
+                key,
+                PhysicalAp {
+                    base_mac: String::new(),
+                    channel: wifi.channel,
+                    bsses: vec![wifi.clone()],
+                },
+            )),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, mut ap)| {
+            ap.base_mac = base_mac(&ap.bsses);
+            ap
+        })
+        .collect()
+}
+
+/// Masks the bits virtual APs commonly vary in: bits 1-3 of the first byte
+/// (locally administered variants) and the lowest `low_bits` bits of the address
+fn radio_key(mut mac: [u8; 6], low_bits: u8) -> [u8; 6] {
+    mac[0] &= 0xf1;
+
+    let mut remaining = low_bits.min(48);
+    for byte in mac.iter_mut().rev() {
+        if remaining == 0 {
+            break;
+        }
+        let bits = remaining.min(8);
+        *byte &= !(((1u16 << bits) - 1) as u8);
+        remaining -= bits;
+    }
+
+    mac
+}
+
+/// Returns the lowest globally administered BSSID, or the lowest BSSID if all are local
+fn base_mac(bsses: &[Wifi]) -> String {
+    let macs: Vec<[u8; 6]> = bsses.iter().filter_map(|b| parse_mac(&b.mac)).collect();
+    let global = macs.iter().filter(|m| m[0] & 0x02 == 0).min();
+
+    match global.or_else(|| macs.iter().min()) {
+        Some(mac) => format_mac(mac),
+        None => bsses.first().map(|b| b.mac.clone()).unwrap_or_default(),
+    }
+}
@@ -0,0 +1,197 @@
+//! Decoding of the Wi-Fi Protected Setup (WPS) information element.
+
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ie::Elements;
+
+const WPS_OUI: [u8; 3] = [0x00, 0x50, 0xf2];
+const WPS_VENDOR_TYPE: u8 = 0x04;
+
+const ATTR_CONFIG_METHODS: u16 = 0x1008;
+const ATTR_MANUFACTURER: u16 = 0x1021;
+const ATTR_MODEL_NAME: u16 = 0x1023;
+const ATTR_MODEL_NUMBER: u16 = 0x1024;
+const ATTR_DEVICE_NAME: u16 = 0x1011;
+const ATTR_SELECTED_REGISTRAR: u16 = 0x1041;
+const ATTR_SERIAL_NUMBER: u16 = 0x1042;
+const ATTR_WPS_STATE: u16 = 0x1044;
+const ATTR_UUID_E: u16 = 0x1047;
+const ATTR_SELECTED_REGISTRAR_CONFIG_METHODS: u16 = 0x1053;
+const ATTR_AP_SETUP_LOCKED: u16 = 0x1057;
+
+/// Config method flags of the WPS Config Methods attribute
+pub mod config_methods {
+    pub const USBA: u16 = 0x0001;
+    pub const ETHERNET: u16 = 0x0002;
+    pub const LABEL: u16 = 0x0004;
+    pub const DISPLAY: u16 = 0x0008;
+    pub const EXTERNAL_NFC_TOKEN: u16 = 0x0010;
+    pub const INTEGRATED_NFC_TOKEN: u16 = 0x0020;
+    pub const NFC_INTERFACE: u16 = 0x0040;
+    pub const PUSH_BUTTON: u16 = 0x0080;
+    pub const KEYPAD: u16 = 0x0100;
+    pub const VIRTUAL_PUSH_BUTTON: u16 = 0x0280;
+    pub const PHYSICAL_PUSH_BUTTON: u16 = 0x0480;
+    pub const VIRTUAL_DISPLAY_PIN: u16 = 0x2008;
+    pub const PHYSICAL_DISPLAY_PIN: u16 = 0x4008;
+}
+
+/// Wi-Fi Protected Setup state of an access point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WpsState {
+    Unconfigured,
+    Configured,
+    #[default]
+    Unknown,
+}
+
+/// Information from the Wi-Fi Protected Setup element
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WpsInfo {
+    /// WPS state of the access point
+    pub state: WpsState,
+    /// `true` if the access point has locked WPS setup, e.g. after repeated PIN failures
+    pub ap_setup_locked: bool,
+    /// `true` if a registrar is currently active (e.g. the push button was pressed)
+    pub selected_registrar: bool,
+    /// Device name. Empty if not advertised.
+    pub device_name: String,
+    /// Manufacturer. Empty if not advertised.
+    pub manufacturer: String,
+    /// Model name. Empty if not advertised.
+    pub model: String,
+    /// Model number. Empty if not advertised.
+    pub model_number: String,
+    /// Serial number. Empty if not advertised.
+    pub serial_number: String,
+    /// UUID of the device. Empty if not advertised.
+    pub uuid: String,
+    /// Supported config methods, see [`config_methods`]. `None` if not advertised.
+    pub config_methods: Option<u16>,
+}
+
+impl fmt::Display for WpsState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WpsState::Unconfigured => write!(f, "Unconfigured"),
+            WpsState::Configured => write!(f, "Configured"),
+            WpsState::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl WpsInfo {
+    /// Parses the WPS element from raw information elements.
+    /// Returns `None` if the network does not advertise WPS.
+    pub fn from_elements(ies: &[u8]) -> Option<WpsInfo> {
+        // the WPS attributes may be split over several vendor elements
+        let mut attributes = Vec::new();
+        let mut found = false;
+        for (oui, vendor_type, body) in Elements::new(ies).filter_map(|e| e.vendor()) {
+            if oui == WPS_OUI && vendor_type == WPS_VENDOR_TYPE {
+                attributes.extend_from_slice(body);
+                found = true;
+            }
+        }
+        if !found {
+            return None;
+        }
+
+        let mut info = WpsInfo::default();
+        let mut selected_registrar_methods = None;
+        let mut data = &attributes[..];
+
+        while data.len() >= 4 {
+            let attribute = u16::from_be_bytes([data[0], data[1]]);
+            let length = u16::from_be_bytes([data[2], data[3]]) as usize;
+            let Some(value) = data.get(4..4 + length) else {
+                break;
+            };
+
+            match attribute {
+                ATTR_WPS_STATE => {
+                    info.state = match value.first() {
+                        Some(1) => WpsState::Unconfigured,
+                        Some(2) => WpsState::Configured,
+                        _ => WpsState::Unknown,
+                    }
+                }
+                ATTR_AP_SETUP_LOCKED => info.ap_setup_locked = value.first() == Some(&1),
+                ATTR_SELECTED_REGISTRAR => info.selected_registrar = value.first() == Some(&1),
+                ATTR_DEVICE_NAME => info.device_name = attribute_string(value),
+                ATTR_MANUFACTURER => info.manufacturer = attribute_string(value),
+                ATTR_MODEL_NAME => info.model = attribute_string(value),
+                ATTR_MODEL_NUMBER => info.model_number = attribute_string(value),
+                ATTR_SERIAL_NUMBER => info.serial_number = attribute_string(value),
+                ATTR_UUID_E => info.uuid = format_uuid(value),
+                ATTR_CONFIG_METHODS if value.len() >= 2 => {
+                    info.config_methods = Some(u16::from_be_bytes([value[0], value[1]]))
+                }
+                ATTR_SELECTED_REGISTRAR_CONFIG_METHODS if value.len() >= 2 => {
+                    selected_registrar_methods = Some(u16::from_be_bytes([value[0], value[1]]))
+                }
+                _ => {}
+            }
+
+            data = &data[4 + length..];
+        }
+
+        if info.config_methods.is_none() {
+            info.config_methods = selected_registrar_methods;
+        }
+
+        Some(info)
+    }
+
+    /// Returns `true` if the access point supports the given config method flags
+    pub fn supports(&self, method: u16) -> bool {
+        self.config_methods.is_some_and(|m| m & method == method)
+    }
+
+    /// Returns `true` if WPS PIN setup is likely available.
+    /// Access points that don't advertise their config methods are assumed to accept PINs,
+    /// as the AP PIN is mandatory for WPS access points.
+    pub fn is_pin_enabled(&self) -> bool {
+        if self.ap_setup_locked {
+            return false;
+        }
+
+        match self.config_methods {
+            Some(methods) => {
+                methods & (config_methods::LABEL | config_methods::DISPLAY | config_methods::KEYPAD)
+                    != 0
+            }
+            None => true,
+        }
+    }
+}
+
+/// Converts a WPS string attribute, dropping trailing NUL bytes
+fn attribute_string(value: &[u8]) -> String {
+    String::from_utf8_lossy(value)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string()
+}
+
+/// Formats a 16 byte UUID as `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`
+fn format_uuid(value: &[u8]) -> String {
+    if value.len() != 16 {
+        return String::new();
+    }
+
+    let hex: String = value.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
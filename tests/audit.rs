@@ -0,0 +1,212 @@
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{
+    audit::{audit, FindingKind, Severity},
+    Wifi, WifiSecurity,
+};
+
+const BSSID: &str = "00:11:22:33:44:55";
+
+/// RSN element with CCMP as group cipher
+fn rsn(pairwise: u8, akms: &[u8], capabilities: &[u8]) -> Vec<u8> {
+    let mut body = vec![0x01, 0x00, 0x00, 0x0f, 0xac, 0x04];
+    body.extend_from_slice(&[0x01, 0x00, 0x00, 0x0f, 0xac, pairwise]);
+    body.extend_from_slice(&[akms.len() as u8, 0x00]);
+    for akm in akms {
+        body.extend_from_slice(&[0x00, 0x0f, 0xac, *akm]);
+    }
+    body.extend_from_slice(capabilities);
+    element(48, &body)
+}
+
+/// WPS element with the given state, AP setup lock and config methods
+fn wps(state: u8, locked: bool, methods: u16) -> Vec<u8> {
+    let mut body = vec![0x00, 0x50, 0xf2, 0x04];
+    body.extend_from_slice(&[0x10, 0x44, 0x00, 0x01, state]);
+    body.extend_from_slice(&[0x10, 0x57, 0x00, 0x01, locked as u8]);
+    body.extend_from_slice(&[0x10, 0x08, 0x00, 0x02]);
+    body.extend_from_slice(&methods.to_be_bytes());
+    element(221, &body)
+}
+
+fn kinds(wifi: &Wifi) -> Vec<FindingKind> {
+    wifi.audit().into_iter().map(|f| f.kind).collect()
+}
+
+#[test]
+fn legacy_security() {
+    let wep = wifi(BSSID).ssid("Old").security(&[WifiSecurity::Wep]);
+    assert_eq!(kinds(&wep.build()), vec![FindingKind::Wep]);
+
+    // WPA with TKIP next to WPA2
+    let mut wpa = vec![0x00, 0x50, 0xf2, 0x01, 0x01, 0x00, 0x00, 0x50, 0xf2, 0x02];
+    wpa.extend_from_slice(&[0x01, 0x00, 0x00, 0x50, 0xf2, 0x02]);
+    wpa.extend_from_slice(&[0x01, 0x00, 0x00, 0x50, 0xf2, 0x02]);
+    let mut ies = rsn(0x04, &[2], &[]);
+    ies.extend(element(221, &wpa));
+    let mixed = wifi(BSSID)
+        .ssid("Mixed")
+        .security(&[WifiSecurity::WpaPersonalPsk, WifiSecurity::Wpa2PersonalPsk])
+        .elements(ies)
+        .build();
+    assert_eq!(
+        kinds(&mixed),
+        vec![
+            FindingKind::Tkip,
+            FindingKind::Wpa1,
+            FindingKind::TransitionMode
+        ]
+    );
+
+    // TKIP as RSN pairwise cipher
+    let tkip = wifi(BSSID)
+        .ssid("Tkip")
+        .security(&[WifiSecurity::Wpa2PersonalPsk])
+        .elements(rsn(0x02, &[2], &[]))
+        .build();
+    assert_eq!(kinds(&tkip), vec![FindingKind::Tkip]);
+}
+
+#[test]
+fn open_networks() {
+    let cafe = || wifi(BSSID).ssid("Cafe").security(&[WifiSecurity::Open]);
+    assert_eq!(kinds(&cafe().build()), vec![FindingKind::OpenWithoutOwe]);
+
+    // OWE Transition Mode element
+    let transition = element(221, &[0x50, 0x6f, 0x9a, 0x1c, 0x00]);
+    assert!(kinds(&cafe().elements(transition).build()).is_empty());
+
+    // reported as open by platforms that only look for an RSN element
+    let mut wpa = vec![0x00, 0x50, 0xf2, 0x01, 0x01, 0x00, 0x00, 0x50, 0xf2, 0x02];
+    wpa.extend_from_slice(&[0x01, 0x00, 0x00, 0x50, 0xf2, 0x02]);
+    wpa.extend_from_slice(&[0x01, 0x00, 0x00, 0x50, 0xf2, 0x02]);
+    let wpa = cafe().elements(element(221, &wpa)).build();
+    assert_eq!(kinds(&wpa), vec![FindingKind::Tkip, FindingKind::Wpa1]);
+
+    // hidden networks are only informational
+    let hidden = wifi(BSSID)
+        .security(&[WifiSecurity::Wpa2PersonalPsk])
+        .build();
+    assert_eq!(kinds(&hidden), vec![FindingKind::HiddenSsid]);
+    assert_eq!(hidden.audit()[0].severity, Severity::Info);
+}
+
+#[test]
+fn protected_management_frames() {
+    let sae = |capabilities: &[u8]| {
+        wifi(BSSID)
+            .ssid("Home")
+            .security(&[WifiSecurity::Wpa3PersonalSae])
+            .elements(rsn(0x04, &[8], capabilities))
+            .build()
+    };
+    // neither capable nor required
+    assert_eq!(
+        kinds(&sae(&[0x00, 0x00])),
+        vec![FindingKind::Wpa3WithoutPmf]
+    );
+    // capable only is not enough for WPA3-only networks
+    assert_eq!(
+        kinds(&sae(&[0x80, 0x00])),
+        vec![FindingKind::Wpa3WithoutPmf]
+    );
+    assert!(kinds(&sae(&[0xc0, 0x00])).is_empty());
+
+    // transition mode only has to be capable
+    let transition = wifi(BSSID)
+        .ssid("Home")
+        .security(&[WifiSecurity::Wpa2PersonalPsk, WifiSecurity::Wpa3PersonalSae])
+        .elements(rsn(0x04, &[2, 8], &[0x80, 0x00]))
+        .build();
+    assert_eq!(kinds(&transition), vec![FindingKind::TransitionMode]);
+
+    // AKM list announcing two suites with only OWE present
+    let mut truncated = rsn(0x04, &[18], &[]);
+    truncated[15] = 2;
+    let owe = wifi(BSSID)
+        .ssid("Cafe")
+        .security(&[WifiSecurity::Open])
+        .elements(truncated)
+        .build();
+    assert_eq!(kinds(&owe), vec![FindingKind::Wpa3WithoutPmf]);
+}
+
+#[test]
+fn enterprise_sha256() {
+    let enterprise = |akms: &[u8], capabilities: &[u8]| {
+        wifi(BSSID)
+            .ssid("Corp")
+            .security(&[
+                WifiSecurity::Wpa2EnterpriseEap,
+                WifiSecurity::Wpa3EnterpriseEap256,
+            ])
+            .elements(rsn(0x04, akms, capabilities))
+            .build()
+    };
+    // WPA2-Enterprise with EAP and EAP-SHA256 is no WPA2/WPA3 transition
+    assert!(kinds(&enterprise(&[1, 5], &[0x80, 0x00])).is_empty());
+    // EAP-SHA256 with optional PMF is WPA2-Enterprise
+    assert!(kinds(&enterprise(&[5], &[0x80, 0x00])).is_empty());
+    // and WPA3-Enterprise once PMF is required
+    assert!(kinds(&enterprise(&[5], &[0xc0, 0x00])).is_empty());
+}
+
+#[test]
+fn wps_pin() {
+    let home = |wps: Vec<u8>| {
+        wifi(BSSID)
+            .ssid("Home")
+            .security(&[WifiSecurity::Wpa2PersonalPsk])
+            .elements(wps)
+            .build()
+    };
+    // configured with label PIN
+    let network = home(wps(0x02, false, 0x0004));
+    assert_eq!(kinds(&network), vec![FindingKind::WpsEnabled]);
+    assert_eq!(network.audit()[0].severity, Severity::Medium);
+
+    // locked, push button only or unconfigured
+    assert!(kinds(&home(wps(0x02, true, 0x0004))).is_empty());
+    assert!(kinds(&home(wps(0x02, false, 0x0080))).is_empty());
+    assert!(kinds(&home(wps(0x01, false, 0x0004))).is_empty());
+}
+
+#[test]
+fn report() {
+    let network =
+        |ssid: &str, security: WifiSecurity| wifi(BSSID).ssid(ssid).security(&[security]).build();
+    let wifis = vec![
+        network("Old", WifiSecurity::Wep),
+        network("Cafe", WifiSecurity::Open),
+        network("Lobby", WifiSecurity::Open),
+        network("", WifiSecurity::Wpa2PersonalPsk),
+        network("Home", WifiSecurity::Wpa2PersonalPsk),
+    ];
+
+    let report = audit(&wifis);
+    assert_eq!(report.networks.len(), 5);
+    assert_eq!(report.networks[0].ssid, "Old");
+    assert_eq!(report.networks[0].bssid, "00:11:22:33:44:55");
+    assert_eq!(report.networks[0].max_severity(), Some(Severity::Critical));
+    assert_eq!(report.networks[4].max_severity(), None);
+
+    let summary = &report.summary;
+    assert_eq!((summary.networks, summary.networks_at_risk), (5, 3));
+    assert_eq!(
+        summary.by_kind,
+        vec![
+            (FindingKind::OpenWithoutOwe, 2),
+            (FindingKind::Wep, 1),
+            (FindingKind::HiddenSsid, 1)
+        ]
+    );
+    assert_eq!(
+        summary.to_string(),
+        "5 networks, 3 at risk | Critical: 1 | High: 0 | Medium: 2 | Low: 0 | Info: 1"
+    );
+    assert_eq!(
+        report.networks[0].findings[0].to_string(),
+        "[Critical] WEP: WEP encryption is broken and can be cracked within minutes"
+    );
+}
@@ -0,0 +1,62 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use wifi_scan::{Wifi, WifiSecurity};
+
+/// Encodes an information element
+pub fn element(id: u8, data: &[u8]) -> Vec<u8> {
+    let mut element = vec![id, data.len() as u8];
+    element.extend_from_slice(data);
+    element
+}
+
+/// Starts building a network with the given BSSID
+pub fn wifi(mac: &str) -> WifiBuilder {
+    let mut wifi = Wifi::default();
+    wifi.mac = mac.to_string();
+    WifiBuilder(wifi)
+}
+
+/// Builder for networks as returned by a scan
+pub struct WifiBuilder(Wifi);
+
+impl WifiBuilder {
+    pub fn ssid(mut self, ssid: &str) -> Self {
+        self.0.ssid = ssid.to_string();
+        self
+    }
+
+    pub fn channel(mut self, channel: u32) -> Self {
+        self.0.channel = channel;
+        self
+    }
+
+    pub fn frequency(mut self, frequency: u32) -> Self {
+        self.0.frequency = frequency;
+        self
+    }
+
+    pub fn signal(mut self, signal_level: i32) -> Self {
+        self.0.signal_level = signal_level;
+        self
+    }
+
+    pub fn security(mut self, security: &[WifiSecurity]) -> Self {
+        self.0.security = security.to_vec();
+        self
+    }
+
+    pub fn elements(mut self, elements: Vec<u8>) -> Self {
+        self.0.set_information_elements(elements);
+        self
+    }
+
+    pub fn inferred(mut self) -> Self {
+        self.0.inferred = true;
+        self
+    }
+
+    pub fn build(self) -> Wifi {
+        self.0
+    }
+}
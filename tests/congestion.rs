@@ -0,0 +1,153 @@
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{congestion::ChannelAnalyzer, Band};
+
+/// BSS Load element with the medium busy all the time
+fn fully_loaded() -> Vec<u8> {
+    element(11, &[10, 0, 255, 0, 0])
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn bss_load() {
+    let load = wifi("00:00:00:00:00:01")
+        .elements(element(11, &[3, 1, 128, 0x10, 0x00]))
+        .build()
+        .bss_load()
+        .unwrap();
+    assert_eq!(load.station_count, 259);
+    assert_eq!(load.channel_utilization, 128);
+    assert_eq!(load.available_admission_capacity, 16);
+    assert_close(load.utilization_percent(), 12800.0 / 255.0);
+
+    let truncated = element(11, &[3, 0, 128]);
+    let wifi = wifi("00:00:00:00:00:01").elements(truncated).build();
+    assert_eq!(wifi.bss_load(), None);
+}
+
+#[test]
+fn overlap_on_2ghz() {
+    // -50 dBm is full quality, unknown utilisation counts half
+    let wifis = vec![wifi("00:00:00:00:00:01").channel(1).signal(-50).build()];
+    let loads = ChannelAnalyzer::new(&wifis).loads(Band::TwoPointFourGhz, 20);
+    assert_eq!(loads.len(), 13);
+
+    // 20 MHz channels on 2.4 GHz occupy 22 MHz
+    assert_close(loads[0].load, 0.5);
+    assert_close(loads[2].load, 0.5 * 12.0 / 22.0);
+    assert_eq!(loads[4].bss_count, 1);
+    assert_close(loads[4].load, 0.5 * 2.0 / 22.0);
+    assert_eq!((loads[5].bss_count, loads[5].load), (0, 0.0));
+    assert_eq!(loads[5].center_frequency, 2437);
+    assert_eq!(loads[5].channels, vec![6]);
+}
+
+#[test]
+fn recommend_2ghz() {
+    let wifis = vec![
+        wifi("00:00:00:00:00:01").channel(1).signal(-50).build(),
+        wifi("00:00:00:00:00:06")
+            .channel(6)
+            .signal(-60)
+            .elements(fully_loaded())
+            .build(),
+    ];
+    let analyzer = ChannelAnalyzer::new(&wifis);
+    let best = analyzer.recommend(Band::TwoPointFourGhz, 20).unwrap();
+    assert_eq!(best.channel, 11);
+    assert_eq!(
+        best.to_string(),
+        "2.4 GHz channel 11 @ 20 MHz: load 0.00 from 0 networks"
+    );
+
+    // 40 MHz network on channels 6 and 10
+    let wifis = vec![wifi("00:00:00:00:00:06")
+        .channel(6)
+        .signal(-50)
+        .elements(element(61, &[6, 0x05, 0, 0, 0]))
+        .build()];
+    let loads = ChannelAnalyzer::new(&wifis).loads(Band::TwoPointFourGhz, 40);
+    assert_eq!(loads.len(), 9);
+    assert_eq!(loads[5].channels, vec![6, 10]);
+    assert_eq!(loads[5].center_frequency, 2447);
+    assert_close(loads[5].load, 0.5);
+    // channels 1 and 5 overlap the lower 15 MHz
+    assert_close(loads[0].load, 0.5 * 15.0 / 40.0);
+}
+
+#[test]
+fn wide_channels_on_5ghz() {
+    // 80 MHz network with primary channel 44, fully loaded at full quality
+    let mut ies = fully_loaded();
+    ies.extend(element(192, &[1, 42, 0]));
+    let wifis = vec![wifi("00:00:00:00:00:2c")
+        .channel(44)
+        .signal(-40)
+        .elements(ies)
+        .build()];
+    let analyzer = ChannelAnalyzer::new(&wifis);
+
+    let loads = analyzer.loads(Band::FiveGhz, 80);
+    let starts: Vec<u32> = loads.iter().map(|l| l.channel).collect();
+    assert_eq!(starts, vec![36, 52, 100, 116, 132, 149, 165]);
+    assert_eq!(loads[0].channels, vec![36, 40, 44, 48]);
+    assert_eq!(loads[0].center_frequency, 5210);
+    assert_close(loads[0].load, 1.0);
+    assert!(!loads[0].dfs && loads[1].dfs && !loads[5].dfs);
+
+    // a quarter of the network overlaps channel 48
+    let loads = analyzer.loads(Band::FiveGhz, 20);
+    assert_close(loads[3].load, 0.25);
+    assert_eq!(loads[4].bss_count, 0);
+
+    let best = analyzer.recommend(Band::FiveGhz, 80).unwrap();
+    assert_eq!((best.channel, best.dfs), (52, true));
+    assert!(best.to_string().ends_with("(DFS)"));
+    let best = analyzer
+        .exclude_dfs(true)
+        .recommend(Band::FiveGhz, 80)
+        .unwrap();
+    assert_eq!(best.channel, 149);
+}
+
+#[test]
+fn supported_widths() {
+    let analyzer = ChannelAnalyzer::new(&[]);
+    assert!(analyzer.loads(Band::TwoPointFourGhz, 80).is_empty());
+    assert!(analyzer.loads(Band::FiveGhz, 320).is_empty());
+    let starts = |band, width| -> Vec<u32> {
+        analyzer
+            .loads(band, width)
+            .iter()
+            .map(|l| l.channel)
+            .collect()
+    };
+    assert_eq!(starts(Band::FiveGhz, 160), vec![36, 100, 149]);
+    // 320-1 and 320-2 channelizations
+    assert_eq!(starts(Band::SixGhz, 320), vec![1, 33, 65, 97, 129, 161]);
+    assert_eq!(analyzer.recommendations().len(), 11);
+    assert_eq!(analyzer.recommend(Band::TwoPointFourGhz, 10), None);
+}
+
+#[test]
+fn overlapping_320mhz_channels() {
+    // 320 MHz network on the 320-2 channel centered on channel 63, primary channel 37
+    let eht_operation = element(255, &[106, 0x01, 0, 0, 0, 0, 0x04, 39, 63]);
+    let wifis = vec![wifi("00:00:00:00:00:25")
+        .channel(37)
+        .frequency(6135)
+        .signal(-40)
+        .elements(eht_operation)
+        .build()];
+    let loads = ChannelAnalyzer::new(&wifis).loads(Band::SixGhz, 320);
+    assert_eq!(loads[1].channel, 33);
+    assert_eq!(loads[1].center_frequency, 6265);
+    assert_close(loads[1].load, 0.5);
+    assert_close(loads[0].load, 0.25);
+    assert_close(loads[2].load, 0.25);
+    assert_eq!(loads[3].bss_count, 0);
+}
@@ -0,0 +1,129 @@
+use std::{
+    io::Cursor,
+    time::{Duration, SystemTime},
+};
+
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{
+    export::{
+        csv::{Column, CsvReader, CsvWriter, DEFAULT_COLUMNS},
+        ScanRecord,
+    },
+    Wifi, WifiSecurity,
+};
+
+const BSSID: &str = "00:11:22:33:44:55";
+
+fn time() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_600)
+}
+
+fn write(columns: &[Column], wifis: &[Wifi]) -> String {
+    let mut writer = CsvWriter::new(Vec::new()).columns(columns);
+    writer.write_at(wifis, time()).unwrap();
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+fn read(text: &str) -> std::io::Result<Vec<ScanRecord>> {
+    CsvReader::new(Cursor::new(text.to_string())).collect()
+}
+
+#[test]
+fn quoting() {
+    let wifis: Vec<Wifi> = ["Cafe, \"Lobby\"", "Line\nbreak", " padded "]
+        .iter()
+        .map(|ssid| wifi(BSSID).ssid(ssid).channel(6).build())
+        .collect();
+    assert_eq!(
+        write(&[Column::Ssid, Column::Channel], &wifis),
+        "ssid,channel\n\
+         \"Cafe, \"\"Lobby\"\"\",6\n\
+         \"Line\nbreak\",6\n\
+         \" padded \",6\n"
+    );
+}
+
+#[test]
+fn round_trip() {
+    let network = |ssid: &str, security: &[WifiSecurity]| {
+        wifi(BSSID)
+            .ssid(ssid)
+            .channel(6)
+            .frequency(2437)
+            .signal(-60)
+            .security(security)
+    };
+    let wifis = vec![
+        network(
+            "Home",
+            &[WifiSecurity::Wpa2PersonalPsk, WifiSecurity::Wpa3PersonalSae],
+        )
+        .elements(element(0, b"Home"))
+        .build(),
+        network("Multi\r\nline, \"quoted\"", &[WifiSecurity::Open]).build(),
+        network("", &[WifiSecurity::Other("Vendor;Mode\\2".to_string())]).build(),
+        network("  ", &[WifiSecurity::Unknown]).build(),
+    ];
+
+    let text = write(&DEFAULT_COLUMNS, &wifis);
+    assert!(text.starts_with(
+        "timestamp,mac,ssid,channel,frequency,signal_level,security,information_elements,inferred\n\
+         2024-05-01T12:30:00.000Z,00:11:22:33:44:55,Home,6,2437,-60,WPA2-Personal (PSK);WPA3-Personal (SAE),0004486f6d65,false\n"
+    ));
+    assert!(text.contains(",,6,2437,-60,Vendor\\;Mode\\\\2,,false\n"));
+
+    let records = read(&text).unwrap();
+    let expected: Vec<ScanRecord> = wifis
+        .into_iter()
+        .map(|wifi| ScanRecord::new(wifi, time()))
+        .collect();
+    assert_eq!(records, expected);
+}
+
+#[test]
+fn hidden_ssid_only() {
+    let wifis: Vec<Wifi> = ["", " ", "Home"]
+        .iter()
+        .map(|ssid| wifi(BSSID).ssid(ssid).build())
+        .collect();
+    let text = write(&[Column::Ssid], &wifis);
+    assert_eq!(text, "ssid\n\"\"\n\" \"\nHome\n");
+
+    let ssids: Vec<String> = read(&text)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.wifi.ssid)
+        .collect();
+    assert_eq!(ssids, vec!["", " ", "Home"]);
+}
+
+#[test]
+fn foreign_files() {
+    // byte order mark, unknown and missing columns, blank lines and CRLF line endings
+    let text = "\u{feff}ssid,vendor,signal_level\r\n\r\nHome,Acme,-50\r\n\"Two\r\nlines\",,-70\r\n";
+    let records = read(text).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].timestamp, None);
+    assert_eq!(records[0].wifi.ssid, "Home");
+    assert_eq!(records[0].wifi.signal_level, -50);
+    assert_eq!(records[1].wifi.ssid, "Two\r\nlines");
+    assert!(read("").unwrap().is_empty());
+}
+
+#[test]
+fn invalid_rows() {
+    let error = read("ssid,channel\nHome,6\nOffice\n").unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("line 3"));
+    assert!(error.to_string().contains("expected 2 fields, found 1"));
+
+    let error = read("ssid,channel\nHome,six\n").unwrap_err();
+    assert!(error.to_string().contains("invalid channel: six"));
+
+    let error = read("ssid,channel\n\"Home,6\n\n").unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("line 2: unterminated quoted field"));
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::distance::{
+    free_space_loss, DistanceConfig, PathLossModel, TxPowerSource, DEFAULT_TX_POWER,
+};
+
+const BSSID: &str = "00:11:22:33:44:55";
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+}
+
+#[test]
+fn path_loss_models() {
+    // well known free-space loss of about 40 dB at 1 m on 2.4 GHz
+    assert_close(free_space_loss(1.0, 2437.0), 40.187_110_6);
+    assert_close(free_space_loss(10.0, 2437.0), 60.187_110_6);
+
+    let loss = free_space_loss(25.0, 5180.0);
+    assert_close(PathLossModel::FreeSpace.distance(loss, 5180.0), 25.0);
+
+    // an exponent of 2 is free space
+    let log_distance = PathLossModel::LogDistance {
+        exponent: 2.0,
+        reference_distance: 1.0,
+    };
+    assert_close(log_distance.distance(loss, 5180.0), 25.0);
+
+    // 30 dB more than at the reference distance is 10 times as far with exponent 3
+    let indoor = PathLossModel::LogDistance {
+        exponent: 3.0,
+        reference_distance: 2.0,
+    };
+    let reference_loss = free_space_loss(2.0, 2437.0);
+    assert_close(indoor.distance(reference_loss, 2437.0), 2.0);
+    assert_close(indoor.distance(reference_loss + 30.0, 2437.0), 20.0);
+}
+
+#[test]
+fn configured_tx_power() {
+    let config = DistanceConfig {
+        tx_power: Some(15.0),
+        model: PathLossModel::FreeSpace,
+        shadowing: -3.0,
+    };
+    let path_loss = free_space_loss(10.0, 2437.0);
+    let signal_level = (15.0 - path_loss).round() as i32;
+    let estimate = wifi(BSSID)
+        .channel(6)
+        .signal(signal_level)
+        .build()
+        .distance(&config)
+        .unwrap();
+
+    let expected = PathLossModel::FreeSpace.distance(15.0 - signal_level as f64, 2437.0);
+    assert_close(estimate.meters, expected);
+    assert!((estimate.meters - 10.0).abs() < 1.0);
+    // the sign of the shadowing is ignored
+    assert!(estimate.min_meters < estimate.meters && estimate.meters < estimate.max_meters);
+    assert_close(
+        estimate.max_meters / estimate.meters,
+        10f64.powf(3.0 / 20.0),
+    );
+    assert_eq!(estimate.tx_power_source, TxPowerSource::Configured);
+    assert_eq!(estimate.tx_power, 15.0);
+}
+
+#[test]
+fn advertised_tx_power() {
+    let config = DistanceConfig::indoor();
+
+    // TPC Report with 17 dBm wins over the Country element
+    let mut ies = element(7, b"DE \x01\x0d\x14");
+    ies.extend(element(35, &[17, 0]));
+    let estimate = wifi(BSSID)
+        .channel(6)
+        .signal(-60)
+        .elements(ies)
+        .build()
+        .distance(&config)
+        .unwrap();
+    assert_eq!(
+        (estimate.tx_power, estimate.tx_power_source),
+        (17.0, TxPowerSource::TpcReport)
+    );
+
+    // Country element with 20 dBm on channels 1-13 and 23 dBm on 36-48,
+    // reduced by a Power Constraint of 3 dB
+    let mut ies = element(7, b"DE \x01\x0d\x14\x24\x04\x17");
+    ies.extend(element(32, &[3]));
+    let estimate = wifi(BSSID)
+        .channel(44)
+        .signal(-60)
+        .elements(ies.clone())
+        .build()
+        .distance(&config)
+        .unwrap();
+    assert_eq!(
+        (estimate.tx_power, estimate.tx_power_source),
+        (20.0, TxPowerSource::Country)
+    );
+    let estimate = wifi(BSSID)
+        .channel(11)
+        .signal(-60)
+        .elements(ies.clone())
+        .build()
+        .distance(&config)
+        .unwrap();
+    assert_eq!(estimate.tx_power, 17.0);
+
+    // channel not covered by the Country element
+    let estimate = wifi(BSSID)
+        .channel(149)
+        .signal(-60)
+        .elements(ies)
+        .build()
+        .distance(&config)
+        .unwrap();
+    assert_eq!(
+        (estimate.tx_power, estimate.tx_power_source),
+        (DEFAULT_TX_POWER, TxPowerSource::Default)
+    );
+}
+
+#[test]
+fn unknown_inputs() {
+    let config = DistanceConfig::default();
+    assert_eq!(config, DistanceConfig::indoor());
+    assert_eq!(
+        wifi(BSSID).channel(6).signal(0).build().distance(&config),
+        None
+    );
+    assert_eq!(
+        wifi(BSSID).channel(0).signal(-60).build().distance(&config),
+        None
+    );
+
+    // stronger signals are closer
+    let near = wifi(BSSID)
+        .channel(6)
+        .signal(-40)
+        .build()
+        .distance(&config)
+        .unwrap();
+    let far = wifi(BSSID)
+        .channel(6)
+        .signal(-80)
+        .build()
+        .distance(&config)
+        .unwrap();
+    assert!(near.meters < far.meters);
+    assert!(DistanceConfig::free_space().shadowing < config.shadowing);
+    assert!(near.to_string().ends_with(" m)"));
+}
+
+#[test]
+fn reported_frequency() {
+    let config = DistanceConfig::free_space();
+    let on_channel = wifi(BSSID).channel(6).signal(-60).build();
+    // the frequency is used when the channel is unknown or disagrees with it
+    let reported = wifi(BSSID).frequency(2437).signal(-60).build();
+    assert_eq!(reported.distance(&config), on_channel.distance(&config));
+    let reported = wifi(BSSID).channel(6).frequency(5180).signal(-60).build();
+    let expected = PathLossModel::FreeSpace.distance(DEFAULT_TX_POWER + 60.0, 5180.0);
+    assert_close(reported.distance(&config).unwrap().meters, expected);
+}
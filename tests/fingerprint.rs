@@ -0,0 +1,144 @@
+use std::io::Cursor;
+
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    fingerprint::{Fingerprint, FingerprintDb},
+    Wifi,
+};
+
+fn scan(levels: &[(&str, i32)]) -> Vec<Wifi> {
+    levels
+        .iter()
+        .map(|(mac, level)| wifi(mac).signal(*level).build())
+        .collect()
+}
+
+fn db() -> FingerprintDb {
+    let mut db = FingerprintDb::new();
+    db.record(
+        "Kitchen",
+        &scan(&[("00:00:00:00:00:01", -40), ("00:00:00:00:00:02", -70)]),
+    );
+    db.record(
+        "Kitchen",
+        &scan(&[("00:00:00:00:00:01", -44), ("00:00:00:00:00:02", -66)]),
+    );
+    db.record(
+        "Office",
+        &scan(&[
+            ("00:00:00:00:00:01", -75),
+            ("00:00:00:00:00:02", -45),
+            ("00:00:00:00:00:03", -60),
+        ]),
+    );
+    db
+}
+
+#[test]
+fn fingerprint_from_scan() {
+    let fingerprint = Fingerprint::from_scan(
+        "Hall",
+        &scan(&[
+            ("AA:BB:CC:DD:EE:01", -60),
+            ("aa:bb:cc:dd:ee:01", -50),
+            ("AA:BB:CC:DD:EE:02", 0),
+            ("invalid", -40),
+        ]),
+    );
+    assert_eq!(fingerprint.len(), 1);
+    assert_eq!(fingerprint.get("aa-bb-cc-dd-ee-01"), Some(-50));
+    assert_eq!(
+        fingerprint.signals(),
+        vec![("aa:bb:cc:dd:ee:01".to_string(), -50)]
+    );
+
+    let mut manual = Fingerprint::new("Hall");
+    assert!(manual.is_empty());
+    assert!(manual.insert("AA:BB:CC:DD:EE:01", -50));
+    assert!(!manual.insert("invalid", -50));
+    assert_eq!(manual, fingerprint);
+}
+
+#[test]
+fn nearest_neighbours() {
+    let db = db();
+    assert_eq!(db.len(), 3);
+    assert_eq!(db.labels(), vec!["Kitchen", "Office"]);
+
+    // BSSIDs are matched regardless of their notation
+    let here = scan(&[("00:00:00:00:00:01", -42), ("00-00-00-00-00-02", -68)]);
+    let matches = db.matches(&here);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].label, "Kitchen");
+    assert!(matches[0].confidence > 0.8);
+    assert!((matches[0].distance - 2.0).abs() < 1e-9);
+    let total: f64 = matches.iter().map(|m| m.confidence).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+
+    let office = scan(&[
+        ("00:00:00:00:00:01", -73),
+        ("00:00:00:00:00:02", -47),
+        ("00:00:00:00:00:03", -62),
+    ]);
+    assert_eq!(db.best_match(&office).unwrap().label, "Office");
+
+    // a single neighbour decides alone
+    let matches = db.matches_with(&office, 1);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].confidence, 1.0);
+    assert_eq!(db.matches_with(&office, 0).len(), 1);
+}
+
+#[test]
+fn no_common_access_points() {
+    let db = db();
+    assert!(db.matches(&scan(&[("00:00:00:00:00:99", -50)])).is_empty());
+    assert_eq!(db.best_match(&[]), None);
+
+    // fingerprints without signals are not recorded
+    let mut db = FingerprintDb::new();
+    db.record("Empty", &scan(&[("00:00:00:00:00:01", 0)]));
+    assert!(db.is_empty());
+}
+
+#[test]
+fn csv_round_trip() {
+    let mut db = db();
+    db.record(
+        "Living room, \"east\"",
+        &scan(&[("00:00:00:00:00:04", -55)]),
+    );
+    db.remove_label("Office");
+
+    let mut buffer = Vec::new();
+    db.write(&mut buffer).unwrap();
+    let text = String::from_utf8(buffer.clone()).unwrap();
+    assert_eq!(
+        text,
+        "Kitchen,00:00:00:00:00:01,-40,00:00:00:00:00:02,-70\n\
+         Kitchen,00:00:00:00:00:01,-44,00:00:00:00:00:02,-66\n\
+         \"Living room, \"\"east\"\"\",00:00:00:00:00:04,-55\n"
+    );
+    assert_eq!(FingerprintDb::read(Cursor::new(buffer)).unwrap(), db);
+
+    let path =
+        std::env::temp_dir().join(format!("wifi_scan_fingerprints_{}.csv", std::process::id()));
+    db.save(&path).unwrap();
+    let loaded = FingerprintDb::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), db);
+}
+
+#[test]
+fn invalid_csv() {
+    let read = |text: &str| FingerprintDb::read(Cursor::new(text.to_string()));
+    assert!(read("\n\nKitchen,00:00:00:00:00:01,-40\n").is_ok());
+
+    let error = read("Kitchen,00:00:00:00:00:01,-40\nOffice,00:00:00:00:00:01\n").unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("line 2"));
+    assert!(read("Kitchen,invalid,-40\n").is_err());
+    assert!(read("Kitchen,00:00:00:00:00:01,loud\n").is_err());
+}
@@ -0,0 +1,94 @@
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    export::{geojson, GpsFix, Observation, ScanRecord},
+    WifiSecurity,
+};
+
+fn observation(
+    mac: &str,
+    ssid: &str,
+    security: &[WifiSecurity],
+    signal_level: i32,
+    fix: GpsFix,
+) -> Observation {
+    let wifi = wifi(mac)
+        .ssid(ssid)
+        .channel(36)
+        .signal(signal_level)
+        .security(security)
+        .build();
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_600);
+    Observation::new(ScanRecord::new(wifi, time), fix)
+}
+
+fn write(observations: &[Observation]) -> String {
+    let mut buffer = Vec::new();
+    geojson::write(&mut buffer, observations).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn golden_output() {
+    let observations = vec![
+        observation(
+            "00-11-22-33-44-55",
+            "Home \"5G\"\\\n\u{1}",
+            &[WifiSecurity::Wpa3PersonalSae],
+            -50,
+            GpsFix {
+                altitude: Some(34.5),
+                ..GpsFix::new(52.6, 13.5)
+            },
+        ),
+        observation(
+            "66:77:88:99:aa:bb",
+            "Cafe",
+            &[WifiSecurity::Other("Vendor \"X\"".to_string())],
+            0,
+            GpsFix::new(-33.9, 151.2),
+        ),
+        // networks without BSSID are skipped
+        observation(
+            "",
+            "Hidden",
+            &[WifiSecurity::Open],
+            -60,
+            GpsFix::new(0.0, 0.0),
+        ),
+    ];
+
+    assert_eq!(
+        write(&observations),
+        r##"{"type":"FeatureCollection","features":[
+{"type":"Feature","geometry":{"type":"Point","coordinates":[13.5,52.6,34.5]},"properties":{"bssid":"00:11:22:33:44:55","ssid":"Home \"5G\"\\\n\u0001","channel":36,"frequency":5180,"signal_level":-50,"security":"WPA3","securities":["WPA3-Personal (SAE)"],"first_seen":"2024-05-01T12:30:00.000Z","last_seen":"2024-05-01T12:30:00.000Z","observations":1,"marker-color":"#2060e0"}},
+{"type":"Feature","geometry":{"type":"Point","coordinates":[151.2,-33.9]},"properties":{"bssid":"66:77:88:99:aa:bb","ssid":"Cafe","channel":36,"frequency":5180,"signal_level":null,"security":"Unknown","securities":["Vendor \"X\""],"first_seen":"2024-05-01T12:30:00.000Z","last_seen":"2024-05-01T12:30:00.000Z","observations":1,"marker-color":"#808080"}}
+]}
+"##
+    );
+}
+
+#[test]
+fn valid_json() {
+    let observations = vec![observation(
+        "00:11:22:33:44:55",
+        "Tab\tand \u{7f} \u{1f600}",
+        &[WifiSecurity::Open],
+        -70,
+        GpsFix::new(52.5, 13.4),
+    )];
+    let value: serde_json::Value = serde_json::from_str(&write(&observations)).unwrap();
+    let feature = &value["features"][0];
+    assert_eq!(feature["properties"]["ssid"], "Tab\tand \u{7f} \u{1f600}");
+    assert_eq!(feature["properties"]["marker-color"], "#e02020");
+    assert_eq!(
+        feature["geometry"]["coordinates"],
+        serde_json::json!([13.4, 52.5])
+    );
+
+    let value: serde_json::Value = serde_json::from_str(&write(&[])).unwrap();
+    assert_eq!(value["features"], serde_json::json!([]));
+}
@@ -0,0 +1,220 @@
+#![cfg(feature = "gps")]
+
+use std::{
+    io::{BufRead, BufReader, Cursor, Write},
+    net::TcpListener,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    export::FixQuality,
+    gps::{gpsd::GpsdClient, nmea::NmeaParser, nmea::NmeaReader, GpsSource, Snapshot},
+};
+
+const TPV: &str = r#"{"class":"TPV","device":"/dev/ttyUSB0","mode":3,"time":"2024-05-01T12:30:00.000Z","ept":0.005,"lat":52.52,"lon":13.405,"altHAE":80.2,"altMSL":34.5,"eph":4.5,"speed":0.1}"#;
+
+/// Starts a stand-in gpsd that expects a WATCH command, sends `reports`
+/// and answers a POLL command with `poll`, or closes the connection if `poll` is empty
+fn gpsd(reports: &'static [&'static str], poll: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        writeln!(
+            stream,
+            r#"{{"class":"VERSION","release":"3.25","rev":"3.25","proto_major":3,"proto_minor":15}}"#
+        )
+        .unwrap();
+
+        let mut command = String::new();
+        reader.read_line(&mut command).unwrap();
+        assert!(command.starts_with("?WATCH={\"enable\":true,\"json\":true};"));
+        writeln!(
+            stream,
+            r#"{{"class":"DEVICES","devices":[{{"class":"DEVICE","path":"/dev/ttyUSB0"}}]}}"#
+        )
+        .unwrap();
+        writeln!(stream, r#"{{"class":"WATCH","enable":true,"json":true}}"#).unwrap();
+        for report in reports {
+            writeln!(stream, "{}", report).unwrap();
+        }
+
+        command.clear();
+        if !poll.is_empty() && reader.read_line(&mut command).unwrap() > 0 {
+            assert_eq!(command.trim(), "?POLL;");
+            writeln!(stream, "{}", poll).unwrap();
+        }
+    });
+
+    address
+}
+
+#[test]
+fn gpsd_next_fix() {
+    let address = gpsd(
+        &[
+            r#"{"class":"SKY","device":"/dev/ttyUSB0","satellites":[]}"#,
+            r#"{"class":"TPV","device":"/dev/ttyUSB0","mode":1}"#,
+            TPV,
+        ],
+        "",
+    );
+    let mut client = GpsdClient::connect(address).unwrap();
+
+    let fix = client.next_fix().unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::NoFix));
+
+    let fix = client.next_fix().unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Fix3d));
+    assert_eq!((fix.latitude, fix.longitude), (52.52, 13.405));
+    assert_eq!(fix.altitude, Some(34.5));
+    assert_eq!(fix.accuracy, Some(4.5));
+    assert_eq!(
+        fix.timestamp,
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_600))
+    );
+}
+
+#[test]
+fn gpsd_poll() {
+    let address = gpsd(
+        &[],
+        r#"{"class":"POLL","time":"2024-05-01T12:30:00.000Z","active":1,"tpv":[{"class":"TPV","mode":2,"status":2,"lat":-33.8,"lon":151.2,"alt":12.0,"epx":3.0,"epy":5.0}],"sky":[]}"#,
+    );
+    let mut client = GpsdClient::connect(address).unwrap();
+
+    let fix = client.current_fix().unwrap().unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Dgps));
+    assert_eq!((fix.latitude, fix.longitude), (-33.8, 151.2));
+    assert_eq!(fix.accuracy, Some(5.0));
+    assert_eq!(fix.timestamp, None);
+}
+
+#[test]
+fn gpsd_poll_without_receiver() {
+    let address = gpsd(&[], r#"{"class":"POLL","active":0,"tpv":[],"sky":[]}"#);
+    let mut client = GpsdClient::connect(address).unwrap();
+    assert_eq!(client.poll().unwrap(), None);
+}
+
+#[test]
+fn gpsd_closed_connection() {
+    let address = gpsd(&[], "");
+    let mut client = GpsdClient::connect(address).unwrap();
+    assert!(client.next_fix().is_err());
+}
+
+#[test]
+fn nmea_gga_and_rmc() {
+    let mut parser = NmeaParser::new();
+    assert_eq!(parser.fix(), None);
+
+    let fix = parser
+        .parse("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Fix3d));
+    assert!((fix.latitude - 48.1173).abs() < 1e-9);
+    assert!((fix.longitude - 11.516_666_666).abs() < 1e-6);
+    assert_eq!(fix.altitude, Some(545.4));
+    // the date is only known after RMC
+    assert_eq!(fix.timestamp, None);
+
+    let fix = parser
+        .parse("$GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Fix3d));
+    assert_eq!(
+        fix.timestamp,
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(764_426_119))
+    );
+}
+
+#[test]
+fn nmea_quality_changes() {
+    let mut parser = NmeaParser::new();
+    let fix = parser
+        .parse("$GPGGA,123519,4807.038,N,01131.000,E,2,08,0.9,545.4,M,46.9,M,,")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Dgps));
+
+    // DGPS corrections were lost
+    let fix = parser
+        .parse("$GPGGA,123520,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Fix3d));
+
+    // the autonomous mode agrees with GGA
+    let fix = parser
+        .parse("$GPRMC,123520,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W,A")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Fix3d));
+
+    let fix = parser
+        .parse("$GPRMC,123521,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W,E")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Estimated));
+
+    // RMC before NMEA 2.3 has no mode
+    let fix = parser
+        .parse("$GPRMC,123522,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Estimated));
+
+    let fix = parser
+        .parse("$GPGGA,123523,4807.038,N,01131.000,E,4,08,0.9,545.4,M,46.9,M,,")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::RtkFixed));
+}
+
+#[test]
+fn nmea_rejects_invalid_sentences() {
+    let mut parser = NmeaParser::new();
+    // wrong checksum
+    assert_eq!(
+        parser.parse("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48"),
+        None
+    );
+    assert_eq!(parser.parse("GPGGA,123519"), None);
+    assert_eq!(parser.parse("$PGRME,15.0,M,45.0,M,25.0,M*1C"), None);
+    assert_eq!(parser.fix(), None);
+}
+
+#[test]
+fn nmea_no_fix_and_southern_hemisphere() {
+    let mut parser = NmeaParser::new();
+    let fix = parser.parse("$GNGGA,001043.00,,,,,0,00,99.99,,,,,,*7E");
+    assert_eq!(fix.unwrap().quality, Some(FixQuality::NoFix));
+
+    parser.parse("$GNGSA,A,2,,,,,,,,,,,,,99.99,99.99,99.99*2D");
+    let fix = parser
+        .parse("$GNGGA,001044.00,3351.000,S,15112.000,E,1,04,2.0,12.0,M,20.0,M,,")
+        .unwrap();
+    assert_eq!(fix.quality, Some(FixQuality::Fix2d));
+    assert_eq!((fix.latitude, fix.longitude), (-33.85, 151.2));
+}
+
+#[test]
+fn nmea_reader_and_snapshot() {
+    let log = "garbage\n\
+               $GPGST,123519,0.6,2.0,1.0,30.0,3.0,4.0,5.0*40\n\
+               $GPRMC,123519,A,4807.038,N,01131.000,E,022.4,084.4,230394,003.1,W*6A\n";
+    let mut reader = NmeaReader::new(Cursor::new(log));
+    let fix = reader.current_fix().unwrap().unwrap();
+    assert_eq!(fix.accuracy, Some(5.0));
+    assert!(reader.current_fix().unwrap().is_none());
+
+    let wifi = wifi("00:11:22:33:44:55").build();
+    let snapshot = Snapshot::new(vec![wifi], Some(fix));
+    assert_eq!(snapshot.observations().len(), 1);
+    assert_eq!(snapshot.observations()[0].fix, fix);
+
+    let mut no_fix = fix;
+    no_fix.quality = Some(FixQuality::NoFix);
+    assert!(Snapshot::new(Vec::new(), Some(no_fix)).fix.is_none());
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::element;
+use wifi_scan::{
+    hotspot::{AccessNetworkType, AdvertisementProtocol, HotspotInfo, Venue},
+    Wifi,
+};
+
+/// Elements of an airport Passpoint hotspot
+fn airport() -> Vec<u8> {
+    // chargeable public with internet, venue Assembly / Airport, HESSID
+    let mut ies = element(107, &[0x12, 1, 4, 0x02, 0x11, 0x22, 0x33, 0x44, 0x55]);
+    // one more OI through ANQP, OIs of 3, 5 and 3 bytes
+    ies.extend(element(
+        111,
+        &[
+            1, 0x53, 0x50, 0x6f, 0x9a, 0x00, 0x1b, 0xc5, 0x04, 0xbd, 0x00, 0x40, 0x96,
+        ],
+    ));
+    // ANQP
+    ies.extend(element(108, &[0x7f, 0x00]));
+    // HS2.0 Indication, release 3 with DGAF disabled
+    ies.extend(element(221, &[0x50, 0x6f, 0x9a, 0x10, 0x21]));
+    ies
+}
+
+#[test]
+fn passpoint_hotspot() {
+    let info = HotspotInfo::from_elements(&airport()).unwrap();
+    assert_eq!(
+        info,
+        HotspotInfo {
+            access_network_type: Some(AccessNetworkType::ChargeablePublic),
+            internet: true,
+            additional_step_required: false,
+            emergency_services_reachable: false,
+            unauthenticated_emergency_access: false,
+            venue: Some(Venue {
+                group: 1,
+                venue_type: 4,
+            }),
+            hessid: Some("02:11:22:33:44:55".to_string()),
+            roaming_consortium: vec![
+                "506F9A".to_string(),
+                "001BC504BD".to_string(),
+                "004096".to_string(),
+            ],
+            anqp_oi_count: 1,
+            advertisement_protocols: vec![AdvertisementProtocol::Anqp],
+            hs20_release: Some(3),
+            dgaf_disabled: true,
+        }
+    );
+    assert!(info.is_passpoint());
+    assert_eq!(info.venue.unwrap().group_name(), "Assembly");
+    assert_eq!(
+        info.access_network_type.unwrap().to_string(),
+        "Chargeable public"
+    );
+
+    let mut wifi = Wifi::default();
+    wifi.set_information_elements(airport());
+    assert!(wifi.is_passpoint());
+    assert_eq!(wifi.hotspot(), Some(info));
+}
+
+#[test]
+fn interworking_layouts() {
+    // access network options only
+    let info = HotspotInfo::from_elements(&element(107, &[0xe3])).unwrap();
+    assert_eq!(
+        info.access_network_type,
+        Some(AccessNetworkType::FreePublic)
+    );
+    assert!(!info.internet);
+    assert!(info.additional_step_required);
+    assert!(info.emergency_services_reachable);
+    assert!(info.unauthenticated_emergency_access);
+    assert_eq!((info.venue, info.hessid.as_deref()), (None, None));
+    assert!(!info.is_passpoint());
+
+    // venue info only
+    let info = HotspotInfo::from_elements(&element(107, &[0x00, 2, 8])).unwrap();
+    assert_eq!(info.access_network_type, Some(AccessNetworkType::Private));
+    assert_eq!(info.venue.unwrap().group_name(), "Business");
+    assert_eq!(info.hessid, None);
+
+    // HESSID only, reserved access network type
+    let info = HotspotInfo::from_elements(&element(107, &[0x07, 0, 1, 2, 3, 4, 5])).unwrap();
+    assert_eq!(info.access_network_type, Some(AccessNetworkType::Other(7)));
+    assert_eq!(info.venue, None);
+    assert_eq!(info.hessid.as_deref(), Some("00:01:02:03:04:05"));
+
+    // unexpected length
+    let info = HotspotInfo::from_elements(&element(107, &[0x0f, 1, 2, 3, 4])).unwrap();
+    assert_eq!(info.access_network_type, Some(AccessNetworkType::Wildcard));
+    assert_eq!((info.venue, info.hessid.as_deref()), (None, None));
+}
+
+#[test]
+fn advertisement_protocol_tuples() {
+    // ANQP, a vendor specific protocol with a 5 byte body, then MIH
+    let ies = element(
+        108,
+        &[
+            0x7f, 0x00, 0x7f, 221, 5, 0x00, 0x11, 0x22, 0x01, 0x02, 0x7f, 0x01,
+        ],
+    );
+    let info = HotspotInfo::from_elements(&ies).unwrap();
+    assert_eq!(
+        info.advertisement_protocols,
+        vec![
+            AdvertisementProtocol::Anqp,
+            AdvertisementProtocol::VendorSpecific,
+            AdvertisementProtocol::MihInformationService,
+        ]
+    );
+
+    // truncated vendor specific body and incomplete tuple
+    let ies = element(108, &[0x7f, 0x00, 0x7f, 221, 5, 0x00, 0x11]);
+    let info = HotspotInfo::from_elements(&ies).unwrap();
+    assert_eq!(
+        info.advertisement_protocols,
+        vec![AdvertisementProtocol::Anqp]
+    );
+    let ies = element(108, &[0x7f, 0x03, 0x7f]);
+    let info = HotspotInfo::from_elements(&ies).unwrap();
+    assert_eq!(
+        info.advertisement_protocols,
+        vec![AdvertisementProtocol::EmergencyAlertSystem]
+    );
+}
+
+#[test]
+fn malformed_elements() {
+    assert_eq!(HotspotInfo::from_elements(&[]), None);
+    assert_eq!(HotspotInfo::from_elements(&element(0, b"Cafe")), None);
+    // empty Interworking and too short Roaming Consortium elements are ignored
+    let mut ies = element(107, &[]);
+    ies.extend(element(111, &[0]));
+    assert_eq!(HotspotInfo::from_elements(&ies), None);
+
+    // OI lengths beyond the element
+    let info = HotspotInfo::from_elements(&element(111, &[0, 0x33, 0x50, 0x6f, 0x9a])).unwrap();
+    assert_eq!(info.roaming_consortium, vec!["506F9A".to_string()]);
+
+    // HS2.0 Indication without configuration is release 1
+    let info = HotspotInfo::from_elements(&element(221, &[0x50, 0x6f, 0x9a, 0x10])).unwrap();
+    assert_eq!(info.hs20_release, Some(1));
+    assert!(!info.dgaf_disabled);
+    assert_eq!(info.access_network_type, None);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "json")]
+
+use std::{
+    io::Cursor,
+    time::{Duration, SystemTime},
+};
+
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    export::{
+        csv::{CsvReader, CsvWriter},
+        jsonl::{JsonLinesReader, JsonLinesWriter},
+        ScanRecord,
+    },
+    Wifi, WifiSecurity,
+};
+
+fn wifis() -> Vec<Wifi> {
+    let home = wifi("00:11:22:33:44:55")
+        .ssid("Home, \"upstairs\"\nand down")
+        .channel(36)
+        .frequency(5180)
+        .signal(-55)
+        .security(&[
+            WifiSecurity::Wpa3PersonalSae,
+            WifiSecurity::Other("Vendor;Mode".to_string()),
+        ])
+        .elements(vec![0, 4, b'H', b'o', b'm', b'e'])
+        .build();
+    let hidden = wifi("66:77:88:99:aa:bb").inferred().build();
+    vec![home, hidden]
+}
+
+fn time() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(1_714_566_600_250)
+}
+
+fn read(text: &str) -> std::io::Result<Vec<ScanRecord>> {
+    JsonLinesReader::new(Cursor::new(text.to_string())).collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut writer = JsonLinesWriter::new(Vec::new());
+    writer.write_at(&wifis(), time()).unwrap();
+    let text = String::from_utf8(writer.into_inner()).unwrap();
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.starts_with(
+        "{\"schema_version\":1,\"timestamp\":\"2024-05-01T12:30:00.250Z\",\"mac\":\"00:11:22:33:44:55\""
+    ));
+
+    let expected: Vec<ScanRecord> = wifis()
+        .into_iter()
+        .map(|wifi| ScanRecord::new(wifi, time()))
+        .collect();
+    assert_eq!(read(&text).unwrap(), expected);
+}
+
+#[test]
+fn csv_to_jsonl() {
+    let mut csv = CsvWriter::new(Vec::new());
+    csv.write_at(&wifis(), time()).unwrap();
+    let csv = csv.into_inner();
+
+    let mut jsonl = JsonLinesWriter::new(Vec::new());
+    for record in CsvReader::new(Cursor::new(csv)) {
+        jsonl.write_record(&record.unwrap()).unwrap();
+    }
+    let text = String::from_utf8(jsonl.into_inner()).unwrap();
+
+    let mut csv = CsvWriter::new(Vec::new());
+    for record in read(&text).unwrap() {
+        csv.write_record(&record).unwrap();
+    }
+    let records: Vec<ScanRecord> = CsvReader::new(Cursor::new(csv.into_inner()))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0], ScanRecord::new(wifis()[0].clone(), time()));
+    assert_eq!(records[1], ScanRecord::new(wifis()[1].clone(), time()));
+}
+
+#[test]
+fn invalid_lines() {
+    assert!(read("\n\n").unwrap().is_empty());
+
+    let error = read("\n{\"schema_version\":1,\"mac\":5}\n").unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains("line 2"));
+
+    let newer = "{\"schema_version\":99,\"mac\":\"00:11:22:33:44:55\"}";
+    assert!(read(newer).unwrap_err().to_string().contains("line 1"));
+}
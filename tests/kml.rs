@@ -0,0 +1,183 @@
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    export::{kml, GpsFix, MapSecurityClass, Observation, ScanRecord},
+    Wifi, WifiSecurity,
+};
+
+fn observation(
+    mac: &str,
+    ssid: &str,
+    security: &[WifiSecurity],
+    signal_level: i32,
+    seconds: u64,
+    fix: GpsFix,
+) -> Observation {
+    let wifi = wifi(mac)
+        .ssid(ssid)
+        .channel(6)
+        .signal(signal_level)
+        .security(security)
+        .build();
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_600 + seconds);
+    Observation::new(ScanRecord::new(wifi, time), fix)
+}
+
+fn write(observations: &[Observation]) -> String {
+    let mut buffer = Vec::new();
+    kml::write(&mut buffer, observations).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn golden_output() {
+    let psk = [WifiSecurity::Wpa2PersonalPsk];
+    let at_altitude = GpsFix {
+        altitude: Some(34.5),
+        ..GpsFix::new(52.5, 13.4)
+    };
+    let observations = vec![
+        observation(
+            "00-11-22-33-44-55",
+            "Home & <Garden>",
+            &psk,
+            -70,
+            0,
+            at_altitude,
+        ),
+        observation(
+            "66:77:88:99:aa:bb",
+            "",
+            &[WifiSecurity::Open],
+            0,
+            5,
+            GpsFix::new(52.5, 13.4),
+        ),
+        observation(
+            "00:11:22:33:44:55",
+            "Home & <Garden>",
+            &psk,
+            -50,
+            10,
+            GpsFix::new(52.6, 13.5),
+        ),
+    ];
+
+    let icon =
+        "<Icon><href>http://maps.google.com/mapfiles/kml/shapes/placemark_circle.png</href></Icon>";
+    let expected = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Document>
+  <name>wifi_scan survey</name>
+  <Style id="open">
+    <IconStyle><color>ff2020e0</color>{icon}</IconStyle>
+  </Style>
+  <Style id="wep">
+    <IconStyle><color>ff0080f0</color>{icon}</IconStyle>
+  </Style>
+  <Style id="wpa">
+    <IconStyle><color>ff00c0e0</color>{icon}</IconStyle>
+  </Style>
+  <Style id="wpa2">
+    <IconStyle><color>ff30a030</color>{icon}</IconStyle>
+  </Style>
+  <Style id="wpa3">
+    <IconStyle><color>ffe06020</color>{icon}</IconStyle>
+  </Style>
+  <Style id="unknown">
+    <IconStyle><color>ff808080</color>{icon}</IconStyle>
+  </Style>
+  <Folder>
+    <name>Open</name>
+    <Placemark>
+      <name>66:77:88:99:aa:bb</name>
+      <description>BSSID: 66:77:88:99:aa:bb
+Channel: 6
+Signal: Unknown
+Security: Open
+Observations: 1</description>
+      <styleUrl>#open</styleUrl>
+      <ExtendedData>
+        <Data name="bssid"><value>66:77:88:99:aa:bb</value></Data>
+        <Data name="ssid"><value></value></Data>
+        <Data name="channel"><value>6</value></Data>
+        <Data name="signal_level"><value></value></Data>
+        <Data name="security"><value>Open</value></Data>
+        <Data name="securities"><value>Open</value></Data>
+        <Data name="first_seen"><value>2024-05-01T12:30:05.000Z</value></Data>
+        <Data name="last_seen"><value>2024-05-01T12:30:05.000Z</value></Data>
+        <Data name="observations"><value>1</value></Data>
+      </ExtendedData>
+      <Point><coordinates>13.4,52.5</coordinates></Point>
+    </Placemark>
+  </Folder>
+  <Folder>
+    <name>WPA2</name>
+    <Placemark>
+      <name>Home &amp; &lt;Garden&gt;</name>
+      <description>BSSID: 00:11:22:33:44:55
+Channel: 6
+Signal: -50 dBm
+Security: WPA2-Personal (PSK)
+Observations: 2</description>
+      <styleUrl>#wpa2</styleUrl>
+      <ExtendedData>
+        <Data name="bssid"><value>00:11:22:33:44:55</value></Data>
+        <Data name="ssid"><value>Home &amp; &lt;Garden&gt;</value></Data>
+        <Data name="channel"><value>6</value></Data>
+        <Data name="signal_level"><value>-50</value></Data>
+        <Data name="security"><value>WPA2</value></Data>
+        <Data name="securities"><value>WPA2-Personal (PSK)</value></Data>
+        <Data name="first_seen"><value>2024-05-01T12:30:00.000Z</value></Data>
+        <Data name="last_seen"><value>2024-05-01T12:30:10.000Z</value></Data>
+        <Data name="observations"><value>2</value></Data>
+      </ExtendedData>
+      <Point><coordinates>13.5,52.6</coordinates></Point>
+    </Placemark>
+  </Folder>
+</Document>
+</kml>
+"#
+    );
+    assert_eq!(write(&observations), expected);
+}
+
+#[test]
+fn security_classes() {
+    let class = |security: &[WifiSecurity]| {
+        let mut wifi = Wifi::default();
+        wifi.security = security.to_vec();
+        MapSecurityClass::of(&wifi)
+    };
+    assert_eq!(
+        class(&[WifiSecurity::Wpa2PersonalPsk, WifiSecurity::Wpa3PersonalSae]),
+        MapSecurityClass::Wpa3
+    );
+    assert_eq!(
+        class(&[WifiSecurity::WpaEnterpriseEap]),
+        MapSecurityClass::Wpa
+    );
+    assert_eq!(class(&[WifiSecurity::Wep]), MapSecurityClass::Wep);
+    assert_eq!(class(&[]), MapSecurityClass::Unknown);
+
+    // OWE networks report an unknown security
+    let mut owe = Wifi::default();
+    owe.security = vec![WifiSecurity::Unknown];
+    owe.set_information_elements(vec![
+        48, 18, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00,
+        0x00, 0x0f, 0xac, 18,
+    ]);
+    assert_eq!(MapSecurityClass::of(&owe), MapSecurityClass::Wpa3);
+    assert_eq!(MapSecurityClass::Wpa3.color(), [0x20, 0x60, 0xe0]);
+}
+
+#[test]
+fn empty() {
+    let text = write(&[]);
+    assert!(!text.contains("<Folder>"));
+    assert!(text.ends_with("</Document>\n</kml>\n"));
+}
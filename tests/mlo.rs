@@ -0,0 +1,205 @@
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{
+    mlo::{group_multi_link, AffiliatedLink},
+    neighbor::MldParameters,
+    Band, Wifi,
+};
+
+const MLD_MAC: [u8; 6] = [0x02, 0x11, 0x22, 0x33, 0x44, 0x00];
+const LINK_5GHZ: &str = "00:11:22:33:44:01";
+const LINK_6GHZ: [u8; 6] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x02];
+const LINK_2GHZ: [u8; 6] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x03];
+
+/// Basic Multi-Link element with link ID 0 and a Per-STA Profile for link 2
+fn multi_link() -> Vec<u8> {
+    // extension ID, Basic variant with Link ID Info present
+    let mut body = vec![107, 0x10, 0x00];
+    // Common Info: length, MLD MAC address, link ID
+    body.push(8);
+    body.extend_from_slice(&MLD_MAC);
+    body.push(0x00);
+    // Per-STA Profile: link ID 2 with STA MAC address
+    let mut profile = vec![0x22, 0x00, 7];
+    profile.extend_from_slice(&LINK_2GHZ);
+    body.extend(element(0, &profile));
+    element(255, &body)
+}
+
+/// Reduced Neighbor Report with link 1 of the same MLD on 6 GHz channel 37
+fn reduced_neighbor_report() -> Vec<u8> {
+    // one TBTT Information field of 16 bytes, operating class 131, channel 37
+    let mut body = vec![0x00, 0x10, 131, 37];
+    body.push(0xff);
+    body.extend_from_slice(&LINK_6GHZ);
+    body.extend_from_slice(&[0x78, 0x56, 0x34, 0x12]);
+    // BSS parameters: same SSID, co-located
+    body.push(0x42);
+    body.push(0xfe);
+    // MLD ID 0, link ID 1
+    body.extend_from_slice(&[0x00, 0x01, 0x00]);
+    element(201, &body)
+}
+
+fn affiliated_ap() -> Wifi {
+    let mut ies = element(0, b"Corp");
+    ies.extend(reduced_neighbor_report());
+    ies.extend(multi_link());
+    wifi(LINK_5GHZ)
+        .ssid("Corp")
+        .channel(36)
+        .elements(ies)
+        .build()
+}
+
+#[test]
+fn multi_link_info() {
+    let wifi = affiliated_ap();
+    assert_eq!(wifi.mld_mac().as_deref(), Some("02:11:22:33:44:00"));
+
+    let info = wifi.multi_link().unwrap();
+    assert_eq!(
+        info.links,
+        vec![
+            AffiliatedLink {
+                link_id: Some(0),
+                band: Some(Band::FiveGhz),
+                channel: 36,
+                bssid: LINK_5GHZ.to_string(),
+            },
+            AffiliatedLink {
+                link_id: Some(1),
+                band: Some(Band::SixGhz),
+                channel: 37,
+                bssid: "00:11:22:33:44:02".to_string(),
+            },
+            AffiliatedLink {
+                link_id: Some(2),
+                band: None,
+                channel: 0,
+                bssid: "00:11:22:33:44:03".to_string(),
+            },
+        ]
+    );
+
+    let neighbors = wifi.reduced_neighbor_report();
+    assert_eq!(neighbors.len(), 1);
+    assert_eq!(neighbors[0].short_ssid, Some(0x12345678));
+    assert!(neighbors[0].same_ssid && neighbors[0].co_located);
+    assert_eq!(
+        neighbors[0].mld,
+        Some(MldParameters {
+            mld_id: 0,
+            link_id: 1
+        })
+    );
+}
+
+#[test]
+fn group_links() {
+    let link_6ghz = wifi("00:11:22:33:44:02")
+        .ssid("Corp")
+        .channel(37)
+        .elements(element(0, b"Corp"))
+        .build();
+    let other = wifi("00:aa:bb:cc:dd:ee")
+        .ssid("Corp")
+        .channel(1)
+        .elements(element(0, b"Guest"))
+        .build();
+    let wifis = vec![other, affiliated_ap(), link_6ghz];
+
+    let groups = group_multi_link(&wifis);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].mld_mac, "02:11:22:33:44:00");
+    assert_eq!(groups[0].links.len(), 3);
+    assert_eq!(groups[0].bands(), vec![Band::FiveGhz, Band::SixGhz]);
+    let macs: Vec<&str> = groups[0].bsses.iter().map(|w| w.mac.as_str()).collect();
+    assert_eq!(macs, vec![LINK_5GHZ, "00:11:22:33:44:02"]);
+
+    assert!(group_multi_link(&wifis[..1]).is_empty());
+}
+
+#[test]
+fn group_links_without_id() {
+    // Basic Multi-Link element without Link ID Info
+    let mut body = vec![107, 0x00, 0x00, 7];
+    body.extend_from_slice(&MLD_MAC);
+    let multi_link = element(255, &body);
+    let wifis = vec![
+        wifi(LINK_5GHZ)
+            .channel(36)
+            .elements(multi_link.clone())
+            .build(),
+        wifi("00:11:22:33:44:02")
+            .channel(1)
+            .elements(multi_link)
+            .build(),
+    ];
+
+    let groups = group_multi_link(&wifis);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].links.len(), 2);
+    assert_eq!(groups[0].links[1].link_id, None);
+    assert_eq!(groups[0].links[1].bssid, "00:11:22:33:44:02");
+    assert_eq!(
+        groups[0].bands(),
+        vec![Band::TwoPointFourGhz, Band::FiveGhz]
+    );
+}
+
+#[test]
+fn malformed_multi_link() {
+    // too short for the Common Info
+    let short = wifi(LINK_5GHZ)
+        .elements(element(255, &[107, 0x10, 0x00, 8, 0x02, 0x11]))
+        .build();
+    assert_eq!(short.multi_link(), None);
+
+    // Probe Request variant without MLD MAC address
+    let mut probe = multi_link();
+    probe[3] = 0x11;
+    assert_eq!(wifi(LINK_5GHZ).elements(probe).build().multi_link(), None);
+
+    // Common Info length beyond the element
+    let mut overlong = multi_link();
+    overlong[5] = 40;
+    assert_eq!(
+        wifi(LINK_5GHZ).elements(overlong).build().multi_link(),
+        None
+    );
+
+    // truncated Per-STA Profile is ignored
+    let mut truncated = multi_link();
+    truncated.truncate(truncated.len() - 3);
+    truncated[1] -= 3;
+    let info = wifi(LINK_5GHZ)
+        .elements(truncated)
+        .build()
+        .multi_link()
+        .unwrap();
+    assert_eq!(info.links.len(), 1);
+    assert_eq!(info.links[0].link_id, Some(0));
+}
+
+#[test]
+fn malformed_reduced_neighbor_report() {
+    // TBTT Information fields shorter than announced
+    let mut rnr = reduced_neighbor_report();
+    rnr.truncate(10);
+    rnr[1] = 8;
+    assert!(wifi(LINK_5GHZ)
+        .elements(rnr)
+        .build()
+        .reduced_neighbor_report()
+        .is_empty());
+
+    // zero length TBTT Information fields
+    let rnr = element(201, &[0x00, 0x00, 131, 37]);
+    assert!(wifi(LINK_5GHZ)
+        .elements(rnr)
+        .build()
+        .reduced_neighbor_report()
+        .is_empty());
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{neighbor::expand_inferred, Band, Wifi, WifiSecurity};
+
+const TRANSMITTED: &str = "00:11:22:33:44:fe";
+/// RSN element body with CCMP and PSK
+const RSN_PSK: [u8; 20] = [
+    0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f,
+    0xac, 0x02, 0x00, 0x00,
+];
+
+/// Nontransmitted BSSID Profile subelement
+fn profile(capability: u8, ssid: &[u8], index: u8, extra: &[u8]) -> Vec<u8> {
+    let mut body = element(83, &[capability, 0x00]);
+    body.extend(element(0, ssid));
+    body.extend(element(85, &[index, 1, 0]));
+    body.extend_from_slice(extra);
+    element(0, &body)
+}
+
+/// Multiple BSSID element for up to 8 BSSes with a secured and an open profile,
+/// the latter not inheriting the RSN element
+fn multiple_bssid() -> Vec<u8> {
+    let mut body = vec![3];
+    body.extend(profile(0x11, b"Guest", 1, &[]));
+    // Non-Inheritance of the RSN element
+    body.extend(profile(0x01, b"IoT", 3, &[255, 4, 56, 1, 48, 0]));
+    element(71, &body)
+}
+
+fn rates() -> Vec<u8> {
+    element(1, &[0x82, 0x84, 0x8b, 0x96])
+}
+
+fn transmitted() -> Wifi {
+    let mut ies = element(0, b"Corp");
+    ies.extend(rates());
+    ies.extend(element(48, &RSN_PSK));
+    ies.extend(multiple_bssid());
+
+    wifi(TRANSMITTED)
+        .ssid("Corp")
+        .channel(36)
+        .signal(-52)
+        .security(&[WifiSecurity::Wpa2PersonalPsk])
+        .elements(ies)
+        .build()
+}
+
+#[test]
+fn nontransmitted_bsses() {
+    let bsses = transmitted().nontransmitted_bsses();
+    assert_eq!(bsses.len(), 2);
+
+    // BSSID indices wrap around within the low bits of the transmitted BSSID
+    assert_eq!(bsses[0].bssid, "00:11:22:33:44:ff");
+    assert_eq!(bsses[0].bssid_index, 1);
+    assert_eq!(bsses[0].ssid, "Guest");
+    assert!(bsses[0].privacy);
+    let mut inherited = element(0, b"Guest");
+    inherited.extend(rates());
+    inherited.extend(element(48, &RSN_PSK));
+    assert_eq!(bsses[0].information_elements, inherited);
+
+    assert_eq!(bsses[1].bssid, "00:11:22:33:44:f9");
+    assert_eq!(bsses[1].ssid, "IoT");
+    assert!(!bsses[1].privacy);
+    let mut inherited = element(0, b"IoT");
+    inherited.extend(rates());
+    assert_eq!(bsses[1].information_elements, inherited);
+}
+
+#[test]
+fn inferred_from_multiple_bssid() {
+    let wifi = transmitted();
+    let inferred = wifi.inferred_bsses();
+    assert_eq!(inferred.len(), 2);
+    assert!(inferred.iter().all(|w| w.inferred));
+    assert!(inferred
+        .iter()
+        .all(|w| w.channel == 36 && w.signal_level == -52));
+    assert_eq!(inferred[0].security, vec![WifiSecurity::Wpa2PersonalPsk]);
+    assert_eq!(inferred[1].security, vec![WifiSecurity::Open]);
+    assert_eq!(inferred[1].band(), Some(Band::FiveGhz));
+}
+
+#[test]
+fn reduced_neighbor_report_layouts() {
+    let mut body = Vec::new();
+    // two 7 byte fields with BSSIDs on operating class 81, channel 1
+    body.extend_from_slice(&[0x10, 0x07, 81, 1]);
+    body.extend_from_slice(&[0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x01]);
+    body.extend_from_slice(&[0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x02]);
+    // a 2 byte field with BSS parameters only
+    body.extend_from_slice(&[0x00, 0x02, 115, 40, 0xff, 0x02]);
+    // a 12 byte field with BSSID, short SSID and BSS parameters on 6 GHz channel 5
+    body.extend_from_slice(&[0x00, 0x0c, 131, 5, 0xff]);
+    body.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x06]);
+    body.extend_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x4e]);
+
+    let wifi = wifi(TRANSMITTED).elements(element(201, &body)).build();
+    let neighbors = wifi.reduced_neighbor_report();
+    assert_eq!(neighbors.len(), 4);
+
+    assert_eq!(neighbors[0].bssid, "00:11:22:33:44:01");
+    assert_eq!(neighbors[1].bssid, "00:11:22:33:44:02");
+    assert_eq!(neighbors[1].band, Some(Band::TwoPointFourGhz));
+    assert_eq!(neighbors[1].short_ssid, None);
+    assert!(!neighbors[1].same_ssid);
+
+    assert_eq!(neighbors[2].bssid, "");
+    assert_eq!(
+        (neighbors[2].operating_class, neighbors[2].channel),
+        (115, 40)
+    );
+    assert_eq!(neighbors[2].band, Some(Band::FiveGhz));
+    assert!(neighbors[2].same_ssid);
+
+    let six_ghz = &neighbors[3];
+    assert_eq!(six_ghz.band, Some(Band::SixGhz));
+    assert_eq!(six_ghz.short_ssid, Some(0x04030201));
+    assert!(six_ghz.same_ssid && six_ghz.multiple_bssid && six_ghz.transmitted_bssid);
+    assert!(six_ghz.co_located);
+    assert_eq!(six_ghz.mld, None);
+}
+
+#[test]
+fn inferred_from_reduced_neighbor_report() {
+    let mut body = Vec::new();
+    // 6 GHz neighbor with the same SSID
+    body.extend_from_slice(&[0x00, 0x0c, 131, 5, 0xff]);
+    body.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x06]);
+    body.extend_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x42]);
+    // the reporting AP itself, a non-transmitted BSS and a neighbor without BSSID
+    body.extend_from_slice(&[
+        0x10, 0x07, 115, 36, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0xfe,
+    ]);
+    body.extend_from_slice(&[0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0xff]);
+    body.extend_from_slice(&[0x00, 0x02, 81, 6, 0xff, 0x00]);
+
+    let mut wifi = transmitted();
+    let mut ies = wifi.information_elements().to_vec();
+    ies.extend(element(201, &body));
+    wifi.set_information_elements(ies);
+
+    let inferred = wifi.inferred_bsses();
+    assert_eq!(inferred.len(), 3);
+    let neighbor = &inferred[2];
+    assert_eq!(neighbor.mac, "00:11:22:33:44:06");
+    assert_eq!(neighbor.ssid, "Corp");
+    assert_eq!(neighbor.channel, 5);
+    assert_eq!(neighbor.frequency, 5975);
+    assert_eq!(neighbor.get_frequency(), 5975);
+    assert_eq!(neighbor.band(), Some(Band::SixGhz));
+    assert_eq!(neighbor.signal_level, 0);
+    assert_eq!(neighbor.security, vec![WifiSecurity::Unknown]);
+    assert!(neighbor.inferred);
+    assert!(neighbor.information_elements().is_empty());
+    assert_eq!(neighbor.phy(), None);
+}
+
+#[test]
+fn expand_without_duplicates() {
+    let seen = wifi("00:11:22:33:44:FF").ssid("Guest").signal(-60).build();
+
+    let expanded = expand_inferred(&[transmitted(), seen.clone()]);
+    assert_eq!(expanded.len(), 3);
+    assert_eq!(expanded[1], seen);
+    assert_eq!(expanded[2].mac, "00:11:22:33:44:f9");
+    assert!(expanded[2].inferred);
+}
+
+#[test]
+fn malformed_multiple_bssid() {
+    let mut wifi = transmitted();
+
+    // profile without BSSID index, empty element and truncated profile
+    let mut body = vec![3];
+    body.extend(element(0, &element(0, b"NoIndex")));
+    let mut ies = element(71, &body);
+    ies.extend(element(71, &[]));
+    let mut truncated = multiple_bssid();
+    truncated.truncate(12);
+    truncated[1] = 10;
+    ies.extend(truncated);
+    wifi.set_information_elements(ies);
+    assert!(wifi.nontransmitted_bsses().is_empty());
+
+    // no BSSID to derive the non-transmitted BSSIDs from
+    let mut wifi = transmitted();
+    wifi.mac = String::new();
+    assert!(wifi.nontransmitted_bsses().is_empty());
+}
@@ -0,0 +1,212 @@
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    export::{netxml, GpsFix, Observation, ScanRecord},
+    WifiSecurity,
+};
+
+fn observation(
+    mac: &str,
+    ssid: &str,
+    security: &[WifiSecurity],
+    signal_level: i32,
+    seconds: u64,
+    fix: GpsFix,
+) -> Observation {
+    let wifi = wifi(mac)
+        .ssid(ssid)
+        .channel(6)
+        .signal(signal_level)
+        .security(security)
+        .build();
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_600 + seconds);
+    Observation::new(ScanRecord::new(wifi, time), fix)
+}
+
+fn fix(latitude: f64, longitude: f64) -> GpsFix {
+    GpsFix {
+        altitude: Some(34.5),
+        ..GpsFix::new(latitude, longitude)
+    }
+}
+
+fn write(observations: &[Observation]) -> String {
+    let mut buffer = Vec::new();
+    netxml::write(&mut buffer, observations).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn golden_output() {
+    let psk = [WifiSecurity::Wpa2PersonalPsk];
+    let observations = vec![
+        observation(
+            "00-11-22-33-44-55",
+            "Home & <Garden>",
+            &psk,
+            -70,
+            0,
+            fix(52.5, 13.4),
+        ),
+        // two weeks later, ctime pads single digit days only
+        observation(
+            "66:77:88:99:aa:bb",
+            "",
+            &[WifiSecurity::Open],
+            -80,
+            14 * 86400,
+            fix(52.5, 13.4),
+        ),
+        observation(
+            "00:11:22:33:44:55",
+            "Home & <Garden>",
+            &psk,
+            -50,
+            10,
+            fix(52.6, 13.5),
+        ),
+    ];
+
+    let expected = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE detection-run SYSTEM "http://kismetwireless.net/kismet-3.1.0.dtd">
+<detection-run kismet-version="wifi_scan "#,
+        env!("CARGO_PKG_VERSION"),
+        r#"" start-time="Wed May  1 12:30:00 2024">
+  <wireless-network number="1" type="infrastructure" first-time="Wed May  1 12:30:00 2024" last-time="Wed May  1 12:30:10 2024">
+    <SSID first-time="Wed May  1 12:30:00 2024" last-time="Wed May  1 12:30:10 2024">
+      <type>Beacon</type>
+      <packets>2</packets>
+      <encryption>WPA+PSK</encryption>
+      <essid cloaked="false">Home &amp; &lt;Garden&gt;</essid>
+    </SSID>
+    <BSSID>00:11:22:33:44:55</BSSID>
+    <manuf>Unknown</manuf>
+    <channel>6</channel>
+    <freqmhz>2437 2</freqmhz>
+    <carrier>IEEE 802.11b+</carrier>
+    <packets>2</packets>
+    <datasize>0</datasize>
+    <snr-info>
+      <last_signal_dbm>-50</last_signal_dbm>
+      <last_noise_dbm>0</last_noise_dbm>
+      <min_signal_dbm>-70</min_signal_dbm>
+      <min_noise_dbm>0</min_noise_dbm>
+      <max_signal_dbm>-50</max_signal_dbm>
+      <max_noise_dbm>0</max_noise_dbm>
+    </snr-info>
+    <gps-info>
+      <min-lat>52.500000</min-lat>
+      <min-lon>13.400000</min-lon>
+      <min-alt>34.500000</min-alt>
+      <min-spd>0.000000</min-spd>
+      <max-lat>52.600000</max-lat>
+      <max-lon>13.500000</max-lon>
+      <max-alt>34.500000</max-alt>
+      <max-spd>0.000000</max-spd>
+      <peak-lat>52.600000</peak-lat>
+      <peak-lon>13.500000</peak-lon>
+      <peak-alt>34.500000</peak-alt>
+      <avg-lat>52.550000</avg-lat>
+      <avg-lon>13.450000</avg-lon>
+      <avg-alt>34.500000</avg-alt>
+    </gps-info>
+  </wireless-network>
+  <wireless-network number="2" type="infrastructure" first-time="Wed May 15 12:30:00 2024" last-time="Wed May 15 12:30:00 2024">
+    <SSID first-time="Wed May 15 12:30:00 2024" last-time="Wed May 15 12:30:00 2024">
+      <type>Beacon</type>
+      <packets>1</packets>
+      <encryption>None</encryption>
+      <essid cloaked="true"></essid>
+    </SSID>
+    <BSSID>66:77:88:99:AA:BB</BSSID>
+    <manuf>Unknown</manuf>
+    <channel>6</channel>
+    <freqmhz>2437 1</freqmhz>
+    <carrier>IEEE 802.11b+</carrier>
+    <packets>1</packets>
+    <datasize>0</datasize>
+    <snr-info>
+      <last_signal_dbm>-80</last_signal_dbm>
+      <last_noise_dbm>0</last_noise_dbm>
+      <min_signal_dbm>-80</min_signal_dbm>
+      <min_noise_dbm>0</min_noise_dbm>
+      <max_signal_dbm>-80</max_signal_dbm>
+      <max_noise_dbm>0</max_noise_dbm>
+    </snr-info>
+    <gps-info>
+      <min-lat>52.500000</min-lat>
+      <min-lon>13.400000</min-lon>
+      <min-alt>34.500000</min-alt>
+      <min-spd>0.000000</min-spd>
+      <max-lat>52.500000</max-lat>
+      <max-lon>13.400000</max-lon>
+      <max-alt>34.500000</max-alt>
+      <max-spd>0.000000</max-spd>
+      <peak-lat>52.500000</peak-lat>
+      <peak-lon>13.400000</peak-lon>
+      <peak-alt>34.500000</peak-alt>
+      <avg-lat>52.500000</avg-lat>
+      <avg-lon>13.400000</avg-lon>
+      <avg-alt>34.500000</avg-alt>
+    </gps-info>
+  </wireless-network>
+</detection-run>
+"#
+    );
+    assert_eq!(write(&observations), expected);
+}
+
+#[test]
+fn ctime() {
+    let time = |seconds: u64| {
+        let mut observation =
+            observation("00:11:22:33:44:55", "Home", &[], -50, 0, fix(52.5, 13.4));
+        observation.record.timestamp = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds));
+        write(&[observation])
+    };
+    // a Sunday at the end of the year
+    assert!(time(1_704_067_199).contains(r#"start-time="Sun Dec 31 23:59:59 2023""#));
+    assert!(time(0).contains(r#"start-time="Thu Jan  1 00:00:00 1970""#));
+}
+
+#[test]
+fn encryption() {
+    let mut mixed = observation(
+        "00:11:22:33:44:55",
+        "Home",
+        &[
+            WifiSecurity::Wpa2PersonalPsk,
+            WifiSecurity::Wpa3PersonalSae,
+            WifiSecurity::Wpa2EnterpriseEap,
+            WifiSecurity::Wpa3EnterpriseEap,
+        ],
+        -50,
+        0,
+        fix(52.5, 13.4),
+    );
+    // RSN element with CCMP as group and pairwise cipher
+    mixed.record.wifi.set_information_elements(vec![
+        48, 14, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x00, 0x00,
+    ]);
+    let text = write(&[mixed]);
+    let encryption: Vec<&str> = text
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("<encryption>"))
+        .filter_map(|l| l.strip_suffix("</encryption>"))
+        .collect();
+    assert_eq!(
+        encryption,
+        vec!["WPA+PSK", "WPA+SAE", "WPA+MGT", "WPA+AES-CCM"]
+    );
+}
+
+#[test]
+fn empty() {
+    let text = write(&[]);
+    assert!(text.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(text.ends_with("\">\n</detection-run>\n"));
+}
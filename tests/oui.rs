@@ -0,0 +1,112 @@
+#![cfg(feature = "oui")]
+
+use std::io::Cursor;
+
+mod common;
+
+use common::wifi;
+use wifi_scan::oui::{set_database, OuiDatabase};
+
+const OUI_CSV: &str = "\
+Registry,Assignment,Organization Name,Organization Address
+MA-L,00000C,\"Cisco Systems, Inc\",170 West Tasman Dr. San Jose CA US 95134
+MA-L,001BC5,IEEE Registration Authority,445 Hoes Lane Piscataway NJ US 08854
+MA-M,70B3D5123,Some Company,Somewhere
+";
+
+const MANUF: &str = "\
+# Wireshark manuf file
+00:00:0C\tCisco\tCisco Systems, Inc
+00-27-22\tUbiquiti
+00:1B:C5:00:00:00/36\tSmall\tSmall block
+FC:EC:DA/24\tUbiquiti\tUbiquiti Inc # comment
+";
+
+#[test]
+fn embedded_database() {
+    let database = OuiDatabase::embedded();
+    assert!(!database.is_empty());
+    assert_eq!(database.lookup("00:00:0C:12:34:56"), Some("Cisco Systems"));
+    assert_eq!(database.lookup_oui([0x00, 0x27, 0x22]), Some("Ubiquiti"));
+    assert_eq!(database.lookup("not a mac"), None);
+}
+
+#[test]
+fn ieee_csv() {
+    let database = OuiDatabase::from_csv(Cursor::new(OUI_CSV)).unwrap();
+    assert_eq!(database.len(), 2);
+    assert_eq!(
+        database.lookup("00:00:0c:00:00:01"),
+        Some("Cisco Systems, Inc")
+    );
+    assert_eq!(
+        database.lookup_oui([0x00, 0x1b, 0xc5]),
+        Some("IEEE Registration Authority")
+    );
+    assert_eq!(database.lookup("70:b3:d5:12:34:56"), None);
+}
+
+#[test]
+fn wireshark_manuf() {
+    let database = OuiDatabase::from_manuf(Cursor::new(MANUF)).unwrap();
+    assert_eq!(database.len(), 3);
+    assert_eq!(
+        database.lookup_oui([0x00, 0x00, 0x0c]),
+        Some("Cisco Systems, Inc")
+    );
+    // the short name is used if there is no long name
+    assert_eq!(database.lookup_oui([0x00, 0x27, 0x22]), Some("Ubiquiti"));
+    assert_eq!(
+        database.lookup_oui([0xfc, 0xec, 0xda]),
+        Some("Ubiquiti Inc")
+    );
+    assert_eq!(database.lookup_oui([0x00, 0x1b, 0xc5]), None);
+}
+
+#[test]
+fn load_detects_format() {
+    let directory = std::env::temp_dir();
+    let csv = directory.join(format!("wifi_scan_oui_{}.csv", std::process::id()));
+    let manuf = directory.join(format!("wifi_scan_manuf_{}", std::process::id()));
+    std::fs::write(&csv, OUI_CSV).unwrap();
+    std::fs::write(&manuf, MANUF).unwrap();
+
+    let from_csv = OuiDatabase::load(&csv);
+    let from_manuf = OuiDatabase::load(&manuf);
+    std::fs::remove_file(&csv).unwrap();
+    std::fs::remove_file(&manuf).unwrap();
+
+    assert_eq!(from_csv.unwrap().len(), 2);
+    assert_eq!(from_manuf.unwrap().len(), 3);
+    assert!(OuiDatabase::load(directory.join("wifi_scan_missing_oui.csv")).is_err());
+}
+
+#[test]
+fn wifi_vendor() {
+    // globally administered BSSID
+    let cisco = wifi("00:40:96:01:02:03").build();
+    assert_eq!(cisco.vendor().as_deref(), Some("Cisco Systems"));
+
+    // locally administered BSSID: WPS and chipset elements rank below the AP vendor
+    let mut ies = vec![221, 5, 0x00, 0x50, 0xf2, 0x04, 0x10];
+    ies.extend_from_slice(&[221, 5, 0x00, 0x10, 0x18, 0x02, 0x00]);
+    ies.extend_from_slice(&[221, 5, 0x00, 0x0b, 0x86, 0x01, 0x00]);
+    let aruba = wifi("02:0b:86:01:02:03").elements(ies).build();
+    assert_eq!(aruba.vendor().as_deref(), Some("Aruba"));
+
+    let chipset_only = wifi("06:11:22:33:44:55")
+        .elements(vec![221, 5, 0x00, 0x10, 0x18, 0x02, 0x00])
+        .build();
+    assert_eq!(chipset_only.vendor().as_deref(), Some("Broadcom"));
+    assert_eq!(wifi("06:11:22:33:44:55").build().vendor(), None);
+    assert_eq!(wifi("").build().vendor(), None);
+
+    // a runtime database replaces the embedded one
+    set_database(OuiDatabase::from_csv(Cursor::new(OUI_CSV)).unwrap());
+    assert_eq!(
+        wifi("00:00:0c:01:02:03").build().vendor().as_deref(),
+        Some("Cisco Systems, Inc")
+    );
+    assert_eq!(cisco.vendor(), None);
+    set_database(OuiDatabase::embedded());
+}
@@ -0,0 +1,430 @@
+use std::{
+    io::Cursor,
+    time::{Duration, UNIX_EPOCH},
+};
+
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{
+    export::pcapng::PcapngWriter,
+    pcap::{self, PcapReader, PcapScanner, LINKTYPE_IEEE802_11, LINKTYPE_PPI},
+    radiotap::Radiotap,
+    Error, Wifi, WifiSecurity, WlanScanner,
+};
+
+const BSSID: [u8; 6] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55];
+const BEACON: u8 = 8;
+const PROBE_RESPONSE: u8 = 5;
+/// RSN element body with CCMP and PSK
+const RSN_PSK: [u8; 20] = [
+    0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f,
+    0xac, 0x02, 0x00, 0x00,
+];
+
+/// Builds a beacon or probe response frame
+fn frame(subtype: u8, bssid: [u8; 6], privacy: bool, ies: &[u8]) -> Vec<u8> {
+    let mut frame = vec![subtype << 4, 0x00, 0x00, 0x00];
+    frame.extend_from_slice(&[0xff; 6]);
+    frame.extend_from_slice(&bssid);
+    frame.extend_from_slice(&bssid);
+    frame.extend_from_slice(&[0x00, 0x00]);
+    // timestamp, beacon interval, capability information
+    frame.extend_from_slice(&[0; 8]);
+    frame.extend_from_slice(&[0x64, 0x00, if privacy { 0x11 } else { 0x01 }, 0x00]);
+    frame.extend_from_slice(ies);
+    frame
+}
+
+/// Builds a radiotap header with TSFT, flags, channel and antenna signal behind an
+/// empty extended presence bitmap
+fn radiotap(frequency: u16, signal: i8, flags: u8) -> Vec<u8> {
+    let mut header = vec![0x00, 0x00, 31, 0x00];
+    header.extend_from_slice(&(0x8000_002bu32).to_le_bytes());
+    header.extend_from_slice(&0u32.to_le_bytes());
+    header.extend_from_slice(&[0; 4]);
+    header.extend_from_slice(&123_456u64.to_le_bytes());
+    header.push(flags);
+    header.push(0x00);
+    header.extend_from_slice(&frequency.to_le_bytes());
+    header.extend_from_slice(&0x00a0u16.to_le_bytes());
+    header.push(signal as u8);
+    header
+}
+
+fn pcap_file(link_type: u32, packets: &[(u32, u32, Vec<u8>)]) -> Vec<u8> {
+    let mut file = Vec::new();
+    file.extend_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+    file.extend_from_slice(&[0x02, 0x00, 0x04, 0x00]);
+    file.extend_from_slice(&[0; 8]);
+    file.extend_from_slice(&65535u32.to_le_bytes());
+    file.extend_from_slice(&link_type.to_le_bytes());
+    for (seconds, microseconds, data) in packets {
+        file.extend_from_slice(&seconds.to_le_bytes());
+        file.extend_from_slice(&microseconds.to_le_bytes());
+        file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        file.extend_from_slice(data);
+    }
+    file
+}
+
+/// Builds a big endian pcapng block
+fn block(block_type: u32, body: &[u8]) -> Vec<u8> {
+    let padding = body.len().next_multiple_of(4) - body.len();
+    let length = (12 + body.len() + padding) as u32;
+    let mut block = Vec::new();
+    block.extend_from_slice(&block_type.to_be_bytes());
+    block.extend_from_slice(&length.to_be_bytes());
+    block.extend_from_slice(body);
+    block.extend_from_slice(&vec![0; padding]);
+    block.extend_from_slice(&length.to_be_bytes());
+    block
+}
+
+/// Builds a big endian pcapng file with one interface with nanosecond timestamps
+fn pcapng_file(link_type: u16, packets: &[(u64, Vec<u8>)]) -> Vec<u8> {
+    let mut section = Vec::new();
+    section.extend_from_slice(&0x1a2b_3c4du32.to_be_bytes());
+    section.extend_from_slice(&[0x00, 0x01, 0x00, 0x00]);
+    section.extend_from_slice(&[0xff; 8]);
+    let mut file = block(0x0a0d_0d0a, &section);
+
+    let mut interface = Vec::new();
+    interface.extend_from_slice(&link_type.to_be_bytes());
+    interface.extend_from_slice(&[0; 2]);
+    interface.extend_from_slice(&65535u32.to_be_bytes());
+    // if_tsresol = 9, end of options
+    interface.extend_from_slice(&[0x00, 0x09, 0x00, 0x01, 9, 0, 0, 0, 0, 0, 0, 0]);
+    file.extend(block(1, &interface));
+
+    for (nanoseconds, data) in packets {
+        let mut packet = Vec::new();
+        packet.extend_from_slice(&0u32.to_be_bytes());
+        packet.extend_from_slice(&((nanoseconds >> 32) as u32).to_be_bytes());
+        packet.extend_from_slice(&(*nanoseconds as u32).to_be_bytes());
+        packet.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        packet.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        packet.extend_from_slice(data);
+        // an unrelated name resolution block between packets
+        file.extend(block(4, &[0; 4]));
+        file.extend(block(6, &packet));
+    }
+    file
+}
+
+#[test]
+fn pcap_with_radiotap() {
+    let mut ies = element(0, b"Corp");
+    ies.extend(element(48, &RSN_PSK));
+    let mut packet = radiotap(2437, -42, 0x10);
+    packet.extend(frame(BEACON, BSSID, true, &ies));
+    // FCS
+    packet.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+    let file = pcap_file(127, &[(1_700_000_000, 250_000, packet)]);
+    let records = pcap::read_records(Cursor::new(file)).unwrap();
+    assert_eq!(records.len(), 1);
+
+    let record = &records[0];
+    assert_eq!(
+        record.timestamp,
+        Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_250))
+    );
+    let wifi = &record.wifi;
+    assert_eq!(wifi.mac, "00:11:22:33:44:55");
+    assert_eq!(wifi.ssid, "Corp");
+    assert_eq!(wifi.channel, 6);
+    assert_eq!(wifi.signal_level, -42);
+    assert_eq!(wifi.security, vec![WifiSecurity::Wpa2PersonalPsk]);
+    assert_eq!(wifi.information_elements(), &ies[..]);
+    assert!(!wifi.inferred);
+}
+
+#[test]
+fn pcap_skips_bad_fcs_and_other_frames() {
+    let ies = element(0, b"Corp");
+    let mut bad = radiotap(5180, -60, 0x50);
+    bad.extend(frame(BEACON, BSSID, false, &ies));
+    bad.extend_from_slice(&[0; 4]);
+    // probe request
+    let mut request = radiotap(5180, -60, 0x00);
+    request.extend(frame(4, BSSID, false, &ies));
+    let mut truncated = radiotap(5180, -60, 0x00);
+    truncated.extend(&frame(BEACON, BSSID, false, &ies)[..30]);
+
+    let file = pcap_file(127, &[(0, 0, bad), (0, 0, request), (0, 0, truncated)]);
+    assert!(pcap::read_records(Cursor::new(file)).unwrap().is_empty());
+}
+
+#[test]
+fn pcapng_without_radio_header() {
+    let mut hidden = element(0, &[0; 4]);
+    hidden.extend(element(3, &[11]));
+    let mut revealed = element(0, b"Lab");
+    revealed.extend(element(3, &[11]));
+    let other = [0x02, 0x11, 0x22, 0x33, 0x44, 0x66];
+
+    let file = pcapng_file(
+        LINKTYPE_IEEE802_11 as u16,
+        &[
+            (1_000_000_001, frame(BEACON, BSSID, true, &hidden)),
+            (
+                2_000_000_002,
+                frame(BEACON, other, false, &element(0, b"Guest")),
+            ),
+            (3_000_000_003, frame(PROBE_RESPONSE, BSSID, true, &revealed)),
+            (4_000_000_004, frame(BEACON, BSSID, true, &hidden)),
+        ],
+    );
+
+    let packets: Vec<_> = PcapReader::new(Cursor::new(file.clone()))
+        .unwrap()
+        .collect::<std::io::Result<_>>()
+        .unwrap();
+    assert_eq!(packets.len(), 4);
+    assert_eq!(packets[0].link_type, LINKTYPE_IEEE802_11);
+    assert_eq!(packets[0].timestamp, Some(UNIX_EPOCH + Duration::new(1, 1)));
+
+    let records = pcap::read_records(Cursor::new(file)).unwrap();
+    assert_eq!(records.len(), 2);
+    // the hidden SSID stays revealed by the probe response
+    assert_eq!(records[0].wifi.ssid, "Lab");
+    assert_eq!(records[0].wifi.channel, 11);
+    assert_eq!(records[0].wifi.signal_level, 0);
+    assert_eq!(records[0].wifi.security, vec![WifiSecurity::Wep]);
+    assert_eq!(records[0].timestamp, Some(UNIX_EPOCH + Duration::new(4, 4)));
+    assert_eq!(records[1].wifi.mac, "02:11:22:33:44:66");
+    assert_eq!(records[1].wifi.security, vec![WifiSecurity::Open]);
+    assert_eq!(records[1].wifi.channel, 0);
+}
+
+#[test]
+fn pcap_with_ppi() {
+    let mut packet = vec![0x00, 0x00, 32, 0x00];
+    packet.extend_from_slice(&LINKTYPE_IEEE802_11.to_le_bytes());
+    // 802.11-Common with FCS present, 5745 MHz and -71 dBm
+    packet.extend_from_slice(&[0x02, 0x00, 20, 0x00]);
+    packet.extend_from_slice(&[0; 8]);
+    packet.extend_from_slice(&0x0001u16.to_le_bytes());
+    packet.extend_from_slice(&[0; 2]);
+    packet.extend_from_slice(&5745u16.to_le_bytes());
+    packet.extend_from_slice(&[0; 4]);
+    packet.extend_from_slice(&[-71i8 as u8, 0x00]);
+    packet.extend(frame(BEACON, BSSID, false, &element(0, b"Corp")));
+    packet.extend_from_slice(&[0; 4]);
+
+    let file = pcap_file(LINKTYPE_PPI, &[(0, 0, packet)]);
+    let records = pcap::read_records(Cursor::new(file)).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].wifi.channel, 149);
+    assert_eq!(records[0].wifi.signal_level, -71);
+    assert_eq!(records[0].wifi.information_elements(), element(0, b"Corp"));
+}
+
+#[test]
+fn truncated_capture() {
+    let mut packet = radiotap(2412, -50, 0x00);
+    packet.extend(frame(BEACON, BSSID, false, &element(0, b"Corp")));
+    let file = pcap_file(127, &[(0, 0, packet.clone()), (1, 0, packet)]);
+
+    // cut off in the second packet
+    let records = pcap::read_records(Cursor::new(&file[..file.len() - 10])).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].timestamp, Some(UNIX_EPOCH));
+
+    let mut reader = PcapReader::new(Cursor::new(&file[..file.len() - 10])).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_err());
+    assert!(reader.next().is_none());
+
+    // every prefix is either rejected or read without panicking
+    for length in 0..file.len() {
+        let _ = pcap::read_records(Cursor::new(&file[..length]));
+    }
+    let file = pcapng_file(127, &[(0, radiotap(2412, -50, 0x00))]);
+    for length in 0..file.len() {
+        let _ = pcap::read_records(Cursor::new(&file[..length]));
+    }
+}
+
+#[test]
+fn rejects_other_files() {
+    assert!(pcap::read_records(Cursor::new(b"not a capture".to_vec())).is_err());
+}
+
+#[test]
+fn pcap_scanner() {
+    let mut packet = radiotap(5955, -80, 0x00);
+    packet.extend(frame(BEACON, BSSID, false, &element(0, b"Corp")));
+    let path = std::env::temp_dir().join(format!("wifi_scan_test_{}.pcap", std::process::id()));
+    std::fs::write(&path, pcap_file(127, &[(0, 0, packet)])).unwrap();
+
+    let mut scanner = PcapScanner::new(&path);
+    let wifis = scanner.scan();
+    std::fs::remove_file(&path).unwrap();
+    let wifis = wifis.unwrap();
+    assert_eq!(wifis.len(), 1);
+    assert_eq!(wifis[0].channel, 1);
+    assert_eq!(wifis[0].signal_level, -80);
+
+    let mut missing = PcapScanner::new(&path);
+    assert!(matches!(missing.scan(), Err(Error::ScanFailed(_))));
+}
+
+#[test]
+fn pcapng_export_round_trip() {
+    let mut ies = element(0, b"Corp");
+    ies.extend(element(48, &RSN_PSK));
+    let corp = wifi("00:11:22:33:44:55")
+        .ssid("Corp")
+        .channel(6)
+        .frequency(2437)
+        .signal(-42)
+        .security(&[WifiSecurity::Wpa2PersonalPsk])
+        .elements(ies.clone())
+        .build();
+
+    let transition = wifi("AA:BB:CC:DD:EE:FF")
+        .ssid("Transition")
+        .channel(36)
+        .signal(-67)
+        .security(&[
+            WifiSecurity::Wpa3PersonalSae,
+            WifiSecurity::Wpa2PersonalPsk,
+            WifiSecurity::WpaPersonalPsk,
+        ])
+        .build();
+
+    let open = wifi("02:00:00:00:00:01")
+        .channel(11)
+        .security(&[WifiSecurity::Open])
+        .build();
+
+    // skipped without BSSID
+    let no_bssid = wifi("").ssid("macOS").build();
+
+    let wifis = vec![corp, transition, open, no_bssid];
+
+    let time = UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+    let mut writer = PcapngWriter::new(Vec::new()).comment("Office, 3rd floor");
+    writer.write_at(&wifis, time).unwrap();
+    writer.flush().unwrap();
+    let file = writer.into_inner();
+
+    let packets: Vec<_> = PcapReader::new(Cursor::new(&file))
+        .unwrap()
+        .collect::<std::io::Result<_>>()
+        .unwrap();
+    assert_eq!(packets.len(), 3);
+    assert!(packets
+        .iter()
+        .all(|p| p.link_type == 127 && p.timestamp == Some(time)));
+
+    let radiotap = Radiotap::parse(&packets[1].data).unwrap();
+    assert_eq!(radiotap.frequency(), Some(5180));
+    assert_eq!(radiotap.signal(), Some(-67));
+    assert_eq!(radiotap.rate, Some(12));
+    assert!(!radiotap.has_fcs());
+
+    let decoded: Vec<Wifi> = packets.iter().map(|p| p.wifi().unwrap()).collect();
+    assert_eq!(decoded[0], wifis[0]);
+    assert_eq!(decoded[0].information_elements(), &ies[..]);
+    assert_eq!(decoded[1].mac, "aa:bb:cc:dd:ee:ff");
+    assert_eq!(decoded[1].ssid, "Transition");
+    assert_eq!(decoded[1].channel, 36);
+    assert_eq!(decoded[1].signal_level, -67);
+    assert_eq!(decoded[1].security, wifis[1].security);
+    assert_eq!(decoded[2].ssid, "");
+    assert_eq!(decoded[2].channel, 11);
+    assert_eq!(decoded[2].signal_level, 0);
+    assert_eq!(decoded[2].security, vec![WifiSecurity::Open]);
+
+    let contains = |text: &str| file.windows(text.len()).any(|w| w == text.as_bytes());
+    assert!(contains("Office, 3rd floor"));
+    assert!(contains(
+        "Scan at 2023-11-14T22:13:20.123Z with 3 networks, 1 without BSSID skipped"
+    ));
+    assert!(contains("BSSID: AA:BB:CC:DD:EE:FF | SSID: Transition"));
+}
+
+#[test]
+fn pcapng_export_securities() {
+    let network =
+        |mac: &str, security: WifiSecurity| wifi(mac).channel(1).security(&[security]).build();
+    let wifis = vec![
+        network("00:11:22:33:44:01", WifiSecurity::Wep),
+        network("00:11:22:33:44:02", WifiSecurity::Unknown),
+        network(
+            "00:11:22:33:44:03",
+            WifiSecurity::Other("Vendor".to_string()),
+        ),
+        network("00:11:22:33:44:04", WifiSecurity::Wpa3EnterpriseEap),
+    ];
+
+    let mut writer = PcapngWriter::new(Vec::new());
+    writer.write(&wifis).unwrap();
+    let securities: Vec<Vec<WifiSecurity>> = PcapReader::new(Cursor::new(writer.into_inner()))
+        .unwrap()
+        .map(|p| p.unwrap().wifi().unwrap().security)
+        .collect();
+    // unknown securities are not claimed to be WEP
+    assert_eq!(
+        securities,
+        vec![
+            vec![WifiSecurity::Wep],
+            vec![WifiSecurity::Open],
+            vec![WifiSecurity::Open],
+            // there is no dedicated AKM for WPA3-Enterprise without Suite B
+            vec![WifiSecurity::Wpa3EnterpriseEap256]
+        ]
+    );
+}
+
+#[test]
+fn pcapng_export_without_networks() {
+    let mut writer = PcapngWriter::new(Vec::new());
+    writer.flush().unwrap();
+    let file = writer.into_inner();
+    assert_eq!(PcapReader::new(Cursor::new(file)).unwrap().count(), 0);
+}
+
+#[test]
+fn pcapng_export_reported_frequency() {
+    // channel 1 is ambiguous between 2.4 GHz and 6 GHz
+    let wifis = vec![wifi("00:11:22:33:44:55").channel(1).frequency(5955).build()];
+    let mut writer = PcapngWriter::new(Vec::new());
+    writer.write(&wifis).unwrap();
+    let packet = PcapReader::new(Cursor::new(writer.into_inner()))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    let radiotap = Radiotap::parse(&packet.data).unwrap();
+    assert_eq!(radiotap.frequency(), Some(5955));
+    assert_eq!(radiotap.rate, Some(12));
+}
+
+#[test]
+fn pcapng_export_scans_without_packets() {
+    let no_bssid = vec![wifi("").ssid("macOS").build()];
+    let open = vec![wifi("02:00:00:00:00:01").channel(11).build()];
+    let first = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let second = first + Duration::from_secs(60);
+    let third = second + Duration::from_secs(60);
+
+    let mut writer = PcapngWriter::new(Vec::new());
+    writer.write_at(&no_bssid, first).unwrap();
+    writer.write_at(&open, second).unwrap();
+    writer.write_at(&[], third).unwrap();
+    writer.flush().unwrap();
+    let file = writer.into_inner();
+
+    let contains = |text: &str| file.windows(text.len()).any(|w| w == text.as_bytes());
+    assert!(contains(
+        "Scan at 2023-11-14T22:13:20.000Z with 0 networks, 1 without BSSID skipped"
+    ));
+    assert!(contains("Scan at 2023-11-14T22:14:20.000Z with 1 networks"));
+    assert!(contains("Scan at 2023-11-14T22:15:20.000Z with 0 networks"));
+    assert_eq!(PcapReader::new(Cursor::new(&file)).unwrap().count(), 1);
+}
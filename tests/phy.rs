@@ -0,0 +1,190 @@
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{
+    phy::{PhyInfo, WifiGeneration},
+    Band, Wifi,
+};
+
+const BSSID: &str = "00:11:22:33:44:55";
+
+fn extension(ext_id: u8, data: &[u8]) -> Vec<u8> {
+    let mut body = vec![ext_id];
+    body.extend_from_slice(data);
+    element(255, &body)
+}
+
+/// HT Capabilities with 40 MHz, short GI for 20 and 40 MHz and MCS 0-15
+fn ht_capabilities() -> Vec<u8> {
+    let mut ht = vec![0u8; 26];
+    ht[0] = 0xef;
+    ht[1] = 0x01;
+    ht[3] = 0xff;
+    ht[4] = 0xff;
+    element(45, &ht)
+}
+
+/// HT Operation on channel 6 with the secondary channel above
+fn ht_operation() -> Vec<u8> {
+    let mut ht_op = vec![0u8; 22];
+    ht_op[0] = 6;
+    ht_op[1] = 0x05;
+    element(61, &ht_op)
+}
+
+/// VHT Capabilities with short GI for 80 MHz and MCS 0-9 for two streams
+fn vht_capabilities() -> Vec<u8> {
+    element(
+        191,
+        &[0x20, 0x00, 0x00, 0x00, 0xfa, 0xff, 0, 0, 0xfa, 0xff, 0, 0],
+    )
+}
+
+/// HE Capabilities supporting 80 MHz in 5 GHz with MCS 0-11 for two streams
+fn he_capabilities() -> Vec<u8> {
+    let mut he = vec![0u8; 21];
+    he[6] = 0x04;
+    he[17] = 0xfa;
+    he[18] = 0xff;
+    he[19] = 0xfa;
+    he[20] = 0xff;
+    extension(35, &he)
+}
+
+#[test]
+fn legacy_rates() {
+    let b_rates = element(1, &[0x82, 0x84, 0x8b, 0x96]);
+    let phy = PhyInfo::from_elements(&b_rates, 6).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Legacy80211b);
+    assert_eq!(phy.max_phy_rate, 11.0);
+    assert_eq!((phy.max_spatial_streams, phy.channel_width), (1, 20));
+
+    let mut g_rates = b_rates.clone();
+    // extended supported rates with a BSS membership selector that is not a rate
+    g_rates.extend(element(
+        50,
+        &[0x0c, 0x12, 0x18, 0x24, 0x30, 0x48, 0x60, 0x6c, 0xff],
+    ));
+    let phy = PhyInfo::from_elements(&g_rates, 1).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Legacy80211g);
+    assert_eq!(phy.max_phy_rate, 54.0);
+
+    let a_rates = element(1, &[0x8c, 0x12, 0x98, 0x24, 0xb0, 0x48, 0x60, 0x6c]);
+    let phy = PhyInfo::from_elements(&a_rates, 36).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Legacy80211a);
+    assert!(phy.generation.is_legacy());
+}
+
+#[test]
+fn wifi4() {
+    let mut ies = element(1, &[0x82, 0x84, 0x8b, 0x96]);
+    ies.extend(ht_capabilities());
+    ies.extend(ht_operation());
+    let phy = PhyInfo::from_elements(&ies, 6).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Wifi4);
+    assert_eq!(phy.max_spatial_streams, 2);
+    assert_eq!(phy.channel_width, 40);
+    assert_eq!(phy.max_phy_rate, 300.0);
+    assert!(!phy.generation.is_legacy());
+}
+
+#[test]
+fn wifi5() {
+    let mut ies = ht_capabilities();
+    ies.extend(vht_capabilities());
+    ies.extend(element(192, &[1, 42, 0, 0xfc, 0xff]));
+    let phy = PhyInfo::from_elements(&ies, 36).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Wifi5);
+    assert_eq!(phy.max_spatial_streams, 2);
+    assert_eq!(phy.channel_width, 80);
+    assert_eq!(phy.max_phy_rate, 866.7);
+
+    // 160 MHz signalled through the second center frequency segment, without short GI
+    let mut ies = ht_capabilities();
+    ies.extend(vht_capabilities());
+    ies.extend(element(192, &[1, 42, 50, 0xfc, 0xff]));
+    let phy = PhyInfo::from_elements(&ies, 36).unwrap();
+    assert_eq!(phy.channel_width, 160);
+    assert_eq!(phy.max_phy_rate, 1560.0);
+}
+
+#[test]
+fn wifi6_and_6e() {
+    let mut ies = ht_capabilities();
+    ies.extend(vht_capabilities());
+    ies.extend(he_capabilities());
+    let phy = PhyInfo::from_elements(&ies, 36).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Wifi6);
+    assert_eq!(phy.max_spatial_streams, 2);
+    assert_eq!(phy.channel_width, 80);
+    assert_eq!(phy.max_phy_rate, 1201.0);
+
+    // 6 GHz Operation Information on channel 37 with 80 MHz
+    let mut ies = he_capabilities();
+    ies.extend(extension(
+        36,
+        &[0x00, 0x00, 0x02, 0x00, 0xfc, 0xff, 37, 0x02, 39, 0, 6],
+    ));
+    let wifi = wifi(BSSID).channel(37).elements(ies).build();
+    let phy = wifi.phy().unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Wifi6E);
+    assert_eq!(phy.channel_width, 80);
+    assert_eq!(wifi.band(), Some(Band::SixGhz));
+}
+
+#[test]
+fn wifi7() {
+    let mut eht = vec![0u8; 14];
+    // 320 MHz in 6 GHz
+    eht[2] = 0x02;
+    // two streams up to MCS 13
+    eht[11..14].copy_from_slice(&[0x22, 0x22, 0x22]);
+
+    let mut ies = he_capabilities();
+    ies.extend(extension(108, &eht));
+    // EHT Operation Information present, 320 MHz
+    ies.extend(extension(
+        106,
+        &[0x01, 0xfc, 0xff, 0xff, 0xff, 0x04, 31, 31],
+    ));
+    let phy = PhyInfo::from_elements(&ies, 31).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Wifi7);
+    assert_eq!(phy.max_spatial_streams, 2);
+    assert_eq!(phy.channel_width, 320);
+    assert_eq!(phy.max_phy_rate, 5764.7);
+}
+
+#[test]
+fn truncated_elements() {
+    assert_eq!(PhyInfo::from_elements(&[], 6), None);
+
+    // too short HT Capabilities are ignored
+    let mut ies = element(1, &[0x82, 0x84]);
+    ies.extend(element(45, &[0xef, 0x01, 0x17, 0xff]));
+    let phy = PhyInfo::from_elements(&ies, 6).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Legacy80211b);
+
+    // truncated elements are not read
+    let mut ht = ht_capabilities();
+    ht.truncate(20);
+    assert_eq!(PhyInfo::from_elements(&ht, 6), None);
+
+    let mut ies = element(1, &[0x82, 0x84]);
+    ies.extend(&ht);
+    let phy = PhyInfo::from_elements(&ies, 6).unwrap();
+    assert_eq!(phy.generation, WifiGeneration::Legacy80211b);
+    assert_eq!(phy.max_phy_rate, 2.0);
+}
+
+#[test]
+fn wifi_generation_and_display() {
+    let mut ies = ht_capabilities();
+    ies.extend(vht_capabilities());
+    let wifi = wifi(BSSID).channel(36).elements(ies).build();
+    assert_eq!(wifi.generation(), Some(WifiGeneration::Wifi5));
+    assert!(wifi.to_string().ends_with("| Standard: Wi-Fi 5 (802.11ac)"));
+
+    let unknown = Wifi::default();
+    assert_eq!(unknown.generation(), None);
+    assert!(unknown.to_string().ends_with("| Standard: Unknown"));
+}
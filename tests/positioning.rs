@@ -0,0 +1,122 @@
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    distance::{free_space_loss, DistanceConfig, PathLossModel},
+    positioning::{ApLocation, ApMap},
+    Wifi,
+};
+
+const TX_POWER: f64 = 20.0;
+
+fn config() -> DistanceConfig {
+    DistanceConfig {
+        tx_power: Some(TX_POWER),
+        model: PathLossModel::FreeSpace,
+        shadowing: 2.0,
+    }
+}
+
+/// Scan result of an AP on channel 6 heard from `meters` away in free space
+fn heard_at(mac: &str, meters: f64) -> Wifi {
+    let signal_level = (TX_POWER - free_space_loss(meters, 2437.0)).round() as i32;
+    wifi(mac).channel(6).signal(signal_level).build()
+}
+
+fn map() -> ApMap {
+    let mut map = ApMap::new();
+    assert!(map.insert("00:00:00:00:00:01", ApLocation::new(0.0, 0.0, 0)));
+    assert!(map.insert("00:00:00:00:00:02", ApLocation::new(20.0, 0.0, 0)));
+    assert!(map.insert("00:00:00:00:00:03", ApLocation::new(10.0, 15.0, 0)));
+    assert!(map.insert("00:00:00:00:00:04", ApLocation::new(5.0, 5.0, 1)));
+    map
+}
+
+#[test]
+fn trilateration() {
+    // true position (6, 4)
+    let wifis = vec![
+        heard_at("00:00:00:00:00:01", 52f64.sqrt()),
+        heard_at("00:00:00:00:00:02", 212f64.sqrt()),
+        heard_at("00:00:00:00:00:03", 137f64.sqrt()),
+        heard_at("00:00:00:00:00:99", 1.0),
+    ];
+
+    let position = map().locate(&wifis, &config()).unwrap();
+    assert_eq!(position.floor, 0);
+    assert!(
+        (position.x - 6.0).hypot(position.y - 4.0) < 1.0,
+        "{position}"
+    );
+    assert!(position.error_radius > 0.0 && position.error_radius < 3.0);
+    assert_eq!(position.aps_used.len(), 3);
+    assert_eq!(position.aps_used[1].bssid, "00:00:00:00:00:02");
+    assert!(position.to_string().ends_with("using 3 APs"));
+}
+
+#[test]
+fn centroid_fallback() {
+    // two APs are not enough for trilateration, the closer one weighs more
+    let wifis = vec![
+        heard_at("00:00:00:00:00:01", 2.0),
+        heard_at("00:00:00:00:00:02", 18.0),
+    ];
+    let position = map().locate(&wifis, &config()).unwrap();
+    assert!(position.x > 0.0 && position.x < 10.0);
+    assert_eq!(position.y, 0.0);
+    assert_eq!(position.aps_used.len(), 2);
+
+    // duplicate reports of a BSSID are used once
+    let wifis = vec![
+        heard_at("00:00:00:00:00:01", 5.0),
+        heard_at("00:00:00:00:00:01", 50.0),
+    ];
+    let position = map().locate(&wifis, &config()).unwrap();
+    assert_eq!((position.x, position.y), (0.0, 0.0));
+    assert_eq!(position.aps_used.len(), 1);
+}
+
+#[test]
+fn floor_selection() {
+    // a single close AP on floor 1 is more confident than two distant ones on floor 0
+    let wifis = vec![
+        heard_at("00:00:00:00:00:01", 30.0),
+        heard_at("00:00:00:00:00:02", 30.0),
+        heard_at("00:00:00:00:00:04", 1.0),
+    ];
+    let position = map().locate(&wifis, &config()).unwrap();
+    assert_eq!(position.floor, 1);
+    assert_eq!((position.x, position.y), (5.0, 5.0));
+}
+
+#[test]
+fn unknown_aps() {
+    let map = map();
+    assert_eq!(
+        map.locate(&[heard_at("00:00:00:00:00:99", 5.0)], &config()),
+        None
+    );
+    // no distance without signal level
+    let mut silent = heard_at("00:00:00:00:00:01", 5.0);
+    silent.signal_level = 0;
+    assert_eq!(map.locate(&[silent], &config()), None);
+    assert_eq!(map.locate(&[], &config()), None);
+}
+
+#[test]
+fn table() {
+    let mut map = map();
+    assert_eq!(map.len(), 4);
+    assert!(!map.insert("invalid", ApLocation::new(0.0, 0.0, 0)));
+    assert_eq!(
+        map.get("00-00-00-00-00-04"),
+        Some(ApLocation::new(5.0, 5.0, 1))
+    );
+    assert_eq!(
+        map.remove("00:00:00:00:00:04"),
+        Some(ApLocation::new(5.0, 5.0, 1))
+    );
+    assert_eq!(map.get("00:00:00:00:00:04"), None);
+    assert!(!map.is_empty());
+    assert!(ApMap::default().is_empty());
+}
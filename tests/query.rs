@@ -0,0 +1,146 @@
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    query::{Query, SecurityClass, SortKey},
+    Band, Wifi, WifiSecurity,
+};
+
+fn wifis() -> Vec<Wifi> {
+    vec![
+        wifi("00:00:00:00:00:01")
+            .ssid("Office-2G")
+            .channel(1)
+            .signal(-71)
+            .security(&[WifiSecurity::Wpa2PersonalPsk])
+            .build(),
+        wifi("00:00:00:00:00:06")
+            .ssid("guest")
+            .channel(6)
+            .signal(-40)
+            .security(&[WifiSecurity::Open])
+            .build(),
+        wifi("00:00:00:00:00:24")
+            .ssid("Office-5G")
+            .channel(36)
+            .signal(-58)
+            .security(&[WifiSecurity::Wpa3PersonalSae])
+            .build(),
+        wifi("00:00:00:00:00:0b")
+            .channel(11)
+            .signal(-62)
+            .security(&[WifiSecurity::Wpa2EnterpriseEap])
+            .build(),
+        wifi("00:00:00:00:00:95")
+            .ssid("Lab")
+            .channel(149)
+            .signal(0)
+            .security(&[WifiSecurity::Wpa3EnterpriseEap256])
+            .build(),
+    ]
+}
+
+fn ssids(wifis: Vec<&Wifi>) -> Vec<&str> {
+    wifis.iter().map(|w| w.ssid.as_str()).collect()
+}
+
+#[test]
+fn filters() {
+    let wifis = wifis();
+    assert_eq!(Query::new(&wifis).count(), 5);
+    assert_eq!(
+        ssids(Query::new(&wifis).band(Band::FiveGhz).collect()),
+        vec!["Office-5G", "Lab"]
+    );
+    assert_eq!(
+        ssids(Query::new(&wifis).channel(6).collect()),
+        vec!["guest"]
+    );
+    // unknown signal levels never pass a signal threshold
+    assert_eq!(Query::new(&wifis).min_signal(-62).count(), 3);
+    assert_eq!(Query::new(&wifis).min_signal(-100).count(), 4);
+    assert_eq!(ssids(Query::new(&wifis).hidden(true).collect()), vec![""]);
+    assert_eq!(
+        ssids(
+            Query::new(&wifis)
+                .filter(|w| w.channel > 6)
+                .hidden(false)
+                .collect()
+        ),
+        vec!["Office-5G", "Lab"]
+    );
+}
+
+#[test]
+fn security_classes() {
+    let wifis = wifis();
+    let matching = |class| ssids(Query::new(&wifis).security(class).collect());
+    assert_eq!(matching(SecurityClass::Open), vec!["guest"]);
+    assert_eq!(matching(SecurityClass::Wpa2), vec!["Office-2G", ""]);
+    assert_eq!(matching(SecurityClass::Wpa3), vec!["Office-5G", "Lab"]);
+    assert_eq!(matching(SecurityClass::Enterprise), vec!["", "Lab"]);
+    assert_eq!(
+        matching(SecurityClass::Personal),
+        vec!["Office-2G", "Office-5G"]
+    );
+}
+
+#[test]
+fn ssid_glob() {
+    let wifis = wifis();
+    let matching = |pattern| ssids(Query::new(&wifis).ssid_glob(pattern).collect());
+    assert_eq!(matching("Office-*"), vec!["Office-2G", "Office-5G"]);
+    assert_eq!(matching("Office-?G"), vec!["Office-2G", "Office-5G"]);
+    assert_eq!(matching("*5*"), vec!["Office-5G"]);
+    assert_eq!(matching("L?b"), vec!["Lab"]);
+    // matching is case sensitive and anchored
+    assert!(matching("office*").is_empty());
+    assert!(matching("Office").is_empty());
+    assert_eq!(
+        matching("*"),
+        vec!["Office-2G", "guest", "Office-5G", "", "Lab"]
+    );
+    assert_eq!(matching(""), vec![""]);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn ssid_regex() {
+    let wifis = wifis();
+    let regex = regex::Regex::new(r"(?i)^office-\dG$").unwrap();
+    assert_eq!(
+        ssids(Query::new(&wifis).ssid_regex(regex).collect()),
+        vec!["Office-2G", "Office-5G"]
+    );
+}
+
+#[test]
+fn sorting() {
+    let wifis = wifis();
+    assert_eq!(
+        ssids(Query::new(&wifis).sort_by(SortKey::Signal).collect()),
+        vec!["guest", "Office-5G", "", "Office-2G", "Lab"]
+    );
+    assert_eq!(
+        ssids(Query::new(&wifis).sort_by(SortKey::Ssid).collect()),
+        vec!["", "guest", "Lab", "Office-2G", "Office-5G"]
+    );
+    assert_eq!(
+        ssids(
+            Query::new(&wifis)
+                .sort_by(SortKey::Channel)
+                .reversed()
+                .collect()
+        ),
+        vec!["Lab", "Office-5G", "", "guest", "Office-2G"]
+    );
+
+    let strongest = Query::new(&wifis)
+        .security(SecurityClass::Personal)
+        .sort_by(SortKey::Signal)
+        .top_n(1)
+        .collect_owned();
+    assert_eq!(strongest.len(), 1);
+    assert_eq!(strongest[0].ssid, "Office-5G");
+    assert_eq!(Query::new(&wifis).top_n(10).count(), 5);
+}
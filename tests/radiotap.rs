@@ -0,0 +1,286 @@
+use wifi_scan::radiotap::{flags, AntennaSignal, HeFormat, Radiotap, VendorNamespace};
+
+/// Builds a radiotap header from presence bitmaps and field data, fixing up the length
+fn header(present: &[u32], fields: &[u8]) -> Vec<u8> {
+    let mut header = vec![0x00, 0x00, 0x00, 0x00];
+    for word in present {
+        header.extend_from_slice(&word.to_le_bytes());
+    }
+    header.extend_from_slice(fields);
+    let length = header.len() as u16;
+    header[2..4].copy_from_slice(&length.to_le_bytes());
+    header
+}
+
+fn bits(bits: &[u32]) -> u32 {
+    bits.iter().fold(0, |word, bit| word | 1 << bit)
+}
+
+#[test]
+fn aligned_fields() {
+    let mut fields = vec![flags::FCS | flags::SHORT_GI, 0, 0, 0];
+    // XChannel: turbo flags, 5180 MHz, channel 36, 20 dBm
+    fields.extend_from_slice(&0x0000_0140u32.to_le_bytes());
+    fields.extend_from_slice(&5180u16.to_le_bytes());
+    fields.extend_from_slice(&[36, 20]);
+    // MCS: bandwidth, index and guard interval known, 40 MHz, short GI, MCS 7
+    fields.extend_from_slice(&[0x07, 0x05, 7, 0]);
+    // A-MPDU: reference 42, last subframe known and set
+    fields.extend_from_slice(&42u32.to_le_bytes());
+    fields.extend_from_slice(&0x000cu16.to_le_bytes());
+    fields.extend_from_slice(&[0xab, 0x00]);
+    // VHT: bandwidth and guard interval known, 80 MHz, user 0 with MCS 9 and 2 streams
+    fields.extend_from_slice(&0x0044u16.to_le_bytes());
+    fields.extend_from_slice(&[0x04, 4, 0x92, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]);
+    fields.extend_from_slice(&[0; 4]);
+    // timestamp in microseconds
+    fields.extend_from_slice(&1_234_567u64.to_le_bytes());
+    fields.extend_from_slice(&[0x00, 0x00, 0x01, 0x00]);
+    // HE: SU with BSS color 5, MCS 11 and 160 MHz
+    let he: [u16; 6] = [0x4024, 0x0002, 0x0b05, 0x0000, 0x0013, 0x0002];
+    for value in he {
+        fields.extend_from_slice(&value.to_le_bytes());
+    }
+    // HE-MU: SIG-B MCS 3, 80 MHz
+    fields.extend_from_slice(&0x0013u16.to_le_bytes());
+    fields.extend_from_slice(&0x0006u16.to_le_bytes());
+    fields.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    // L-SIG: rate 11, length 100
+    fields.extend_from_slice(&0x0003u16.to_le_bytes());
+    fields.extend_from_slice(&((100u16 << 4) | 11).to_le_bytes());
+
+    let data = header(&[bits(&[1, 18, 19, 20, 21, 22, 23, 24, 27])], &fields);
+    let radiotap = Radiotap::parse(&data).unwrap();
+    assert_eq!(radiotap.length, 88);
+    assert!(radiotap.has_fcs());
+    assert!(!radiotap.bad_fcs());
+    assert_eq!(radiotap.frequency(), Some(5180));
+    assert_eq!(radiotap.xchannel.unwrap().channel, 36);
+    assert_eq!(radiotap.signal(), None);
+
+    let mcs = radiotap.mcs.unwrap();
+    assert_eq!(mcs.bandwidth(), Some(40));
+    assert_eq!(mcs.mcs(), Some(7));
+    assert_eq!(mcs.short_gi(), Some(true));
+    assert_eq!(mcs.ldpc(), None);
+
+    let ampdu = radiotap.ampdu.unwrap();
+    assert_eq!(ampdu.reference, 42);
+    assert_eq!(ampdu.is_last(), Some(true));
+
+    let vht = radiotap.vht.unwrap();
+    assert_eq!(vht.bandwidth(), Some(80));
+    assert_eq!(vht.short_gi(), Some(true));
+    assert_eq!(vht.user(0), Some((9, 2)));
+    assert_eq!(vht.user(1), None);
+    assert!(vht.ldpc(0));
+    assert!(!vht.ldpc(1));
+
+    let timestamp = radiotap.timestamp.unwrap();
+    assert_eq!(timestamp.timestamp, 1_234_567);
+    assert_eq!(timestamp.unit_nanoseconds(), Some(1_000));
+
+    let he = radiotap.he.unwrap();
+    assert_eq!(he.format(), HeFormat::Su);
+    assert_eq!(he.bss_color(), Some(5));
+    assert_eq!(he.mcs(), Some(11));
+    assert_eq!(he.bandwidth(), Some(160));
+    assert_eq!(he.guard_interval(), Some(1600));
+    assert_eq!(he.nss(), Some(2));
+
+    let he_mu = radiotap.he_mu.unwrap();
+    assert_eq!(he_mu.sig_b_mcs(), Some(3));
+    assert_eq!(he_mu.bandwidth(), Some(80));
+    assert_eq!(he_mu.ru_channel2, [5, 6, 7, 8]);
+
+    let lsig = radiotap.lsig.unwrap();
+    assert_eq!(lsig.rate(), Some(11));
+    assert_eq!(lsig.length(), Some(100));
+}
+
+#[test]
+fn namespaces() {
+    let present = [
+        bits(&[0, 1, 3, 5, 29, 31]),
+        bits(&[5, 11, 29, 31]),
+        bits(&[5, 11, 30, 31]),
+        // vendor defined bitmap
+        bits(&[0, 29, 31]),
+        bits(&[5]),
+    ];
+    let mut fields = Vec::new();
+    fields.extend_from_slice(&99u64.to_le_bytes());
+    fields.extend_from_slice(&[0x00, 0x00]);
+    fields.extend_from_slice(&2412u16.to_le_bytes());
+    fields.extend_from_slice(&0x00a0u16.to_le_bytes());
+    fields.extend_from_slice(&[-41i8 as u8, -40i8 as u8, 0, -45i8 as u8, 1, 0]);
+    // vendor namespace with three bytes of data
+    fields.extend_from_slice(&[0x00, 0x11, 0x22, 0x01, 0x03, 0x00, 0xaa, 0xbb, 0xcc]);
+    fields.push(-50i8 as u8);
+
+    let data = header(&present, &fields);
+    let radiotap = Radiotap::parse(&data).unwrap();
+    assert_eq!(radiotap.length, 54);
+    assert_eq!(radiotap.tsft, Some(99));
+    assert_eq!(radiotap.frequency(), Some(2412));
+    assert_eq!(radiotap.antenna_signal, Some(-41));
+    assert_eq!(
+        radiotap.antennas,
+        vec![
+            AntennaSignal {
+                antenna: Some(0),
+                signal: Some(-40),
+                noise: None
+            },
+            AntennaSignal {
+                antenna: Some(1),
+                signal: Some(-45),
+                noise: None
+            },
+            AntennaSignal {
+                antenna: None,
+                signal: Some(-50),
+                noise: None
+            },
+        ]
+    );
+    assert_eq!(
+        radiotap.vendor_namespaces,
+        vec![VendorNamespace {
+            oui: [0x00, 0x11, 0x22],
+            sub_namespace: 1,
+            present: vec![present[3]],
+            data: vec![0xaa, 0xbb, 0xcc],
+        }]
+    );
+
+    // without the combined signal the strongest antenna is used
+    let data = header(
+        &[bits(&[1, 29, 31]), bits(&[5, 11, 29, 31]), bits(&[5, 11])],
+        &[0x00, -60i8 as u8, 0, -55i8 as u8, 1],
+    );
+    let radiotap = Radiotap::parse(&data).unwrap();
+    assert_eq!(radiotap.antenna_signal, None);
+    assert_eq!(radiotap.antennas.len(), 2);
+    assert_eq!(radiotap.signal(), Some(-55));
+}
+
+#[test]
+fn stops_at_undefined_fields() {
+    // flags, then an undefined field in the extended bitmap of the radiotap namespace
+    let data = header(&[bits(&[1, 31]), bits(&[0])], &[flags::BAD_FCS, 1, 2, 3]);
+    let radiotap = Radiotap::parse(&data).unwrap();
+    assert!(radiotap.bad_fcs());
+    assert_eq!(radiotap.length, 16);
+
+    // TLVs after the antenna signal
+    let data = header(&[bits(&[5, 28])], &[0xc0, 0, 0, 0, 1, 0, 4, 0, 1, 2, 3, 4]);
+    assert_eq!(Radiotap::parse(&data).unwrap().signal(), Some(-64));
+}
+
+#[test]
+fn rejects_malformed_headers() {
+    assert_eq!(Radiotap::parse(&[]), None);
+    assert_eq!(Radiotap::parse(&[0, 0, 8, 0, 0, 0, 0]), None);
+    // unsupported version
+    assert_eq!(Radiotap::parse(&[1, 0, 8, 0, 0, 0, 0, 0]), None);
+    // longer than the packet
+    assert_eq!(Radiotap::parse(&[0, 0, 9, 0, 0, 0, 0, 0]), None);
+    // presence bitmaps beyond the header
+    assert_eq!(
+        Radiotap::parse(&[0, 0, 8, 0, 0, 0, 0, 0x80, 0, 0, 0, 0]),
+        None
+    );
+    // truncated TSFT
+    assert_eq!(Radiotap::parse(&header(&[1], &[0; 7])), None);
+    // vendor namespace data beyond the header
+    let data = header(&[bits(&[30, 31]), 0], &[0x00, 0x11, 0x22, 0x01, 0x10, 0x00]);
+    assert_eq!(Radiotap::parse(&data), None);
+
+    let empty = Radiotap::parse(&[0, 0, 8, 0, 0, 0, 0, 0, 0x80]).unwrap();
+    assert_eq!(empty.length, 8);
+    assert_eq!(empty.frequency(), None);
+}
+
+/// xorshift64* for reproducible fuzzing without dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn check(data: &[u8]) {
+    if let Some(radiotap) = Radiotap::parse(data) {
+        assert!(radiotap.length >= 8 && radiotap.length <= data.len());
+        let _ = (radiotap.frequency(), radiotap.signal(), radiotap.has_fcs());
+        if let Some(vht) = radiotap.vht {
+            let _ = (vht.bandwidth(), vht.user(3), vht.ldpc(7));
+        }
+        for vendor in &radiotap.vendor_namespaces {
+            assert!(vendor.data.len() <= radiotap.length);
+        }
+    }
+}
+
+#[test]
+fn fuzz() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    // random bytes behind a plausible version and length
+    for _ in 0..20_000 {
+        let mut data: Vec<u8> = (0..rng.below(96)).map(|_| rng.next() as u8).collect();
+        if data.len() >= 4 {
+            data[0] = 0;
+            data[2] = rng.below(data.len() + 8) as u8;
+            data[3] = 0;
+        }
+        check(&data);
+    }
+
+    // mutations of valid headers with extended bitmaps and namespaces
+    let seeds = [
+        header(
+            &[
+                bits(&[0, 1, 3, 5, 29, 31]),
+                bits(&[5, 11, 30, 31]),
+                bits(&[0, 29, 31]),
+                bits(&[5]),
+            ],
+            &[
+                0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 0x10, 0, 0x6c, 0x09, 0xa0, 0, 0xd0, 0xc8, 1, 0,
+                0x00, 0x11, 0x22, 0x01, 0x02, 0x00, 0xaa, 0xbb, 0xc0,
+            ],
+        ),
+        header(&[bits(&[19, 20, 21, 22, 23, 24, 25, 26, 27])], &[0x5a; 96]),
+    ];
+    for seed in &seeds {
+        check(seed);
+        for length in 0..seed.len() {
+            check(&seed[..length]);
+        }
+        for _ in 0..50_000 {
+            let mut data = seed.clone();
+            for _ in 0..1 + rng.below(4) {
+                let index = rng.below(data.len());
+                match rng.below(3) {
+                    0 => data[index] = rng.next() as u8,
+                    1 => data[index] ^= 1 << rng.below(8),
+                    _ => data.truncate(index),
+                }
+                if data.is_empty() {
+                    break;
+                }
+            }
+            check(&data);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::wifi;
+use wifi_scan::{results::ScanResults, Band, Wifi, WifiSecurity};
+
+fn office() -> ScanResults {
+    use WifiSecurity::*;
+    vec![
+        wifi("00:00:00:00:00:01")
+            .ssid("Corp")
+            .channel(1)
+            .signal(-70)
+            .security(&[Wpa2PersonalPsk, Wpa3PersonalSae])
+            .build(),
+        wifi("00:00:00:00:00:02")
+            .ssid("Guest")
+            .channel(6)
+            .signal(-40)
+            .security(&[Open])
+            .build(),
+        wifi("00:00:00:00:00:03")
+            .ssid("Corp")
+            .channel(36)
+            .signal(-55)
+            .security(&[Wpa3PersonalSae, Wpa2PersonalPsk])
+            .build(),
+        // same name with other security is another network
+        wifi("00:00:00:00:00:04")
+            .ssid("Corp")
+            .channel(11)
+            .signal(-80)
+            .security(&[Wpa2PersonalPsk])
+            .build(),
+        wifi("00:00:00:00:00:05")
+            .channel(1)
+            .signal(-60)
+            .security(&[Wpa2PersonalPsk])
+            .build(),
+        wifi("00:00:00:00:00:06")
+            .channel(1)
+            .signal(-65)
+            .security(&[Wpa2PersonalPsk])
+            .build(),
+        wifi("00:00:00:00:00:07")
+            .ssid("Corp")
+            .channel(149)
+            .security(&[Wpa2PersonalPsk, Wpa3PersonalSae])
+            .build(),
+    ]
+    .into()
+}
+
+#[test]
+fn group_by_ess() {
+    let results = office();
+    let groups = results.group_by_ess();
+    assert_eq!(groups.len(), 5);
+
+    let corp = &groups[0];
+    assert_eq!(corp.ssid, "Corp");
+    assert_eq!(
+        corp.bssids(),
+        vec![
+            "00:00:00:00:00:01",
+            "00:00:00:00:00:03",
+            "00:00:00:00:00:07"
+        ]
+    );
+    assert_eq!(corp.bands(), vec![Band::TwoPointFourGhz, Band::FiveGhz]);
+    assert_eq!(corp.best().unwrap().mac, "00:00:00:00:00:03");
+    // unknown signal levels are ignored
+    assert_eq!(
+        (corp.min_signal(), corp.max_signal()),
+        (Some(-70), Some(-55))
+    );
+    assert!(!corp.is_hidden());
+
+    assert_eq!(groups[2].bssids(), vec!["00:00:00:00:00:04"]);
+
+    // hidden networks are never merged
+    assert!(groups[3].is_hidden() && groups[4].is_hidden());
+    assert_eq!(groups[3].bsses.len(), 1);
+}
+
+#[test]
+fn unknown_signal() {
+    let results: ScanResults = vec![wifi("00:00:00:00:00:01").ssid("A").channel(1).build()]
+        .into_iter()
+        .collect();
+    let groups = results.group_by_ess();
+    assert_eq!(
+        (groups[0].min_signal(), groups[0].max_signal()),
+        (None, None)
+    );
+    assert!(groups[0].best().is_some());
+    assert!(ScanResults::default().group_by_ess().is_empty());
+}
+
+#[test]
+fn collection() {
+    let results = office();
+    assert_eq!(results.len(), 7);
+    assert_eq!(results[1].ssid, "Guest");
+    assert_eq!((&results).into_iter().count(), 7);
+    assert_eq!(results.query().hidden(true).count(), 2);
+
+    let wifis: Vec<Wifi> = results.clone().into();
+    assert_eq!(ScanResults::new(wifis.clone()), results);
+    assert_eq!(results.clone().into_inner(), wifis);
+    assert_eq!(results.into_iter().count(), 7);
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    rogue::{Reason, RoguePolicy, SsidPolicy, Verdict},
+    Band, WifiSecurity,
+};
+
+fn policy() -> RoguePolicy {
+    RoguePolicy::new()
+        .with(
+            SsidPolicy::new("Corp")
+                .oui("00:11:22")
+                .bssid("AA:BB:CC:DD:EE:FF")
+                .security(WifiSecurity::Wpa3PersonalSae)
+                .band(Band::FiveGhz),
+        )
+        .with(SsidPolicy::new("Guest"))
+}
+
+#[test]
+fn authorized() {
+    let policy = policy();
+    let sae = [WifiSecurity::Wpa2PersonalPsk, WifiSecurity::Wpa3PersonalSae];
+    assert_eq!(
+        policy.classify(
+            &wifi("00:11:22:33:44:55")
+                .ssid("Corp")
+                .channel(36)
+                .security(&sae)
+                .build()
+        ),
+        Verdict::Authorized
+    );
+    // permitted by BSSID regardless of notation
+    assert_eq!(
+        policy.classify(
+            &wifi("aa-bb-cc-dd-ee-ff")
+                .ssid("Corp")
+                .channel(149)
+                .security(&sae)
+                .build()
+        ),
+        Verdict::Authorized
+    );
+    // anything goes for an SSID without restrictions
+    assert_eq!(
+        policy.classify(
+            &wifi("66:77:88:99:aa:bb")
+                .ssid("Guest")
+                .channel(1)
+                .security(&[WifiSecurity::Open])
+                .build()
+        ),
+        Verdict::Authorized
+    );
+    // unknown security and band are not held against the network
+    assert_eq!(
+        policy.classify(
+            &wifi("00:11:22:33:44:55")
+                .ssid("Corp")
+                .channel(0)
+                .security(&[WifiSecurity::Unknown])
+                .build()
+        ),
+        Verdict::Authorized
+    );
+}
+
+#[test]
+fn suspicious() {
+    let policy = policy();
+    let verdict = policy.classify(
+        &wifi("66:77:88:99:aa:bb")
+            .ssid("Corp")
+            .channel(6)
+            .security(&[WifiSecurity::Wpa2PersonalPsk])
+            .build(),
+    );
+    assert_eq!(
+        verdict,
+        Verdict::Suspicious(vec![
+            Reason::UnknownBssid,
+            Reason::UnknownVendor,
+            Reason::SecurityDowngrade {
+                missing: vec![WifiSecurity::Wpa3PersonalSae]
+            },
+            Reason::DisallowedBand(Band::TwoPointFourGhz),
+        ])
+    );
+    assert_eq!(
+        verdict.to_string(),
+        "Suspicious: Unknown BSSID; Unknown vendor; Security downgrade (missing WPA3-Personal (SAE)); Not allowed on 2.4 GHz"
+    );
+
+    // an open network is an open twin instead of a downgrade
+    assert_eq!(
+        policy.classify(
+            &wifi("00:11:22:33:44:66")
+                .ssid("Corp")
+                .channel(36)
+                .security(&[WifiSecurity::Open])
+                .build()
+        ),
+        Verdict::Suspicious(vec![Reason::OpenTwin])
+    );
+}
+
+#[test]
+fn unknown() {
+    let policy = policy();
+    let sae = [WifiSecurity::Wpa3PersonalSae];
+    assert_eq!(
+        policy.classify(
+            &wifi("00:11:22:33:44:55")
+                .ssid("Other")
+                .channel(36)
+                .security(&sae)
+                .build()
+        ),
+        Verdict::Unknown
+    );
+    assert_eq!(
+        policy.classify(&wifi("00:11:22:33:44:55").channel(36).security(&sae).build()),
+        Verdict::Unknown
+    );
+    // SSIDs are case sensitive
+    assert_eq!(
+        policy.classify(
+            &wifi("00:11:22:33:44:55")
+                .ssid("corp")
+                .channel(36)
+                .security(&sae)
+                .build()
+        ),
+        Verdict::Unknown
+    );
+    assert_eq!(Verdict::Unknown.to_string(), "Unknown");
+}
+
+#[test]
+fn open_twin_in_scan() {
+    let policy = RoguePolicy::new().with(SsidPolicy::new("Cafe").oui("00:11:22"));
+    let wifis = vec![
+        wifi("00:11:22:33:44:55")
+            .ssid("Cafe")
+            .channel(6)
+            .security(&[WifiSecurity::Wpa2PersonalPsk])
+            .build(),
+        wifi("00:11:22:33:44:66")
+            .ssid("Cafe")
+            .channel(1)
+            .security(&[WifiSecurity::Open])
+            .build(),
+        wifi("66:77:88:99:aa:bb")
+            .ssid("Cafe")
+            .channel(11)
+            .security(&[WifiSecurity::Open])
+            .build(),
+        wifi("66:77:88:99:aa:cc")
+            .ssid("Lobby")
+            .channel(11)
+            .security(&[WifiSecurity::Open])
+            .build(),
+    ];
+
+    let verdicts: Vec<Verdict> = policy
+        .analyze(&wifis)
+        .into_iter()
+        .map(|a| a.verdict)
+        .collect();
+    assert_eq!(
+        verdicts,
+        vec![
+            Verdict::Authorized,
+            Verdict::Suspicious(vec![Reason::OpenTwin]),
+            // only vendor prefixes are allow-listed
+            Verdict::Suspicious(vec![Reason::UnknownVendor, Reason::OpenTwin]),
+            Verdict::Unknown,
+        ]
+    );
+
+    // without a secured access point an open network is fine
+    let verdicts: Vec<Verdict> = policy
+        .analyze(&wifis[1..2])
+        .into_iter()
+        .map(|a| a.verdict)
+        .collect();
+    assert_eq!(verdicts, vec![Verdict::Authorized]);
+}
+
+#[test]
+fn policy_table() {
+    let mut policy = policy();
+    assert_eq!(policy.get("Corp").unwrap().ouis, vec![[0x00, 0x11, 0x22]]);
+    assert_eq!(
+        policy.get("Corp").unwrap().bssids,
+        vec![[0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]]
+    );
+
+    // invalid addresses are ignored and policies are replaced by SSID
+    policy.add(SsidPolicy::new("Corp").oui("invalid").bssid("invalid"));
+    let corp = policy.get("Corp").unwrap();
+    assert!(corp.ouis.is_empty() && corp.bssids.is_empty());
+    assert!(corp.required_security.is_empty() && corp.bands.is_empty());
+    assert!(policy.get("Lobby").is_none());
+}
@@ -0,0 +1,264 @@
+#![cfg(feature = "serde")]
+
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::wifi;
+use serde::{de::DeserializeOwned, Serialize};
+use wifi_scan::{
+    audit,
+    distance::DistanceConfig,
+    export::wigle::DeviceInfo,
+    fingerprint::FingerprintDb,
+    pcap::Packet,
+    phy::{PhyInfo, WifiGeneration},
+    positioning::{ApLocation, ApMap},
+    results::ScanResults,
+    rogue::{RoguePolicy, SsidPolicy, Verdict},
+    schema::{Versioned, SCHEMA_VERSION},
+    signal::SignalThresholds,
+    tracker::Tracker,
+    Band, Error, SignalStrength, Wifi, WifiSecurity,
+};
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+fn sample_wifis() -> Vec<Wifi> {
+    let corp = wifi("00:11:22:33:44:55")
+        .ssid("Corp")
+        .channel(36)
+        .signal(-48)
+        .security(&[WifiSecurity::Wpa3PersonalSae, WifiSecurity::Wpa2PersonalPsk])
+        // SSID "Corp" followed by an RSN element with a TKIP group cipher
+        .elements(vec![
+            0x00, 0x04, b'C', b'o', b'r', b'p', 0x30, 0x14, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x02,
+            0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x02, 0x00, 0x00,
+        ])
+        .build();
+
+    let hidden = wifi("aa:bb:cc:dd:ee:ff")
+        .channel(6)
+        .signal(-81)
+        .security(&[
+            WifiSecurity::Open,
+            WifiSecurity::Other("Custom".to_string()),
+        ])
+        .inferred()
+        .build();
+
+    vec![corp, hidden]
+}
+
+#[test]
+fn wifi_round_trip() {
+    let wifis = sample_wifis();
+    let read = round_trip(&wifis);
+    assert_eq!(read, wifis);
+    assert_eq!(
+        read[0].information_elements(),
+        wifis[0].information_elements()
+    );
+}
+
+#[test]
+fn wifi_field_names() {
+    let json = serde_json::to_value(&sample_wifis()[1]).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "mac": "aa:bb:cc:dd:ee:ff",
+            "ssid": "",
+            "channel": 6,
+            "frequency": 0,
+            "signal_level": -81,
+            "security": ["Open", {"Other": "Custom"}],
+            "information_elements": "",
+            "inferred": true
+        })
+    );
+}
+
+#[test]
+fn information_elements_as_hex() {
+    let json = serde_json::to_value(&sample_wifis()[0]).unwrap();
+    let ies = json["information_elements"].as_str().unwrap();
+    assert!(ies.starts_with("0004436f727030"));
+}
+
+#[test]
+fn wifi_optional_fields_default() {
+    let json = r#"{"mac":"00:11:22:33:44:55","ssid":"Home","channel":1,"signal_level":-60,"security":["Wpa2PersonalPsk"]}"#;
+    let wifi: Wifi = serde_json::from_str(json).unwrap();
+    assert!(wifi.information_elements().is_empty());
+    assert!(!wifi.inferred);
+}
+
+#[test]
+fn invalid_hex_is_rejected() {
+    let json = r#"{"mac":"","ssid":"","channel":1,"signal_level":0,"security":[],"information_elements":"0g"}"#;
+    assert!(serde_json::from_str::<Wifi>(json).is_err());
+}
+
+#[test]
+fn enums_round_trip() {
+    for strength in [
+        SignalStrength::Unknown,
+        SignalStrength::Weak,
+        SignalStrength::Fair,
+        SignalStrength::Good,
+        SignalStrength::Excellent,
+    ] {
+        assert_eq!(round_trip(&strength), strength);
+    }
+    for band in [Band::TwoPointFourGhz, Band::FiveGhz, Band::SixGhz] {
+        assert_eq!(round_trip(&band), band);
+    }
+
+    let errors = vec![
+        Error::InterfaceError("wlan0".to_string()),
+        Error::SocketError("denied".to_string()),
+        Error::ScanFailed("busy".to_string()),
+        Error::UnsupportedSchema(7),
+    ];
+    assert_eq!(round_trip(&errors), errors);
+}
+
+#[test]
+fn versioned_round_trip() {
+    let wifis = sample_wifis();
+    let json = serde_json::to_string(&Versioned::new(wifis.clone())).unwrap();
+    assert!(json.contains(&format!("\"schema_version\":{}", SCHEMA_VERSION)));
+
+    let versioned: Versioned<Vec<Wifi>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(versioned.into_data().unwrap(), wifis);
+}
+
+#[test]
+fn versioned_rejects_newer_schema() {
+    let json = format!(r#"{{"schema_version":{},"data":[]}}"#, SCHEMA_VERSION + 1);
+    let versioned: Versioned<Vec<Wifi>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        versioned.into_data(),
+        Err(Error::UnsupportedSchema(SCHEMA_VERSION + 1))
+    );
+}
+
+#[test]
+fn scan_results_round_trip() {
+    let results = ScanResults::new(sample_wifis());
+    assert_eq!(round_trip(&results), results);
+
+    let groups = results.group_by_ess();
+    assert_eq!(round_trip(&groups), groups);
+}
+
+#[test]
+fn phy_round_trip() {
+    let phy = PhyInfo {
+        generation: WifiGeneration::Wifi6E,
+        max_spatial_streams: 2,
+        channel_width: 160,
+        max_phy_rate: 2401.9,
+    };
+    assert_eq!(round_trip(&phy), phy);
+}
+
+#[test]
+fn settings_round_trip() {
+    let thresholds = SignalThresholds::default();
+    assert_eq!(round_trip(&thresholds), thresholds);
+
+    let config = DistanceConfig::indoor();
+    assert_eq!(round_trip(&config), config);
+}
+
+#[test]
+fn audit_round_trip() {
+    let report = audit::audit(&sample_wifis());
+    assert!(report.summary.networks_at_risk > 0);
+    assert_eq!(round_trip(&report), report);
+}
+
+#[test]
+fn rogue_policy_round_trip() {
+    let policy = RoguePolicy::new().with(
+        SsidPolicy::new("Corp")
+            .bssid("00:11:22:33:44:55")
+            .oui("00:11:22")
+            .security(WifiSecurity::Wpa3PersonalSae)
+            .band(Band::FiveGhz),
+    );
+    let json = serde_json::to_string(&policy).unwrap();
+    assert!(json.contains("\"00:11:22:33:44:55\""));
+    assert!(json.contains("\"00:11:22\""));
+
+    let read: RoguePolicy = serde_json::from_str(&json).unwrap();
+    assert_eq!(read, policy);
+
+    let wifis = sample_wifis();
+    assert_eq!(read.classify(&wifis[0]), Verdict::Authorized);
+}
+
+#[test]
+fn ap_map_round_trip() {
+    let mut map = ApMap::new();
+    map.insert("00:11:22:33:44:55", ApLocation::new(1.5, -2.0, 3));
+    map.insert("00:11:22:33:44:66", ApLocation::new(10.0, 4.25, 0));
+    assert_eq!(round_trip(&map), map);
+}
+
+#[test]
+fn fingerprint_db_round_trip() {
+    let mut db = FingerprintDb::new();
+    db.record("Kitchen", &sample_wifis());
+    assert_eq!(round_trip(&db), db);
+}
+
+#[test]
+fn tracker_round_trip() {
+    let mut tracker = Tracker::new();
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    tracker.ingest_at(&sample_wifis(), start);
+    tracker.ingest_at(&sample_wifis(), start + Duration::from_secs(5));
+
+    let read = round_trip(&tracker);
+    assert_eq!(read.len(), tracker.len());
+    assert_eq!(
+        read.get("00:11:22:33:44:55"),
+        tracker.get("00:11:22:33:44:55")
+    );
+}
+
+#[test]
+fn device_info_round_trip() {
+    let device = DeviceInfo {
+        model: "ThinkPad".to_string(),
+        ..DeviceInfo::default()
+    };
+    assert_eq!(round_trip(&device), device);
+}
+
+#[test]
+fn packet_round_trip() {
+    let packet = Packet {
+        link_type: 127,
+        timestamp: Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_250)),
+        fcs_len: Some(4),
+        data: vec![0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00],
+    };
+    let json = serde_json::to_value(&packet).unwrap();
+    assert_eq!(json["timestamp"], "2023-11-14T22:13:20.250Z");
+    assert_eq!(json["data"], "0000080000000000");
+    assert_eq!(round_trip(&packet), packet);
+}
+
+#[cfg(feature = "oui")]
+#[test]
+fn oui_database_round_trip() {
+    let database = wifi_scan::oui::OuiDatabase::embedded();
+    assert_eq!(round_trip(&database), database);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::wifi;
+use wifi_scan::{
+    signal::{bars, normalize_rssi, quality, SignalThresholds},
+    SignalStrength,
+};
+
+const BSSID: &str = "00:11:22:33:44:55";
+
+#[test]
+fn default_thresholds() {
+    let thresholds = SignalThresholds::default();
+    assert_eq!(thresholds.classify(-30), SignalStrength::Excellent);
+    assert_eq!(thresholds.classify(-50), SignalStrength::Excellent);
+    assert_eq!(thresholds.classify(-51), SignalStrength::Good);
+    assert_eq!(thresholds.classify(-70), SignalStrength::Good);
+    assert_eq!(thresholds.classify(-71), SignalStrength::Fair);
+    assert_eq!(thresholds.classify(-80), SignalStrength::Fair);
+    assert_eq!(thresholds.classify(-81), SignalStrength::Weak);
+    assert_eq!(thresholds.classify(0), SignalStrength::Unknown);
+
+    assert_eq!(
+        wifi(BSSID).signal(-45).build().readable_signal(),
+        SignalStrength::Excellent
+    );
+    assert_eq!(
+        wifi(BSSID).signal(-90).build().readable_signal(),
+        SignalStrength::Weak
+    );
+    assert_eq!(
+        wifi(BSSID).signal(0).build().readable_signal(),
+        SignalStrength::Unknown
+    );
+}
+
+#[test]
+fn custom_thresholds() {
+    let strict = SignalThresholds {
+        excellent: -40,
+        good: -60,
+        fair: -75,
+    };
+    assert_eq!(strict.classify(-45), SignalStrength::Good);
+    assert_eq!(strict.classify(-65), SignalStrength::Fair);
+    assert_eq!(strict.classify(-76), SignalStrength::Weak);
+    assert_eq!(
+        wifi(BSSID)
+            .signal(-45)
+            .build()
+            .readable_signal_with(&strict),
+        SignalStrength::Good
+    );
+}
+
+#[test]
+fn positive_rssi() {
+    // unsigned 8-bit dBm
+    assert_eq!(normalize_rssi(200), -56);
+    assert_eq!(normalize_rssi(255), -1);
+    // dB above the noise floor
+    assert_eq!(normalize_rssi(40), -55);
+    assert_eq!(normalize_rssi(127), -1);
+    assert_eq!(normalize_rssi(-60), -60);
+    assert_eq!(normalize_rssi(0), 0);
+    assert_eq!(
+        SignalThresholds::default().classify(50),
+        SignalStrength::Excellent
+    );
+}
+
+#[test]
+fn quality_and_bars() {
+    assert_eq!(quality(-50), Some(100));
+    assert_eq!(quality(-30), Some(100));
+    assert_eq!(quality(-75), Some(50));
+    assert_eq!(quality(-100), Some(0));
+    assert_eq!(quality(-110), Some(0));
+    assert_eq!(quality(0), None);
+
+    assert_eq!(bars(SignalStrength::Unknown), 0);
+    assert_eq!(bars(SignalStrength::Weak), 1);
+    assert_eq!(bars(SignalStrength::Excellent), 4);
+    assert_eq!(SignalStrength::Good.to_string(), "Good");
+}
+
+#[test]
+fn wifi_quality() {
+    assert_eq!(wifi(BSSID).signal(-60).build().signal_quality(), Some(80));
+    assert_eq!(wifi(BSSID).signal(0).build().signal_quality(), None);
+    assert_eq!(wifi(BSSID).signal(-60).build().signal_bars(), 3);
+    assert_eq!(wifi(BSSID).signal(0).build().signal_bars(), 0);
+}
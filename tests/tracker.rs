@@ -0,0 +1,136 @@
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::wifi;
+use wifi_scan::tracker::{Tracker, Trend};
+
+const BSSID: &str = "AA:BB:CC:DD:EE:FF";
+
+/// Feeds one scan per signal level, one second apart
+fn track(tracker: &mut Tracker, levels: &[i32]) -> SystemTime {
+    let mut time = SystemTime::UNIX_EPOCH;
+    for level in levels {
+        time += Duration::from_secs(1);
+        tracker.ingest_at(&[wifi(BSSID).ssid("Corp").signal(*level).build()], time);
+    }
+    time
+}
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.unwrap();
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[test]
+fn moving_average() {
+    let mut tracker = Tracker::with_settings(0.5, 10);
+    track(&mut tracker, &[-60, -70, -50]);
+
+    let bss = tracker.get(BSSID).unwrap();
+    // -60, then 0.5 * -70 + 0.5 * -60 = -65, then 0.5 * -50 + 0.5 * -65
+    assert_close(bss.smoothed(), -57.5);
+    assert_close(bss.mean(), -60.0);
+    assert_close(bss.median(), -60.0);
+    assert_close(bss.variance(), 200.0 / 3.0);
+    assert_eq!((bss.min(), bss.max()), (Some(-70), Some(-50)));
+    assert_eq!(bss.seen_count, 3);
+
+    // alpha 1 follows the last sample
+    let mut tracker = Tracker::with_settings(1.5, 10);
+    track(&mut tracker, &[-60, -70]);
+    assert_close(tracker.get(BSSID).unwrap().smoothed(), -70.0);
+}
+
+#[test]
+fn statistics() {
+    let mut tracker = Tracker::with_settings(0.3, 4);
+    track(&mut tracker, &[-90, -40, -62, -58, -66]);
+
+    // only the last 4 samples are kept
+    let bss = tracker.get(&BSSID.to_lowercase()).unwrap();
+    let levels: Vec<i32> = bss.samples().map(|(_, s)| s).collect();
+    assert_eq!(levels, vec![-40, -62, -58, -66]);
+    assert_close(bss.median(), -60.0);
+    assert_close(bss.mean(), -56.5);
+    assert_eq!(bss.min(), Some(-66));
+}
+
+#[test]
+fn trend() {
+    let trend = |levels: &[i32]| {
+        let mut tracker = Tracker::new();
+        track(&mut tracker, levels);
+        tracker.get(BSSID).unwrap().trend()
+    };
+    assert_eq!(trend(&[-70, -65]), Trend::Unknown);
+    assert_eq!(trend(&[-70, -66, -62, -60]), Trend::Rising);
+    assert_eq!(trend(&[-50, -55, -61]), Trend::Falling);
+    assert_eq!(trend(&[-60, -61, -60, -60]), Trend::Stable);
+    assert_eq!(Trend::Rising.to_string(), "Rising");
+}
+
+#[test]
+fn unknown_signal() {
+    let mut tracker = Tracker::new();
+    track(&mut tracker, &[0, -60, 0]);
+
+    let bss = tracker.get(BSSID).unwrap();
+    assert_eq!(bss.seen_count, 3);
+    assert_eq!(bss.samples().count(), 1);
+    assert_close(bss.smoothed(), -60.0);
+    assert_eq!(bss.last.signal_level, 0);
+
+    let mut tracker = Tracker::new();
+    track(&mut tracker, &[0]);
+    let bss = tracker.get(BSSID).unwrap();
+    assert_eq!(
+        (bss.smoothed(), bss.median(), bss.variance()),
+        (None, None, None)
+    );
+}
+
+#[test]
+fn prune() {
+    let mut tracker = Tracker::default();
+    let start = SystemTime::UNIX_EPOCH;
+    tracker.ingest_at(
+        &[
+            wifi(BSSID).ssid("Corp").signal(-60).build(),
+            wifi("00:11:22:33:44:55").ssid("Corp").signal(-70).build(),
+        ],
+        start,
+    );
+    let now = start + Duration::from_secs(60);
+    tracker.ingest_at(&[wifi(BSSID).ssid("Corp").signal(-62).build()], now);
+    assert_eq!(tracker.len(), 2);
+
+    let bss = tracker.get(BSSID).unwrap();
+    assert_eq!((bss.first_seen, bss.last_seen), (start, now));
+
+    tracker.prune(Duration::from_secs(30), now);
+    assert_eq!(tracker.len(), 1);
+    assert!(tracker.get("00:11:22:33:44:55").is_none());
+    assert_eq!(tracker.iter().next().unwrap().ssid, "Corp");
+
+    tracker.clear();
+    assert!(tracker.is_empty());
+}
+
+#[test]
+fn bssid_formats() {
+    let mut tracker = Tracker::new();
+    tracker.ingest_at(
+        &[
+            wifi("aa-bb-cc-dd-ee-ff").signal(-60).build(),
+            // macOS may not report the BSSID
+            wifi("").ssid("Home").signal(-50).build(),
+            wifi("").ssid("Office").signal(-70).build(),
+        ],
+        SystemTime::UNIX_EPOCH,
+    );
+    tracker.ingest_at(&[wifi(BSSID).signal(-62).build()], SystemTime::UNIX_EPOCH);
+    assert_eq!(tracker.len(), 1);
+    assert_eq!(tracker.get("aa:bb:cc:dd:ee:ff").unwrap().seen_count, 2);
+    assert!(tracker.get("").is_none());
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::wifi;
+use wifi_scan::virtual_ap::{group_physical_aps, group_physical_aps_with};
+
+#[test]
+fn locally_administered() {
+    assert!(!wifi("00:11:22:33:44:55").build().is_locally_administered());
+    assert!(wifi("02:11:22:33:44:55").build().is_locally_administered());
+    assert!(wifi("DA:11:22:33:44:55").build().is_locally_administered());
+    assert!(!wifi("invalid").build().is_locally_administered());
+}
+
+#[test]
+fn group_virtual_aps() {
+    let wifis = vec![
+        wifi("00:11:22:33:44:52").ssid("Corp").channel(36).build(),
+        wifi("00:11:22:33:44:50").ssid("Guest").channel(36).build(),
+        // locally administered variant of the same radio
+        wifi("06:11:22:33:44:51").channel(36).build(),
+        wifi("06:11:22:33:44:53").ssid("Guest").build(),
+        // same radio address range on another channel
+        wifi("00:11:22:33:44:54").ssid("Corp").channel(1).build(),
+        wifi("00:11:22:33:44:60").ssid("Other").channel(36).build(),
+    ];
+
+    let aps = group_physical_aps(&wifis);
+    assert_eq!(aps.len(), 3);
+
+    assert_eq!(aps[0].base_mac, "00:11:22:33:44:50");
+    assert_eq!(aps[0].channel, 36);
+    assert_eq!(aps[0].bsses.len(), 4);
+    assert_eq!(aps[0].ssids(), vec!["Corp", "Guest"]);
+    assert!(aps[0].is_multi_bssid());
+    assert!(aps[0].has_locally_administered());
+
+    assert_eq!(aps[1].base_mac, "00:11:22:33:44:54");
+    assert_eq!(aps[1].channel, 1);
+    assert!(!aps[1].is_multi_bssid());
+    assert!(!aps[1].has_locally_administered());
+
+    assert_eq!(aps[2].ssids(), vec!["Other"]);
+}
+
+#[test]
+fn low_bits() {
+    let wifis = vec![
+        wifi("00:11:22:33:44:50").ssid("Corp").channel(6).build(),
+        wifi("00:11:22:33:44:60").ssid("Guest").channel(6).build(),
+    ];
+    assert_eq!(group_physical_aps(&wifis).len(), 2);
+    assert_eq!(group_physical_aps_with(&wifis, 8).len(), 1);
+    assert_eq!(group_physical_aps_with(&wifis, 0).len(), 2);
+}
+
+#[test]
+fn base_mac() {
+    // only locally administered BSSIDs, the channel is taken from the first known one
+    let wifis = vec![
+        wifi("0A:11:22:33:44:03").ssid("A").build(),
+        wifi("06:11:22:33:44:01").ssid("B").channel(11).build(),
+    ];
+    let aps = group_physical_aps(&wifis);
+    assert_eq!(aps.len(), 1);
+    assert_eq!(aps[0].base_mac, "06:11:22:33:44:01");
+    assert_eq!(aps[0].channel, 11);
+
+    // invalid BSSIDs are never grouped
+    let wifis = vec![
+        wifi("invalid").ssid("A").channel(1).build(),
+        wifi("invalid").ssid("B").channel(1).build(),
+    ];
+    let aps = group_physical_aps(&wifis);
+    assert_eq!(aps.len(), 2);
+    assert_eq!(aps[0].base_mac, "invalid");
+    assert!(group_physical_aps(&[]).is_empty());
+}
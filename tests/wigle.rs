@@ -0,0 +1,137 @@
+use std::time::{Duration, SystemTime};
+
+mod common;
+
+use common::{element, wifi};
+use wifi_scan::{
+    export::{
+        wigle::{auth_mode, DeviceInfo, WigleWriter},
+        GpsFix, Observation, ScanRecord,
+    },
+    WifiSecurity,
+};
+
+/// RSN element with CCMP as group and pairwise cipher and the given AKM
+fn rsn(akm: u8) -> Vec<u8> {
+    element(
+        48,
+        &[
+            0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00, 0x00, 0x0f, 0xac, 0x04, 0x01, 0x00,
+            0x00, 0x0f, 0xac, akm,
+        ],
+    )
+}
+
+fn device() -> DeviceInfo {
+    DeviceInfo {
+        app_release: "0.8.0".to_string(),
+        model: "Survey, Mk\n2".to_string(),
+        release: "linux".to_string(),
+        ..DeviceInfo::default()
+    }
+}
+
+fn fix() -> GpsFix {
+    GpsFix {
+        altitude: Some(34.5),
+        accuracy: Some(5.0),
+        timestamp: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_600)),
+        ..GpsFix::new(52.52, 13.405)
+    }
+}
+
+#[test]
+fn golden_output() {
+    let wifis = vec![
+        wifi("00-11-22-33-44-55")
+            .ssid("Home")
+            .channel(6)
+            .signal(-61)
+            .security(&[WifiSecurity::Wpa2PersonalPsk])
+            .elements(rsn(2))
+            .build(),
+        wifi("66:77:88:99:aa:bb")
+            .ssid("Cafe, \"Lobby\"")
+            .channel(6)
+            .signal(-61)
+            .security(&[WifiSecurity::Open])
+            .build(),
+        wifi("")
+            .ssid("No BSSID")
+            .channel(6)
+            .signal(-61)
+            .security(&[WifiSecurity::Open])
+            .build(),
+    ];
+
+    let mut writer = WigleWriter::new(Vec::new()).device(device());
+    writer.write(&wifis, &fix()).unwrap();
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "WigleWifi-1.4,appRelease=0.8.0,model=Survey  Mk 2,release=linux,device=,display=,board=,brand=\n\
+         MAC,SSID,AuthMode,FirstSeen,Channel,RSSI,CurrentLatitude,CurrentLongitude,AltitudeMeters,AccuracyMeters,Type\n\
+         00:11:22:33:44:55,Home,[WPA2-PSK-CCMP][ESS],2024-05-01 12:30:00,6,-61,52.52000000,13.40500000,34.5,5,WIFI\n\
+         66:77:88:99:aa:bb,\"Cafe, \"\"Lobby\"\"\",[ESS],2024-05-01 12:30:00,6,-61,52.52000000,13.40500000,34.5,5,WIFI\n"
+    );
+}
+
+#[test]
+fn header_only() {
+    let mut writer = WigleWriter::new(Vec::new());
+    writer.flush().unwrap();
+    // the device can't be changed once the header is written
+    let text = String::from_utf8(writer.device(device()).into_inner()).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("WigleWifi-1.4,appRelease="));
+    assert!(lines[0].contains(",model=wifi_scan,"));
+    assert!(lines[1].starts_with("MAC,SSID,AuthMode,"));
+}
+
+#[test]
+fn record_time_before_fix_time() {
+    let record = ScanRecord::new(
+        wifi("00:11:22:33:44:55")
+            .ssid("Home")
+            .channel(6)
+            .signal(-61)
+            .build(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_714_566_659),
+    );
+    let mut writer = WigleWriter::new(Vec::new());
+    writer
+        .write_observation(&Observation::new(record, fix()))
+        .unwrap();
+    let text = String::from_utf8(writer.into_inner()).unwrap();
+    assert!(text.contains(",2024-05-01 12:30:59,"));
+}
+
+#[test]
+fn auth_modes() {
+    let mixed = wifi("00:11:22:33:44:55")
+        .ssid("Home")
+        .channel(6)
+        .signal(-61)
+        .security(&[WifiSecurity::Wpa2PersonalPsk, WifiSecurity::Wpa3PersonalSae])
+        .build();
+    assert_eq!(auth_mode(&mixed), "[WPA2-PSK][WPA3-SAE][ESS]");
+
+    // OWE networks report an unknown security
+    let owe = wifi("00:11:22:33:44:55")
+        .channel(6)
+        .signal(-61)
+        .security(&[WifiSecurity::Unknown])
+        .elements(rsn(18))
+        .build();
+    assert_eq!(auth_mode(&owe), "[OWE][ESS]");
+
+    let other = wifi("00:11:22:33:44:55")
+        .channel(6)
+        .signal(-61)
+        .security(&[
+            WifiSecurity::Wep,
+            WifiSecurity::Other("[Vendor]".to_string()),
+        ])
+        .build();
+    assert_eq!(auth_mode(&other), "[WEP][Vendor][ESS]");
+}
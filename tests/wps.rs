@@ -0,0 +1,147 @@
+use wifi_scan::{
+    wps::{config_methods, WpsInfo, WpsState},
+    Wifi,
+};
+
+fn attribute(kind: u16, value: &[u8]) -> Vec<u8> {
+    let mut attribute = kind.to_be_bytes().to_vec();
+    attribute.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    attribute.extend_from_slice(value);
+    attribute
+}
+
+/// Wraps WPS attributes in a vendor specific element with the Microsoft OUI
+fn wps_element(attributes: &[u8]) -> Vec<u8> {
+    let mut element = vec![221, (attributes.len() + 4) as u8, 0x00, 0x50, 0xf2, 0x04];
+    element.extend_from_slice(attributes);
+    element
+}
+
+/// Attributes of a configured home router with label and virtual push button setup
+fn router_attributes() -> Vec<u8> {
+    let mut attributes = attribute(0x104a, &[0x10]);
+    attributes.extend(attribute(0x1044, &[0x02]));
+    attributes.extend(attribute(0x1057, &[0x00]));
+    attributes.extend(attribute(
+        0x1047,
+        &[
+            0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+            0xcd, 0xef,
+        ],
+    ));
+    attributes.extend(attribute(0x1021, b"ACME Networks"));
+    attributes.extend(attribute(0x1023, b"Router"));
+    attributes.extend(attribute(0x1024, b"R-1000\0"));
+    attributes.extend(attribute(0x1042, b" 0123456789 "));
+    attributes.extend(attribute(0x1011, b"Living Room"));
+    attributes.extend(attribute(0x1008, &[0x02, 0x84]));
+    attributes
+}
+
+#[test]
+fn router() {
+    let mut ies = vec![0, 4, b'H', b'o', b'm', b'e'];
+    ies.extend(wps_element(&router_attributes()));
+
+    let wps = WpsInfo::from_elements(&ies).unwrap();
+    assert_eq!(
+        wps,
+        WpsInfo {
+            state: WpsState::Configured,
+            ap_setup_locked: false,
+            selected_registrar: false,
+            device_name: "Living Room".to_string(),
+            manufacturer: "ACME Networks".to_string(),
+            model: "Router".to_string(),
+            model_number: "R-1000".to_string(),
+            serial_number: "0123456789".to_string(),
+            uuid: "12345678-9abc-def0-0123-456789abcdef".to_string(),
+            config_methods: Some(0x0284),
+        }
+    );
+    assert!(wps.supports(config_methods::LABEL));
+    assert!(wps.supports(config_methods::VIRTUAL_PUSH_BUTTON));
+    assert!(!wps.supports(config_methods::PHYSICAL_PUSH_BUTTON));
+    assert!(wps.is_pin_enabled());
+    assert_eq!(wps.state.to_string(), "Configured");
+
+    let mut wifi = Wifi::default();
+    wifi.set_information_elements(ies);
+    assert!(wifi.is_wps_enabled());
+    assert_eq!(wifi.wps(), Some(wps));
+}
+
+#[test]
+fn attributes_split_over_elements() {
+    let attributes = router_attributes();
+    let (first, second) = attributes.split_at(30);
+    let mut ies = wps_element(first);
+    // unrelated vendor element in between
+    ies.extend_from_slice(&[221, 5, 0x00, 0x50, 0xf2, 0x02, 0x01]);
+    ies.extend(wps_element(second));
+
+    let wps = WpsInfo::from_elements(&ies).unwrap();
+    assert_eq!(wps.manufacturer, "ACME Networks");
+    assert_eq!(wps.device_name, "Living Room");
+}
+
+#[test]
+fn pin_availability() {
+    // locked after repeated PIN failures
+    let mut attributes = attribute(0x1044, &[0x02]);
+    attributes.extend(attribute(0x1057, &[0x01]));
+    attributes.extend(attribute(0x1008, &[0x00, 0x04]));
+    let locked = WpsInfo::from_elements(&wps_element(&attributes)).unwrap();
+    assert!(locked.ap_setup_locked);
+    assert!(!locked.is_pin_enabled());
+
+    // push button only
+    let mut attributes = attribute(0x1044, &[0x01]);
+    attributes.extend(attribute(0x1008, &[0x04, 0x80]));
+    let push_button = WpsInfo::from_elements(&wps_element(&attributes)).unwrap();
+    assert_eq!(push_button.state, WpsState::Unconfigured);
+    assert!(!push_button.is_pin_enabled());
+
+    // config methods of the selected registrar are used if the AP does not list its own
+    let mut attributes = attribute(0x1041, &[0x01]);
+    attributes.extend(attribute(0x1053, &[0x01, 0x00]));
+    let registrar = WpsInfo::from_elements(&wps_element(&attributes)).unwrap();
+    assert!(registrar.selected_registrar);
+    assert_eq!(registrar.config_methods, Some(config_methods::KEYPAD));
+    assert!(registrar.is_pin_enabled());
+
+    // no config methods, the AP PIN is mandatory
+    let minimal = WpsInfo::from_elements(&wps_element(&attribute(0x104a, &[0x10]))).unwrap();
+    assert_eq!(minimal.state, WpsState::Unknown);
+    assert!(minimal.is_pin_enabled());
+}
+
+#[test]
+fn malformed_attributes() {
+    assert_eq!(WpsInfo::from_elements(&[]), None);
+    // other vendor type of the same OUI
+    assert_eq!(
+        WpsInfo::from_elements(&[221, 5, 0x00, 0x50, 0xf2, 0x01, 0x01]),
+        None
+    );
+
+    // attribute length beyond the element stops parsing
+    let mut attributes = attribute(0x1021, b"ACME");
+    attributes.extend_from_slice(&[0x10, 0x11, 0x00, 0x40, b'X']);
+    let wps = WpsInfo::from_elements(&wps_element(&attributes)).unwrap();
+    assert_eq!(wps.manufacturer, "ACME");
+    assert_eq!(wps.device_name, "");
+
+    // invalid UUID length, short config methods and invalid UTF-8
+    let mut attributes = attribute(0x1047, &[0x12, 0x34]);
+    attributes.extend(attribute(0x1008, &[0x01]));
+    attributes.extend(attribute(0x1023, &[b'A', 0xff]));
+    let wps = WpsInfo::from_elements(&wps_element(&attributes)).unwrap();
+    assert_eq!(wps.uuid, "");
+    assert_eq!(wps.config_methods, None);
+    assert_eq!(wps.model, "A\u{fffd}");
+
+    // empty WPS element
+    let wps = WpsInfo::from_elements(&wps_element(&[])).unwrap();
+    assert_eq!(wps, WpsInfo::default());
+}